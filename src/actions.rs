@@ -0,0 +1,409 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::mpsc;
+
+use crate::app::{Message, RunStatus};
+use crate::config::{Config, OpenConfig};
+use crate::git;
+
+/// An operation that can be run across many repositories at once.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BulkAction {
+    Fetch,
+    Pull,
+    Push,
+    Checkout(String),
+    RunCommand(String),
+    OpenInEditor,
+}
+
+impl BulkAction {
+    /// Entries of the bulk action menu, in display order. Actions taking an
+    /// argument start out empty and are filled in by a prompt.
+    pub fn menu() -> Vec<BulkAction> {
+        vec![
+            Self::Fetch,
+            Self::Pull,
+            Self::Push,
+            Self::Checkout(String::new()),
+            Self::RunCommand(String::new()),
+            Self::OpenInEditor,
+        ]
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Self::Fetch => "Fetch".to_string(),
+            Self::Pull => "Pull".to_string(),
+            Self::Push => "Push".to_string(),
+            Self::Checkout(b) if b.is_empty() => "Checkout branch".to_string(),
+            Self::Checkout(b) => format!("Checkout {b}"),
+            Self::RunCommand(c) if c.is_empty() => "Run command".to_string(),
+            Self::RunCommand(c) => format!("Run `{c}`"),
+            Self::OpenInEditor => "Open in editor".to_string(),
+        }
+    }
+
+    /// Prompt title for actions that need an argument.
+    pub fn prompt(&self) -> Option<&'static str> {
+        match self {
+            Self::Checkout(_) => Some("Branch"),
+            Self::RunCommand(_) => Some("Command"),
+            _ => None,
+        }
+    }
+
+    /// Fill in the argument collected by [`Self::prompt`].
+    pub fn with_input(self, input: String) -> Self {
+        match self {
+            Self::Checkout(_) => Self::Checkout(input),
+            Self::RunCommand(_) => Self::RunCommand(input),
+            other => other,
+        }
+    }
+
+    /// Whether the action can change branches or the working tree, so the
+    /// repo should be rescanned afterwards.
+    pub fn modifies_repo(&self) -> bool {
+        !matches!(self, Self::OpenInEditor)
+    }
+
+    /// Run the action in one repository. Blocks; call from a blocking task.
+    /// Shell commands are refused: they stream through `App::run_command`.
+    pub fn run(&self, path: &Path, config: &Config) -> Result<String, String> {
+        match self {
+            Self::Fetch => git::fetch(path),
+            Self::Pull => git::pull(path),
+            Self::Push => git::push(path),
+            Self::Checkout(branch) => git::checkout_branch(path, branch),
+            // Output is streamed to the Output tab, which a blocking run can't do
+            Self::RunCommand(_) => Err("shell commands run through the command runner".to_string()),
+            Self::OpenInEditor => open_in_editor(path, &config.open),
+        }
+    }
+}
+
+/// Run `command` through `sh -c` in `path`, forwarding each stdout/stderr
+/// line as a [`Message::CommandOutput`]. Returns once the process exits.
+pub async fn stream_command(
+    path: &Path,
+    command: &str,
+    tx: &mpsc::UnboundedSender<Message>,
+) -> RunStatus {
+    let mut child = match tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(c) => c,
+        Err(e) => return RunStatus::Failed(e.to_string()),
+    };
+
+    let stdout = child
+        .stdout
+        .take()
+        .map(|out| forward_lines(out, path, false, tx));
+    let stderr = child
+        .stderr
+        .take()
+        .map(|err| forward_lines(err, path, true, tx));
+    let status = child.wait().await;
+    if let Some(task) = stdout {
+        let _ = task.await;
+    }
+    if let Some(task) = stderr {
+        let _ = task.await;
+    }
+
+    match status {
+        Ok(status) => match status.code() {
+            Some(code) => RunStatus::Exited(code),
+            None => RunStatus::Failed("killed by signal".to_string()),
+        },
+        Err(e) => RunStatus::Failed(e.to_string()),
+    }
+}
+
+fn forward_lines<R>(
+    reader: R,
+    path: &Path,
+    stderr: bool,
+    tx: &mpsc::UnboundedSender<Message>,
+) -> tokio::task::JoinHandle<()>
+where
+    R: tokio::io::AsyncRead + Unpin + Send + 'static,
+{
+    let path = path.to_path_buf();
+    let tx = tx.clone();
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(reader).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let _ = tx.send(Message::CommandOutput {
+                path: path.clone(),
+                line,
+                stderr,
+            });
+        }
+    })
+}
+
+/// A program to run outside the dashboard. Interactive programs need the
+/// terminal, so the TUI is suspended while they run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalCommand {
+    pub program: String,
+    pub args: Vec<String>,
+    pub cwd: PathBuf,
+    /// Suspend the TUI and wait for the program to exit.
+    pub suspend: bool,
+}
+
+/// GUI editors that open their own window.
+const GUI_EDITORS: &[&str] = &[
+    "code",
+    "codium",
+    "cursor",
+    "subl",
+    "zed",
+    "idea",
+    "goland",
+    "rustrover",
+    "clion",
+    "fleet",
+    "gvim",
+    "mvim",
+    "atom",
+    "mate",
+];
+
+/// The editor command from config, `$VISUAL` or `$EDITOR`, split into
+/// program and arguments.
+pub fn editor_command(config: &OpenConfig) -> Option<Vec<String>> {
+    config
+        .editor
+        .clone()
+        .into_iter()
+        .chain(
+            ["VISUAL", "EDITOR"]
+                .iter()
+                .filter_map(|var| std::env::var(var).ok()),
+        )
+        .find(|v| !v.trim().is_empty())
+        .map(|v| v.split_whitespace().map(String::from).collect())
+}
+
+fn program_name(program: &str) -> &str {
+    Path::new(program)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(program)
+}
+
+/// Open `repo`, or `file` at `line` inside it, in the configured editor.
+pub fn editor_invocation(
+    config: &OpenConfig,
+    repo: &Path,
+    file: Option<(&str, u32)>,
+) -> Result<ExternalCommand, String> {
+    let cmd = editor_command(config).ok_or_else(|| "$VISUAL / $EDITOR not set".to_string())?;
+    let name = program_name(&cmd[0]);
+    let gui = config
+        .gui_editor
+        .unwrap_or_else(|| GUI_EDITORS.contains(&name));
+
+    let mut args = cmd[1..].to_vec();
+    match file {
+        Some((file, line)) => {
+            let file = repo.join(file).display().to_string();
+            match name {
+                "code" | "codium" | "cursor" => {
+                    args.push("-g".to_string());
+                    args.push(format!("{file}:{line}"));
+                }
+                "subl" | "zed" | "hx" | "helix" | "idea" | "goland" | "rustrover" | "clion" => {
+                    args.push(format!("{file}:{line}"));
+                }
+                _ => {
+                    args.push(format!("+{line}"));
+                    args.push(file);
+                }
+            }
+        }
+        None => args.push(repo.display().to_string()),
+    }
+
+    Ok(ExternalCommand {
+        program: cmd[0].clone(),
+        args,
+        cwd: repo.to_path_buf(),
+        suspend: !gui,
+    })
+}
+
+/// Edit a scratch file and wait for the editor to exit, so its contents
+/// can be read back. GUI editors need their wait flag (e.g. `code -w`).
+pub fn edit_file_invocation(
+    config: &OpenConfig,
+    file: &Path,
+    cwd: &Path,
+) -> Result<ExternalCommand, String> {
+    let cmd = editor_command(config).ok_or_else(|| "$VISUAL / $EDITOR not set".to_string())?;
+    let mut args = cmd[1..].to_vec();
+    args.push(file.display().to_string());
+    Ok(ExternalCommand {
+        program: cmd[0].clone(),
+        args,
+        cwd: cwd.to_path_buf(),
+        suspend: true,
+    })
+}
+
+/// An interactive subshell in the repository directory.
+pub fn shell_invocation(config: &OpenConfig, repo: &Path) -> ExternalCommand {
+    let program = config
+        .shell
+        .clone()
+        .or_else(|| std::env::var("SHELL").ok())
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or_else(|| "sh".to_string());
+    ExternalCommand {
+        program,
+        args: Vec::new(),
+        cwd: repo.to_path_buf(),
+        suspend: true,
+    }
+}
+
+/// tmux session name for a repo; tmux reserves `.` and `:`.
+pub fn tmux_session_name(repo_name: &str) -> String {
+    repo_name.replace(['.', ':'], "_")
+}
+
+/// Create or attach a tmux session named after the repo. Inside tmux the
+/// client switches to it; outside, the dashboard is suspended until the
+/// session is detached.
+pub fn tmux_invocation(repo_name: &str, repo: &Path) -> Result<ExternalCommand, String> {
+    let session = tmux_session_name(repo_name);
+    let cwd = repo.display().to_string();
+
+    if std::env::var_os("TMUX").is_none() {
+        return Ok(ExternalCommand {
+            program: "tmux".to_string(),
+            args: ["new-session", "-A", "-s", &session, "-c", &cwd]
+                .map(String::from)
+                .to_vec(),
+            cwd: repo.to_path_buf(),
+            suspend: true,
+        });
+    }
+
+    let exists = Command::new("tmux")
+        .args(["has-session", "-t", &format!("={session}")])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| format!("tmux: {e}"))?
+        .success();
+    if !exists {
+        let created = Command::new("tmux")
+            .args(["new-session", "-d", "-s", &session, "-c", &cwd])
+            .status()
+            .map_err(|e| format!("tmux: {e}"))?;
+        if !created.success() {
+            return Err(format!("tmux could not create session {session}"));
+        }
+    }
+    Ok(ExternalCommand {
+        program: "tmux".to_string(),
+        args: ["switch-client", "-t", &format!("={session}")]
+            .map(String::from)
+            .to_vec(),
+        cwd: repo.to_path_buf(),
+        suspend: false,
+    })
+}
+
+/// Launch the editor on the repo without waiting for it. Bulk opens only
+/// make sense for GUI editors, so this never takes over the terminal.
+fn open_in_editor(path: &Path, config: &OpenConfig) -> Result<String, String> {
+    let cmd = editor_invocation(config, path, None)?;
+    if cmd.suspend {
+        return Err(format!(
+            "{} runs in the terminal; open repos one at a time",
+            cmd.program
+        ));
+    }
+    Command::new(&cmd.program)
+        .args(&cmd.args)
+        .current_dir(&cmd.cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("{}: {e}", cmd.program))?;
+    Ok(format!("opened in {}", cmd.program))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_stream_command_captures_both_streams() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let dir = std::env::temp_dir();
+        let status = stream_command(&dir, "echo out; echo err >&2; exit 3", &tx).await;
+        assert_eq!(status, RunStatus::Exited(3));
+
+        drop(tx);
+        let mut lines = Vec::new();
+        while let Some(msg) = rx.recv().await {
+            if let Message::CommandOutput { line, stderr, .. } = msg {
+                lines.push((line, stderr));
+            }
+        }
+        lines.sort();
+        assert_eq!(
+            lines,
+            vec![("err".to_string(), true), ("out".to_string(), false)]
+        );
+    }
+
+    #[test]
+    fn test_editor_invocation_line_syntax() {
+        let repo = Path::new("/src/app");
+        let vim = OpenConfig {
+            editor: Some("nvim".to_string()),
+            ..Default::default()
+        };
+        let cmd = editor_invocation(&vim, repo, Some(("src/main.rs", 12))).unwrap();
+        assert_eq!(cmd.args, vec!["+12", "/src/app/src/main.rs"]);
+        assert!(cmd.suspend);
+
+        let code = OpenConfig {
+            editor: Some("code -w".to_string()),
+            ..Default::default()
+        };
+        let cmd = editor_invocation(&code, repo, Some(("src/main.rs", 12))).unwrap();
+        assert_eq!(cmd.args, vec!["-w", "-g", "/src/app/src/main.rs:12"]);
+        assert!(!cmd.suspend);
+    }
+
+    #[test]
+    fn test_tmux_session_name() {
+        assert_eq!(tmux_session_name("my.repo:x"), "my_repo_x");
+    }
+
+    #[test]
+    fn test_run_refuses_shell_commands() {
+        let action = BulkAction::RunCommand("true".to_string());
+        assert!(action.run(Path::new("."), &Config::default()).is_err());
+    }
+}
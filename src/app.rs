@@ -1,12 +1,20 @@
 use std::path::PathBuf;
 
+use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::widgets::TableState;
 use tokio::sync::mpsc;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-use crate::github;
-use crate::repo::{GitHubData, RepoInfo};
+use crate::actions::{self, BulkAction, ExternalCommand};
+use crate::config::Config;
+use crate::git::{self, StaleBranch};
+use crate::github::{
+    self, ActionOutcome, BranchPr, InboxItem, IssueComment, IssueTemplate, ItemAction, ItemDetail,
+    ItemFilter, ItemPage, JobLog, ListedItem, MergeMethod, MergeRequest, MergeSettings, NewIssue,
+    NotificationThread, PrDetail, Release, ReleasesInfo, RemoteRepo, WorkflowRun,
+};
+use crate::repo::{GitHubData, GitHubItem, RepoInfo};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ActivePane {
@@ -20,32 +28,571 @@ pub enum DetailTab {
     Commits,
     Issues,
     Prs,
+    Actions,
+    Releases,
+    Output,
 }
 
 impl DetailTab {
-    pub fn next(self) -> Self {
+    /// All tabs in tab-bar order.
+    pub const ALL: [DetailTab; 7] = [
+        Self::Changes,
+        Self::Commits,
+        Self::Issues,
+        Self::Prs,
+        Self::Actions,
+        Self::Releases,
+        Self::Output,
+    ];
+
+    pub fn title(self) -> &'static str {
         match self {
-            Self::Changes => Self::Commits,
-            Self::Commits => Self::Issues,
-            Self::Issues => Self::Prs,
-            Self::Prs => Self::Changes,
+            Self::Changes => "Changes",
+            Self::Commits => "Commits",
+            Self::Issues => "Issues",
+            Self::Prs => "PRs",
+            Self::Actions => "Actions",
+            Self::Releases => "Releases",
+            Self::Output => "Output",
         }
     }
 
+    fn index(self) -> usize {
+        Self::ALL.iter().position(|t| *t == self).unwrap_or(0)
+    }
+
+    pub fn next(self) -> Self {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
     pub fn prev(self) -> Self {
-        match self {
-            Self::Changes => Self::Prs,
-            Self::Commits => Self::Changes,
-            Self::Issues => Self::Commits,
-            Self::Prs => Self::Issues,
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+/// An issue or pull request opened from the Issues or PRs tab. Its
+/// details load in the background and replace the list until closed
+/// with Esc.
+#[derive(Debug)]
+pub struct ItemView {
+    pub path: PathBuf,
+    pub number: u64,
+    pub is_pr: bool,
+    pub detail: Option<ItemDetail>,
+    pub error: Option<String>,
+    /// A further page of comments is being fetched.
+    pub loading_more: bool,
+    /// Scratch file a new comment is being written in.
+    pub comment_file: Option<PathBuf>,
+}
+
+/// Workflow runs of the selected repo in the Actions tab, optionally with
+/// one run's failed job logs open in place of the list.
+#[derive(Debug)]
+pub struct ActionsView {
+    pub path: PathBuf,
+    pub runs: Option<Vec<WorkflowRun>>,
+    pub error: Option<String>,
+    pub cursor: usize,
+    pub log: Option<RunLogView>,
+}
+
+#[derive(Debug)]
+pub struct RunLogView {
+    pub run_id: u64,
+    pub title: String,
+    /// `None` while loading.
+    pub jobs: Option<Result<Vec<JobLog>, String>>,
+}
+
+/// Releases of the selected repo and the next-release preview, shown in
+/// the Releases tab.
+#[derive(Debug)]
+pub struct ReleasesView {
+    pub path: PathBuf,
+    /// `None` while loading.
+    pub info: Option<Result<Box<ReleasesInfo>, String>>,
+    pub creating: bool,
+}
+
+/// The Issues or PRs tab's filtered list, loaded a page at a time as the
+/// cursor nears its end.
+#[derive(Debug)]
+pub struct ItemList {
+    pub path: PathBuf,
+    pub is_pr: bool,
+    pub filter: ItemFilter,
+    pub items: Vec<ListedItem>,
+    /// Matching items in all pages; `None` until the first page arrives.
+    pub total: Option<u64>,
+    /// The page to load next, or `None` after the last one.
+    pub next_page: Option<u32>,
+    pub loading: bool,
+    pub error: Option<String>,
+}
+
+/// Load the next page once the cursor is this close to the loaded end.
+const ITEM_LIST_PREFETCH: usize = 5;
+
+/// The "My work" inbox: open items across all scanned repos that need the
+/// user, newest activity first. Replaces the dashboard while open.
+#[derive(Debug, Default)]
+pub struct Inbox {
+    pub items: Vec<InboxItem>,
+    pub cursor: usize,
+    /// Hosts whose results are still loading.
+    pub pending: usize,
+    pub errors: Vec<String>,
+}
+
+/// A modal dialog drawn over the dashboard. While one is open it receives
+/// all key input.
+#[derive(Debug)]
+pub enum Overlay {
+    BranchCleanup(BranchCleanup),
+    BulkMenu { cursor: usize },
+    Prompt(Prompt),
+    BulkProgress(BulkProgress),
+    RemoteRepos(RemoteRepoList),
+    IssueForm(Box<IssueForm>),
+    LabelPicker(LabelPicker),
+    MergeForm(Box<MergeForm>),
+}
+
+/// Labels to set on the open issue or PR, chosen from the repo's labels.
+#[derive(Debug)]
+pub struct LabelPicker {
+    pub path: PathBuf,
+    pub number: u64,
+    pub choices: Vec<Choice>,
+    pub cursor: usize,
+    pub loading: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MergeField {
+    Method,
+    Title,
+    Message,
+    DeleteBranch,
+    Submit,
+}
+
+/// The merge form for the open PR. The methods offered are the ones the
+/// repo allows, loaded when the form opens; the message is written in
+/// `$EDITOR`.
+#[derive(Debug)]
+pub struct MergeForm {
+    pub path: PathBuf,
+    pub number: u64,
+    pub pr: Box<PrDetail>,
+    pub owner: String,
+    pub field: MergeField,
+    pub methods: Vec<MergeMethod>,
+    pub method: MergeMethod,
+    pub title: String,
+    pub message: String,
+    /// `None` when the head branch lives in a fork, out of our reach.
+    pub delete_branch: Option<bool>,
+    /// Reasons GitHub is likely to refuse the merge.
+    pub blockers: Vec<String>,
+    pub loading_settings: bool,
+    pub submitting: bool,
+    pub error: Option<String>,
+    /// Scratch file the message is open in while the editor runs.
+    pub message_file: Option<PathBuf>,
+}
+
+impl MergeForm {
+    fn new(path: PathBuf, pr: Box<PrDetail>, owner: String) -> Self {
+        let method = MergeMethod::Merge;
+        let (title, message) = method.default_commit(&pr, &owner);
+        Self {
+            path,
+            number: pr.number,
+            blockers: pr.merge_blockers(),
+            delete_branch: (!pr.head.contains(':')).then_some(false),
+            pr,
+            owner,
+            field: MergeField::Method,
+            methods: MergeMethod::ALL.to_vec(),
+            method,
+            title,
+            message,
+            loading_settings: true,
+            submitting: false,
+            error: None,
+            message_file: None,
+        }
+    }
+
+    fn fields(&self) -> Vec<MergeField> {
+        let mut fields = vec![MergeField::Method];
+        if self.method.takes_message() {
+            fields.extend([MergeField::Title, MergeField::Message]);
+        }
+        if self.delete_branch.is_some() {
+            fields.push(MergeField::DeleteBranch);
+        }
+        fields.push(MergeField::Submit);
+        fields
+    }
+
+    fn move_field(&mut self, forward: bool) {
+        let fields = self.fields();
+        let i = fields.iter().position(|f| *f == self.field).unwrap_or(0);
+        let next = if forward {
+            (i + 1) % fields.len()
+        } else {
+            (i + fields.len() - 1) % fields.len()
+        };
+        self.field = fields[next];
+    }
+
+    /// Switch to the next allowed method, replacing the title and message
+    /// with its defaults unless they were edited.
+    fn cycle_method(&mut self, forward: bool) {
+        let Some(i) = self.methods.iter().position(|m| *m == self.method) else {
+            return;
+        };
+        let len = self.methods.len();
+        let next = self.methods[if forward {
+            (i + 1) % len
+        } else {
+            (i + len - 1) % len
+        }];
+        self.set_method(next);
+    }
+
+    fn set_method(&mut self, method: MergeMethod) {
+        let (old_title, old_message) = self.method.default_commit(&self.pr, &self.owner);
+        let (title, message) = method.default_commit(&self.pr, &self.owner);
+        if self.title == old_title {
+            self.title = title;
+        }
+        if self.message == old_message {
+            self.message = message;
+        }
+        self.method = method;
+    }
+
+    /// Offer only the methods the repo allows, keeping the chosen one if
+    /// it is among them.
+    fn set_settings(&mut self, settings: MergeSettings) {
+        if settings.methods.is_empty() {
+            self.error = Some("The repository allows no merge method".to_string());
+            return;
+        }
+        if !settings.methods.contains(&self.method) {
+            self.set_method(settings.methods[0]);
         }
+        self.methods = settings.methods;
+        if let Some(delete) = &mut self.delete_branch {
+            *delete = settings.delete_branch_on_merge;
+        }
+    }
+
+    fn request(&self) -> MergeRequest {
+        MergeRequest {
+            method: self.method,
+            title: self.title.trim().to_string(),
+            message: self.message.trim_end().to_string(),
+            sha: self.pr.head_sha.clone(),
+            delete_branch: (self.delete_branch == Some(true)).then(|| self.pr.head.clone()),
+        }
+    }
+}
+
+/// A label or assignee offered by the new issue form.
+#[derive(Debug, Clone)]
+pub struct Choice {
+    pub name: String,
+    pub selected: bool,
+}
+
+/// Mark `names` as selected, adding any the list doesn't know yet.
+fn select_choices(choices: &mut Vec<Choice>, names: &[String]) {
+    for name in names {
+        match choices
+            .iter_mut()
+            .find(|c| c.name.eq_ignore_ascii_case(name))
+        {
+            Some(choice) => choice.selected = true,
+            None => choices.push(Choice {
+                name: name.clone(),
+                selected: true,
+            }),
+        }
+    }
+}
+
+fn selected_names(choices: &[Choice]) -> Vec<String> {
+    choices
+        .iter()
+        .filter(|c| c.selected)
+        .map(|c| c.name.clone())
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IssueField {
+    Template,
+    Title,
+    Body,
+    Labels,
+    Assignees,
+    Submit,
+}
+
+/// The new issue form. The body is written in `$EDITOR`; labels and
+/// assignees come from the repo's lists, loaded when the form opens.
+#[derive(Debug)]
+pub struct IssueForm {
+    pub path: PathBuf,
+    pub repo_name: String,
+    pub field: IssueField,
+    pub title: String,
+    pub body: String,
+    pub templates: Vec<IssueTemplate>,
+    pub template_cursor: usize,
+    pub labels: Vec<Choice>,
+    pub label_cursor: usize,
+    pub assignees: Vec<Choice>,
+    pub assignee_cursor: usize,
+    pub loading_options: bool,
+    pub submitting: bool,
+    pub error: Option<String>,
+    /// Scratch file the body is open in while the editor runs.
+    pub body_file: Option<PathBuf>,
+}
+
+impl IssueForm {
+    fn fields(&self) -> Vec<IssueField> {
+        let mut fields = vec![
+            IssueField::Title,
+            IssueField::Body,
+            IssueField::Labels,
+            IssueField::Assignees,
+            IssueField::Submit,
+        ];
+        if !self.templates.is_empty() {
+            fields.insert(0, IssueField::Template);
+        }
+        fields
+    }
+
+    fn move_field(&mut self, forward: bool) {
+        let fields = self.fields();
+        let i = fields.iter().position(|f| *f == self.field).unwrap_or(0);
+        let next = if forward {
+            (i + 1) % fields.len()
+        } else {
+            (i + fields.len() - 1) % fields.len()
+        };
+        self.field = fields[next];
+    }
+
+    fn apply_template(&mut self) {
+        let Some(template) = self.templates.get(self.template_cursor) else {
+            return;
+        };
+        self.title = template.title.clone();
+        self.body = template.body.clone();
+        for choice in self.labels.iter_mut().chain(self.assignees.iter_mut()) {
+            choice.selected = false;
+        }
+        let (labels, assignees) = (template.labels.clone(), template.assignees.clone());
+        select_choices(&mut self.labels, &labels);
+        select_choices(&mut self.assignees, &assignees);
+        self.field = IssueField::Title;
+    }
+
+    /// Replace the label and assignee lists with the repo's, keeping
+    /// whatever was already selected.
+    fn set_options(&mut self, labels: Vec<String>, assignees: Vec<String>) {
+        let to_choices = |names: Vec<String>| -> Vec<Choice> {
+            names
+                .into_iter()
+                .map(|name| Choice {
+                    name,
+                    selected: false,
+                })
+                .collect()
+        };
+        let (keep_labels, keep_assignees) = (
+            selected_names(&self.labels),
+            selected_names(&self.assignees),
+        );
+        self.labels = to_choices(labels);
+        self.assignees = to_choices(assignees);
+        select_choices(&mut self.labels, &keep_labels);
+        select_choices(&mut self.assignees, &keep_assignees);
+        self.label_cursor = 0;
+        self.assignee_cursor = 0;
+    }
+
+    fn new_issue(&self) -> NewIssue {
+        NewIssue {
+            title: self.title.trim().to_string(),
+            body: self.body.clone(),
+            labels: selected_names(&self.labels),
+            assignees: selected_names(&self.assignees),
+        }
+    }
+}
+
+/// A single-line text input.
+#[derive(Debug)]
+pub struct Prompt {
+    pub title: String,
+    pub input: String,
+    pub action: PromptAction,
+    /// Position while browsing command history with the arrow keys.
+    pub history_pos: Option<usize>,
+}
+
+impl Prompt {
+    pub fn new(title: impl Into<String>, action: PromptAction) -> Self {
+        Self {
+            title: title.into(),
+            input: String::new(),
+            action,
+            history_pos: None,
+        }
+    }
+
+    pub fn with_input(mut self, input: String) -> Self {
+        self.input = input;
+        self
+    }
+}
+
+/// What to do with a prompt's input once it is submitted.
+#[derive(Debug)]
+pub enum PromptAction {
+    MarkMatching,
+    Bulk(BulkAction),
+    CloneSource,
+    CloneDest {
+        url: String,
+    },
+    ListOwner,
+    RunCommand,
+    DraftRelease,
+    ItemFilter,
+    /// Check out a PR as the branch typed, in place.
+    CheckoutPr {
+        number: u64,
+        head: String,
+    },
+    /// Check out a PR in a new worktree at the path typed.
+    PrWorktree {
+        number: u64,
+        head: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RunStatus {
+    Running,
+    Exited(i32),
+    /// Could not start, or was killed by a signal.
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct OutputLine {
+    pub text: String,
+    pub stderr: bool,
+}
+
+/// The latest shell command run in a repository and its captured output.
+#[derive(Debug, Clone)]
+pub struct CommandRun {
+    pub command: String,
+    pub output: Vec<OutputLine>,
+    pub status: RunStatus,
+}
+
+#[derive(Debug, Clone)]
+pub struct RemoteRepoEntry {
+    pub repo: RemoteRepo,
+    pub selected: bool,
+}
+
+/// An owner's GitHub repositories that are not cloned under the scan root.
+#[derive(Debug, Default)]
+pub struct RemoteRepoList {
+    pub owner: String,
+    pub entries: Vec<RemoteRepoEntry>,
+    pub cursor: usize,
+    pub loading: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BulkState {
+    Running,
+    Done(String),
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct BulkEntry {
+    pub path: PathBuf,
+    pub name: String,
+    pub state: BulkState,
+    /// Latest progress report while running.
+    pub progress: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct BulkProgress {
+    pub label: String,
+    pub entries: Vec<BulkEntry>,
+    pub scroll: usize,
+}
+
+impl BulkProgress {
+    pub fn finished(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| e.state != BulkState::Running)
+            .count()
     }
+}
+
+/// Maximum number of repositories a bulk action runs in at once.
+const BULK_CONCURRENCY: usize = 8;
+/// Output lines kept per repository for a command run.
+const MAX_OUTPUT_LINES: usize = 5000;
+/// Previous shell commands remembered for the command prompt.
+const MAX_COMMAND_HISTORY: usize = 100;
+
+#[derive(Debug, Clone)]
+pub struct CleanupEntry {
+    pub branch: StaleBranch,
+    pub selected: bool,
+    pub error: Option<String>,
+}
 
+#[derive(Debug, Default)]
+pub struct BranchCleanup {
+    /// Whether the cleanup spans every repo or just the selected one.
+    pub all_repos: bool,
+    pub entries: Vec<CleanupEntry>,
+    pub cursor: usize,
+    pub loading: bool,
+    pub status: Option<String>,
+    /// Unmerged branches are selected and Enter was pressed: waiting for
+    /// `y` before deleting them and their commits.
+    pub confirm_unmerged: bool,
 }
 
 #[derive(Debug)]
 pub enum Message {
     Quit,
+    Key(KeyEvent),
     MoveUp,
     MoveDown,
     Refresh,
@@ -54,27 +601,197 @@ pub enum Message {
     ForceRetryGitHub,
     Tick,
     SwitchPane,
+    /// Enter: open the highlighted item in the detail pane, or switch panes.
+    Activate,
     FocusList,
     NextTab,
     PrevTab,
-    Click { column: u16, row: u16 },
+    Click {
+        column: u16,
+        row: u16,
+    },
     ReposScanned(Vec<RepoInfo>),
-    GitHubDataReceived { path: PathBuf, data: GitHubData },
-    GitHubError { path: PathBuf, error: String },
+    GitHubDataReceived {
+        path: PathBuf,
+        data: GitHubData,
+    },
+    GitHubError {
+        path: PathBuf,
+        error: String,
+    },
+    /// A batch query failed, so its repos were fetched over REST instead.
+    GraphQlFailed {
+        host: String,
+        error: String,
+    },
+    /// A background prefetch got its turn and is fetching these repos.
+    GitHubPrefetchStarted(Vec<PathBuf>),
+    /// Tokens of the `gh` CLI and git credential helpers were looked up.
+    TokensDiscovered,
+    OpenBranchCleanup {
+        all_repos: bool,
+    },
+    StaleBranchesFound(Vec<StaleBranch>),
+    BranchesDeleted(Vec<(StaleBranch, Result<(), String>)>),
+    RepoUpdated(Box<RepoInfo>),
+    ToggleMark,
+    ToggleMarkAll,
+    OpenMarkPrompt,
+    OpenBulkMenu,
+    BulkResult {
+        path: PathBuf,
+        result: Result<String, String>,
+    },
+    BulkProgressUpdate {
+        path: PathBuf,
+        progress: String,
+    },
+    OpenClonePrompt,
+    OpenOwnerPrompt,
+    OwnerReposListed {
+        owner: String,
+        result: Result<Vec<RemoteRepo>, String>,
+    },
+    RepoCloned(Box<RepoInfo>),
+    OpenCommandPrompt,
+    CommandOutput {
+        path: PathBuf,
+        line: String,
+        stderr: bool,
+    },
+    CommandExited {
+        path: PathBuf,
+        status: RunStatus,
+    },
+    OpenEditor,
+    OpenShell,
+    OpenTmux,
+    ExternalFinished(Result<(), String>),
+    ItemDetailLoaded {
+        path: PathBuf,
+        number: u64,
+        result: Result<ItemDetail, String>,
+    },
+    LoadMoreComments,
+    IssueCommentsLoaded {
+        path: PathBuf,
+        number: u64,
+        result: Result<(Vec<IssueComment>, Option<u32>), String>,
+    },
+    OpenIssueForm,
+    IssueOptionsLoaded {
+        path: PathBuf,
+        result: Result<(Vec<String>, Vec<String>), String>,
+    },
+    IssueCreated {
+        path: PathBuf,
+        result: Result<GitHubItem, String>,
+    },
+    ItemActionDone {
+        path: PathBuf,
+        number: u64,
+        result: Result<ActionOutcome, String>,
+    },
+    ToggleInbox,
+    InboxLoaded {
+        generation: u64,
+        host: String,
+        result: Result<Vec<InboxItem>, String>,
+    },
+    ToggleNotifications,
+    NotificationsLoaded {
+        host: String,
+        repos: Vec<crate::repo::GitHubRepo>,
+        result: Result<Vec<NotificationThread>, String>,
+    },
+    NotificationDismissed {
+        id: u64,
+        unsubscribe: bool,
+        result: Result<(), String>,
+    },
+    WorkflowRunsLoaded {
+        path: PathBuf,
+        result: Result<Vec<WorkflowRun>, String>,
+    },
+    RunLogsLoaded {
+        path: PathBuf,
+        run_id: u64,
+        result: Result<Vec<JobLog>, String>,
+    },
+    RunRerunRequested {
+        path: PathBuf,
+        run_id: u64,
+        result: Result<(), String>,
+    },
+    BranchPrLoaded {
+        path: PathBuf,
+        branch: String,
+        result: Result<BranchPr, String>,
+    },
+    ReleasesLoaded {
+        path: PathBuf,
+        result: Result<Box<ReleasesInfo>, String>,
+    },
+    ReleaseCreated {
+        path: PathBuf,
+        result: Result<Release, String>,
+    },
+    MergeSettingsLoaded {
+        path: PathBuf,
+        number: u64,
+        result: Result<MergeSettings, String>,
+    },
+    PrHeadLoaded {
+        path: PathBuf,
+        number: u64,
+        worktree: bool,
+        result: Result<String, String>,
+    },
+    PrCheckedOut(Result<String, String>),
+    ItemPageLoaded {
+        path: PathBuf,
+        is_pr: bool,
+        filter: ItemFilter,
+        page: u32,
+        result: Result<ItemPage, String>,
+    },
+    /// Open GitHub's page for creating a PR from the current branch.
+    CreateBranchPr,
+}
+
+/// A short-lived message shown in the status bar.
+#[derive(Debug)]
+pub struct Flash {
+    pub text: String,
+    pub error: bool,
+    pub shown_at: std::time::Instant,
 }
 
+const FLASH_DURATION: std::time::Duration = std::time::Duration::from_secs(5);
+/// How often unread notifications are refreshed in the background.
+const NOTIFICATION_POLL: std::time::Duration = std::time::Duration::from_secs(60);
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum AppState {
     Scanning,
     Ready,
 }
 
+/// What clicking a zone of the detail pane does.
+#[derive(Debug, Clone)]
+pub enum ClickTarget {
+    Url(String),
+    /// Open the new issue form, like `i`.
+    NewIssue,
+}
+
 pub struct App {
     pub repos: Vec<RepoInfo>,
     pub table_state: TableState,
     pub state: AppState,
     pub scan_path: PathBuf,
-    pub github_token: Option<String>,
+    pub github_token: Option<crate::auth::Token>,
+    pub config: Config,
     pub should_quit: bool,
     pub tx: mpsc::UnboundedSender<Message>,
     pub active_pane: ActivePane,
@@ -83,15 +800,56 @@ pub struct App {
     pub list_area: ratatui::layout::Rect,
     pub tab_bar_area: ratatui::layout::Rect,
     pub detail_content_area: ratatui::layout::Rect,
-    /// Clickable regions: (rect, url)
-    pub click_zones: Vec<(ratatui::layout::Rect, String)>,
+    /// Clickable regions and what clicking them does
+    pub click_zones: Vec<(ratatui::layout::Rect, ClickTarget)>,
+    pub overlay: Option<Overlay>,
+    /// Repositories marked for bulk actions.
+    pub marked: HashSet<PathBuf>,
+    /// Latest shell command run per repository.
+    pub command_runs: HashMap<PathBuf, CommandRun>,
+    pub command_history: Vec<String>,
+    /// Row selected in the Changes tab, used as the file to open.
+    pub change_cursor: usize,
+    /// Row selected in the Issues and PRs tabs.
+    pub item_cursor: usize,
+    pub item_view: Option<ItemView>,
+    pub actions: Option<ActionsView>,
+    pub releases: Option<ReleasesView>,
+    pub item_list: Option<ItemList>,
+    /// The filter last used for each repo's issues (`false`) and PRs
+    /// (`true`) this session.
+    item_filters: HashMap<(PathBuf, bool), ItemFilter>,
+    pub inbox: Option<Inbox>,
+    /// Bumped on every inbox load so replies to an earlier load are dropped.
+    inbox_generation: u64,
+    /// Open PR lookup for each repo's current branch, keyed by path and
+    /// tagged with the branch it was made for.
+    pub branch_prs: HashMap<PathBuf, (String, Result<BranchPr, String>)>,
+    /// Unread notification threads in scanned repos, across all hosts.
+    pub notifications: Vec<NotificationThread>,
+    pub notification_error: Option<String>,
+    /// The notifications pane replaces the dashboard while open.
+    pub notifications_open: bool,
+    pub notification_cursor: usize,
+    notifications_polled: Option<std::time::Instant>,
+    pub flash: Option<Flash>,
+    /// Program the main loop should run with the terminal handed over.
+    pub pending_external: Option<ExternalCommand>,
     github_fetching: HashSet<PathBuf>,
+    /// Set while `gh` and git credential tokens are looked up; GitHub
+    /// fetches wait for them.
+    discovering_tokens: bool,
+    /// Repos whose GitHub data was refreshed this session rather than
+    /// loaded from the disk cache.
+    github_fresh: HashSet<PathBuf>,
+    branch_pr_fetching: HashSet<PathBuf>,
 }
 
 impl App {
     pub fn new(
         scan_path: PathBuf,
-        github_token: Option<String>,
+        github_token: Option<crate::auth::Token>,
+        config: Config,
         tx: mpsc::UnboundedSender<Message>,
     ) -> Self {
         Self {
@@ -100,6 +858,7 @@ impl App {
             state: AppState::Scanning,
             scan_path,
             github_token,
+            config,
             should_quit: false,
             tx,
             active_pane: ActivePane::RepoList,
@@ -109,14 +868,36 @@ impl App {
             tab_bar_area: ratatui::layout::Rect::default(),
             detail_content_area: ratatui::layout::Rect::default(),
             click_zones: Vec::new(),
+            overlay: None,
+            marked: HashSet::new(),
+            command_runs: HashMap::new(),
+            command_history: Vec::new(),
+            change_cursor: 0,
+            item_cursor: 0,
+            item_view: None,
+            actions: None,
+            releases: None,
+            item_list: None,
+            item_filters: HashMap::new(),
+            inbox: None,
+            inbox_generation: 0,
+            branch_prs: HashMap::new(),
+            notifications: Vec::new(),
+            notification_error: None,
+            notifications_open: false,
+            notification_cursor: 0,
+            notifications_polled: None,
+            flash: None,
+            pending_external: None,
             github_fetching: HashSet::new(),
+            discovering_tokens: false,
+            github_fresh: HashSet::new(),
+            branch_pr_fetching: HashSet::new(),
         }
     }
 
     pub fn selected_repo(&self) -> Option<&RepoInfo> {
-        self.table_state
-            .selected()
-            .and_then(|i| self.repos.get(i))
+        self.table_state.selected().and_then(|i| self.repos.get(i))
     }
 
     pub fn update(&mut self, msg: Message) {
@@ -124,6 +905,21 @@ impl App {
             Message::Quit => {
                 self.should_quit = true;
             }
+            Message::Key(key) => {
+                if self.overlay.is_some() {
+                    self.handle_overlay_key(key);
+                } else if self.inbox.is_some() {
+                    self.handle_inbox_key(key);
+                } else if self.notifications_open {
+                    self.handle_notifications_key(key);
+                } else if self.handle_item_key(key) {
+                    // Handled by the open issue or PR
+                } else if self.handle_tab_key(key) {
+                    // Handled by the current detail tab
+                } else if let Some(msg) = map_key(key) {
+                    self.update(msg);
+                }
+            }
             Message::MoveUp => match self.active_pane {
                 ActivePane::RepoList => {
                     if self.repos.is_empty() {
@@ -140,10 +936,20 @@ impl App {
                         None => 0,
                     };
                     self.table_state.select(Some(i));
-                    self.detail_scroll = 0;
-                    self.detail_tab = DetailTab::Changes;
+                    self.reset_detail();
                     self.maybe_fetch_selected_github();
                 }
+                ActivePane::Detail if self.detail_tab == DetailTab::Changes => {
+                    self.change_cursor = self.change_cursor.saturating_sub(1);
+                }
+                ActivePane::Detail if self.item_list_active() => {
+                    self.item_cursor = self.item_cursor.saturating_sub(1);
+                }
+                ActivePane::Detail if self.run_list_active() => {
+                    if let Some(actions) = &mut self.actions {
+                        actions.cursor = actions.cursor.saturating_sub(1);
+                    }
+                }
                 ActivePane::Detail => {
                     self.detail_scroll = self.detail_scroll.saturating_sub(1);
                 }
@@ -164,10 +970,33 @@ impl App {
                         None => 0,
                     };
                     self.table_state.select(Some(i));
-                    self.detail_scroll = 0;
-                    self.detail_tab = DetailTab::Changes;
+                    self.reset_detail();
                     self.maybe_fetch_selected_github();
                 }
+                ActivePane::Detail if self.detail_tab == DetailTab::Changes => {
+                    let count = self.selected_repo().map_or(0, |r| r.changed_files.len());
+                    if self.change_cursor + 1 < count {
+                        self.change_cursor += 1;
+                    }
+                }
+                ActivePane::Detail if self.item_list_active() => {
+                    let count = self.selected_items().len();
+                    if self.item_cursor + 1 < count {
+                        self.item_cursor += 1;
+                    }
+                    let loaded = self.item_list.as_ref().map_or(0, |l| l.items.len());
+                    if loaded > 0 && self.item_cursor + ITEM_LIST_PREFETCH >= loaded {
+                        self.load_next_item_page();
+                    }
+                }
+                ActivePane::Detail if self.run_list_active() => {
+                    if let Some(actions) = &mut self.actions {
+                        let count = actions.runs.as_ref().map_or(0, Vec::len);
+                        if actions.cursor + 1 < count {
+                            actions.cursor += 1;
+                        }
+                    }
+                }
                 ActivePane::Detail => {
                     self.detail_scroll = self.detail_scroll.saturating_add(1);
                 }
@@ -180,17 +1009,32 @@ impl App {
                 self.detail_scroll = 0;
                 self.maybe_fetch_selected_github();
             }
+            Message::Activate => {
+                if self.active_pane == ActivePane::Detail && self.item_list_active() {
+                    self.open_selected_item();
+                } else if self.active_pane == ActivePane::Detail && self.run_list_active() {
+                    self.open_run_logs();
+                } else {
+                    self.update(Message::SwitchPane);
+                }
+            }
             Message::FocusList => {
-                self.active_pane = ActivePane::RepoList;
+                // Esc backs out of an open issue, PR or log before leaving the pane
+                let closed = self.item_view.take().is_some()
+                    || self
+                        .actions
+                        .as_mut()
+                        .is_some_and(|a| a.log.take().is_some());
+                if !closed {
+                    self.active_pane = ActivePane::RepoList;
+                }
                 self.detail_scroll = 0;
             }
             Message::NextTab => {
-                self.detail_tab = self.detail_tab.next();
-                self.detail_scroll = 0;
+                self.switch_tab(self.detail_tab.next());
             }
             Message::PrevTab => {
-                self.detail_tab = self.detail_tab.prev();
-                self.detail_scroll = 0;
+                self.switch_tab(self.detail_tab.prev());
             }
             Message::Click { column, row } => {
                 // Check repo list click
@@ -205,8 +1049,7 @@ impl App {
                         let idx = (row - data_start) as usize;
                         if idx < self.repos.len() {
                             self.table_state.select(Some(idx));
-                            self.detail_scroll = 0;
-                            self.detail_tab = DetailTab::Changes;
+                            self.reset_detail();
                             self.active_pane = ActivePane::RepoList;
                             self.maybe_fetch_selected_github();
                         }
@@ -218,35 +1061,52 @@ impl App {
                 let tb = self.tab_bar_area;
                 if row == tb.y && column >= tb.x && column < tb.x + tb.width {
                     let rel = (column - tb.x) as usize;
-                    // Tab layout: " Changes │ Commits │ Issues │ PRs "
-                    // positions:   1-7       11-17      21-26    30-32
-                    let tab = if rel < 9 {
-                        Some(DetailTab::Changes)
-                    } else if rel < 19 {
-                        Some(DetailTab::Commits)
-                    } else if rel < 27 {
-                        Some(DetailTab::Issues)
-                    } else {
-                        Some(DetailTab::Prs)
-                    };
-                    if let Some(t) = tab {
-                        self.detail_tab = t;
-                        self.detail_scroll = 0;
+                    // Tab layout: " Changes │ Commits │ ... " — each tab owns
+                    // its title plus the separator half on either side.
+                    let mut end = 1;
+                    let tab = DetailTab::ALL.into_iter().find(|t| {
+                        end += t.title().chars().count() + 2;
+                        rel < end
+                    });
+                    if let Some(t) = tab.or(DetailTab::ALL.last().copied()) {
+                        self.switch_tab(t);
                     }
                     return;
                 }
 
                 // Check click zones (clickable items in detail content)
-                for (rect, url) in &self.click_zones {
-                    if column >= rect.x
+                let target = self.click_zones.iter().find(|(rect, _)| {
+                    column >= rect.x
                         && column < rect.x + rect.width
                         && row >= rect.y
                         && row < rect.y + rect.height
-                    {
+                });
+                match target.map(|(_, target)| target.clone()) {
+                    Some(ClickTarget::Url(url)) => {
                         let _ = open::that(url);
-                        return;
                     }
+                    Some(ClickTarget::NewIssue) => self.update(Message::OpenIssueForm),
+                    None => {}
+                }
+            }
+            Message::RetryGitHub if self.item_view.is_some() => {
+                if let Some(view) = &mut self.item_view {
+                    view.detail = None;
+                    view.error = None;
+                    view.loading_more = false;
                 }
+                self.fetch_item_view();
+            }
+            Message::RetryGitHub if self.item_list.is_some() && self.item_list_active() => {
+                if let Some(filter) = self.item_list.as_ref().map(|l| l.filter.clone()) {
+                    self.load_item_list(filter);
+                }
+            }
+            Message::RetryGitHub if self.detail_tab == DetailTab::Actions => {
+                self.load_workflow_runs();
+            }
+            Message::RetryGitHub if self.detail_tab == DetailTab::Releases => {
+                self.load_releases();
             }
             Message::RetryGitHub => {
                 if let Some(idx) = self.table_state.selected() {
@@ -254,6 +1114,7 @@ impl App {
                         repo.github_error = None;
                         repo.github_data = None;
                         self.github_fetching.remove(&repo.path);
+                        self.branch_prs.remove(&repo.path);
                     }
                 }
                 self.maybe_fetch_selected_github();
@@ -261,12 +1122,13 @@ impl App {
             Message::ForceRetryGitHub => {
                 if let Some(idx) = self.table_state.selected() {
                     if let Some(repo) = self.repos.get_mut(idx) {
-                        if let Some((owner, name)) = &repo.github_repo {
-                            github::invalidate_cached(owner, name);
+                        if let Some(gh) = &repo.github_repo {
+                            github::invalidate_cached(gh);
                         }
                         repo.github_error = None;
                         repo.github_data = None;
                         self.github_fetching.remove(&repo.path);
+                        self.branch_prs.remove(&repo.path);
                     }
                 }
                 self.maybe_fetch_selected_github();
@@ -274,7 +1136,6 @@ impl App {
             Message::Refresh => match self.active_pane {
                 ActivePane::Detail => {
                     self.update(Message::RetryGitHub);
-                    return;
                 }
                 ActivePane::RepoList => {
                     self.state = AppState::Scanning;
@@ -282,10 +1143,11 @@ impl App {
                     self.table_state.select(None);
                     self.detail_scroll = 0;
                     let path = self.scan_path.clone();
+                    let hosts = self.config.github.extra_hosts();
                     let tx = self.tx.clone();
                     tokio::spawn(async move {
                         let repos = tokio::task::spawn_blocking(move || {
-                            crate::repo::scan_directory(&path)
+                            crate::repo::scan_directory(&path, &hosts)
                         })
                         .await
                         .unwrap_or_default();
@@ -296,7 +1158,6 @@ impl App {
             Message::ForceRefresh => match self.active_pane {
                 ActivePane::Detail => {
                     self.update(Message::ForceRetryGitHub);
-                    return;
                 }
                 ActivePane::RepoList => {
                     crate::repo::invalidate_all_repo_caches();
@@ -305,10 +1166,11 @@ impl App {
                     self.table_state.select(None);
                     self.detail_scroll = 0;
                     let path = self.scan_path.clone();
+                    let hosts = self.config.github.extra_hosts();
                     let tx = self.tx.clone();
                     tokio::spawn(async move {
                         let repos = tokio::task::spawn_blocking(move || {
-                            crate::repo::scan_directory(&path)
+                            crate::repo::scan_directory(&path, &hosts)
                         })
                         .await
                         .unwrap_or_default();
@@ -316,57 +1178,2437 @@ impl App {
                     });
                 }
             },
-            Message::Tick => {}
+            Message::Tick => {
+                if self
+                    .flash
+                    .as_ref()
+                    .is_some_and(|f| f.shown_at.elapsed() >= FLASH_DURATION)
+                {
+                    self.flash = None;
+                }
+                if self.state == AppState::Ready
+                    && self
+                        .notifications_polled
+                        .is_none_or(|t| t.elapsed() >= NOTIFICATION_POLL)
+                {
+                    self.poll_notifications();
+                }
+            }
             Message::ReposScanned(repos) => {
                 self.repos = repos;
                 self.state = AppState::Ready;
                 self.github_fetching.clear();
+                let repos = &self.repos;
+                self.marked.retain(|p| repos.iter().any(|r| &r.path == p));
                 if !self.repos.is_empty() {
                     self.table_state.select(Some(0));
                 }
+                let hosts = self.hosts_without_token();
+                if hosts.is_empty() {
+                    self.start_github_fetches();
+                } else {
+                    self.discovering_tokens = true;
+                    github::spawn_token_discovery(hosts, self.tx.clone());
+                }
+            }
+            Message::TokensDiscovered => {
+                self.discovering_tokens = false;
+                self.start_github_fetches();
             }
             Message::GitHubDataReceived { path, data } => {
+                self.github_fetching.remove(&path);
                 if let Some(repo) = self.repos.iter_mut().find(|r| r.path == path) {
                     repo.github_data = Some(data);
                     repo.github_error = None;
                 }
+                self.github_fresh.insert(path);
+            }
+            Message::GitHubPrefetchStarted(paths) => self.github_fetching.extend(paths),
+            Message::GraphQlFailed { host, error } => {
+                self.show_flash(
+                    format!("GraphQL on {host} failed, using REST: {error}"),
+                    true,
+                );
             }
             Message::GitHubError { path, error } => {
+                self.github_fetching.remove(&path);
                 if let Some(repo) = self.repos.iter_mut().find(|r| r.path == path) {
                     repo.github_error = Some(error);
                 }
             }
-        }
-    }
-
-    fn maybe_fetch_selected_github(&mut self) {
-        let repo = match self.selected_repo() {
-            Some(r) => r,
-            None => return,
-        };
-
-        // Skip if already fetched, errored, or in-flight
-        if repo.github_data.is_some()
-            || repo.github_error.is_some()
-            || self.github_fetching.contains(&repo.path)
-        {
-            return;
-        }
-
-        // Extract what we need before mutating self
-        let path = repo.path.clone();
-        let (owner, name) = match &repo.github_repo {
-            Some(pair) => pair.clone(),
-            None => return,
-        };
-
-        self.github_fetching.insert(path.clone());
-        github::spawn_github_fetch(
-            path,
-            owner,
-            name,
-            self.github_token.clone(),
-            self.tx.clone(),
-        );
+            Message::OpenBranchCleanup { all_repos } => {
+                let paths: Vec<PathBuf> = if all_repos {
+                    self.repos.iter().map(|r| r.path.clone()).collect()
+                } else {
+                    self.selected_repo()
+                        .map(|r| r.path.clone())
+                        .into_iter()
+                        .collect()
+                };
+                if paths.is_empty() {
+                    return;
+                }
+                self.overlay = Some(Overlay::BranchCleanup(BranchCleanup {
+                    all_repos,
+                    loading: true,
+                    ..Default::default()
+                }));
+                let config = self.config.clone();
+                let tx = self.tx.clone();
+                tokio::spawn(async move {
+                    let stale = tokio::task::spawn_blocking(move || {
+                        paths
+                            .iter()
+                            .flat_map(|p| git::find_stale_branches(p, &config))
+                            .collect()
+                    })
+                    .await
+                    .unwrap_or_default();
+                    let _ = tx.send(Message::StaleBranchesFound(stale));
+                });
+            }
+            Message::StaleBranchesFound(stale) => {
+                if let Some(Overlay::BranchCleanup(cleanup)) = &mut self.overlay {
+                    cleanup.loading = false;
+                    cleanup.cursor = 0;
+                    cleanup.entries = stale
+                        .into_iter()
+                        .map(|branch| CleanupEntry {
+                            // Gone branches may hold local-only work
+                            selected: branch.merged,
+                            branch,
+                            error: None,
+                        })
+                        .collect();
+                }
+            }
+            Message::BranchesDeleted(results) => {
+                let mut touched: Vec<PathBuf> = Vec::new();
+                let mut deleted = 0;
+                let mut failed = 0;
+                if let Some(Overlay::BranchCleanup(cleanup)) = &mut self.overlay {
+                    cleanup.loading = false;
+                    for (branch, result) in results {
+                        let idx = cleanup.entries.iter().position(|e| {
+                            e.branch.repo_path == branch.repo_path
+                                && e.branch.branch == branch.branch
+                        });
+                        match (result, idx) {
+                            (Ok(()), Some(i)) => {
+                                cleanup.entries.remove(i);
+                                deleted += 1;
+                            }
+                            (Err(e), Some(i)) => {
+                                cleanup.entries[i].error = Some(e);
+                                failed += 1;
+                            }
+                            _ => {}
+                        }
+                        if !touched.contains(&branch.repo_path) {
+                            touched.push(branch.repo_path);
+                        }
+                    }
+                    cleanup.cursor = cleanup.cursor.min(cleanup.entries.len().saturating_sub(1));
+                    cleanup.status = Some(if failed == 0 {
+                        format!("Deleted {deleted} branches")
+                    } else {
+                        format!("Deleted {deleted} branches, {failed} failed")
+                    });
+                }
+                for path in touched {
+                    self.spawn_repo_rescan(path);
+                }
+            }
+            Message::RepoUpdated(info) => {
+                if let Some(repo) = self.repos.iter_mut().find(|r| r.path == info.path) {
+                    let github_data = repo.github_data.take();
+                    let github_error = repo.github_error.take();
+                    *repo = *info;
+                    repo.github_data = github_data;
+                    repo.github_error = github_error;
+                }
+                // A checkout may have changed which PR the branch belongs to
+                self.maybe_fetch_branch_pr();
+            }
+            Message::ToggleMark => {
+                if self.active_pane != ActivePane::RepoList {
+                    return;
+                }
+                if let Some(path) = self.selected_repo().map(|r| r.path.clone()) {
+                    if !self.marked.remove(&path) {
+                        self.marked.insert(path);
+                    }
+                }
+                self.update(Message::MoveDown);
+            }
+            Message::ToggleMarkAll => {
+                if self.marked.len() == self.repos.len() {
+                    self.marked.clear();
+                } else {
+                    self.marked = self.repos.iter().map(|r| r.path.clone()).collect();
+                }
+            }
+            Message::OpenMarkPrompt => {
+                self.open_prompt(Prompt::new(
+                    "Mark repos matching",
+                    PromptAction::MarkMatching,
+                ));
+            }
+            Message::OpenBulkMenu => {
+                if !self.bulk_targets().is_empty() {
+                    self.overlay = Some(Overlay::BulkMenu { cursor: 0 });
+                }
+            }
+            Message::BulkProgressUpdate { path, progress } => {
+                if let Some(Overlay::BulkProgress(bulk)) = &mut self.overlay {
+                    if let Some(entry) = bulk.entries.iter_mut().find(|e| e.path == path) {
+                        entry.progress = Some(progress);
+                    }
+                }
+            }
+            Message::OpenClonePrompt => {
+                self.open_prompt(Prompt::new(
+                    "Clone (URL or owner/name)",
+                    PromptAction::CloneSource,
+                ));
+            }
+            Message::OpenOwnerPrompt => {
+                self.open_prompt(Prompt::new(
+                    "List repos of GitHub owner or org",
+                    PromptAction::ListOwner,
+                ));
+            }
+            Message::OwnerReposListed { owner, result } => {
+                if let Some(Overlay::RemoteRepos(list)) = &mut self.overlay {
+                    if list.owner != owner {
+                        return;
+                    }
+                    list.loading = false;
+                    match result {
+                        Ok(remote) => {
+                            let scan_path = &self.scan_path;
+                            let local: HashSet<String> = self
+                                .repos
+                                .iter()
+                                .filter_map(|r| r.github_repo.as_ref())
+                                .map(|gh| gh.full_name().to_lowercase())
+                                .collect();
+                            list.entries = remote
+                                .into_iter()
+                                .filter(|r| {
+                                    !local.contains(&r.full_name.to_lowercase())
+                                        && !scan_path.join(&r.name).exists()
+                                })
+                                .map(|repo| RemoteRepoEntry {
+                                    selected: false,
+                                    repo,
+                                })
+                                .collect();
+                            list.entries.sort_by_key(|e| e.repo.name.to_lowercase());
+                        }
+                        Err(e) => list.error = Some(e),
+                    }
+                }
+            }
+            Message::RepoCloned(info) => {
+                if self.repos.iter().any(|r| r.path == info.path) {
+                    return;
+                }
+                let selected = self.selected_repo().map(|r| r.path.clone());
+                self.repos.push(*info);
+                self.repos.sort_by_key(|r| r.name.to_lowercase());
+                let idx = selected
+                    .and_then(|p| self.repos.iter().position(|r| r.path == p))
+                    .unwrap_or(0);
+                self.table_state.select(Some(idx));
+            }
+            Message::OpenCommandPrompt => {
+                if !self.bulk_targets().is_empty() {
+                    self.open_prompt(Prompt::new("Run command", PromptAction::RunCommand));
+                }
+            }
+            Message::CommandOutput { path, line, stderr } => {
+                if let Some(run) = self.command_runs.get_mut(&path) {
+                    run.output.push(OutputLine { text: line, stderr });
+                    if run.output.len() > MAX_OUTPUT_LINES {
+                        let excess = run.output.len() - MAX_OUTPUT_LINES;
+                        run.output.drain(..excess);
+                    }
+                }
+            }
+            Message::CommandExited { path, status } => {
+                if let Some(run) = self.command_runs.get_mut(&path) {
+                    run.status = status;
+                }
+            }
+            Message::OpenEditor => {
+                let Some(repo) = self.selected_repo() else {
+                    return;
+                };
+                // In the Changes tab, open the highlighted file at its first change
+                let file = (self.active_pane == ActivePane::Detail
+                    && self.detail_tab == DetailTab::Changes)
+                    .then(|| repo.changed_files.get(self.change_cursor))
+                    .flatten()
+                    .map(|f| f[2..].to_string())
+                    .filter(|f| repo.path.join(f).exists());
+                let line = file
+                    .as_deref()
+                    .map(|f| git::first_changed_line(&repo.path, f).unwrap_or(1));
+                let target = file.as_deref().zip(line);
+                match actions::editor_invocation(&self.config.open, &repo.path, target) {
+                    Ok(cmd) => self.pending_external = Some(cmd),
+                    Err(e) => self.show_flash(e, true),
+                }
+            }
+            Message::OpenShell => {
+                if let Some(repo) = self.selected_repo() {
+                    self.pending_external =
+                        Some(actions::shell_invocation(&self.config.open, &repo.path));
+                }
+            }
+            Message::OpenTmux => {
+                let Some(repo) = self.selected_repo() else {
+                    return;
+                };
+                match actions::tmux_invocation(&repo.name, &repo.path) {
+                    Ok(cmd) => self.pending_external = Some(cmd),
+                    Err(e) => self.show_flash(e, true),
+                }
+            }
+            Message::ExternalFinished(result) => {
+                if let Err(e) = result {
+                    self.show_flash(e, true);
+                }
+                if let Some(Overlay::IssueForm(form)) = &mut self.overlay {
+                    if let Some(body) = form.body_file.take().and_then(|f| read_scratch(&f)) {
+                        form.body = body;
+                    }
+                }
+                if let Some(Overlay::MergeForm(form)) = &mut self.overlay {
+                    if let Some(message) = form.message_file.take().and_then(|f| read_scratch(&f)) {
+                        form.message = message;
+                    }
+                }
+                let comment_file = self.item_view.as_mut().and_then(|v| v.comment_file.take());
+                match comment_file.and_then(|f| read_scratch(&f)) {
+                    Some(comment) if !comment.is_empty() => {
+                        self.run_item_action(ItemAction::Comment(comment));
+                    }
+                    Some(_) => self.show_flash("Empty comment, nothing posted", false),
+                    None => {}
+                }
+                // The program may have changed the working tree or branch
+                if let Some(path) = self.selected_repo().map(|r| r.path.clone()) {
+                    self.spawn_repo_rescan(path);
+                }
+            }
+            Message::ItemDetailLoaded {
+                path,
+                number,
+                result,
+            } => {
+                if let Some(view) = &mut self.item_view {
+                    if view.path == path && view.number == number {
+                        match result {
+                            Ok(detail) => view.detail = Some(detail),
+                            Err(e) => view.error = Some(e),
+                        }
+                    }
+                }
+            }
+            Message::LoadMoreComments => self.load_more_comments(),
+            Message::OpenIssueForm => {
+                let Some(repo) = self.selected_repo() else {
+                    return;
+                };
+                let Some((gh, target)) = self.github_repo_target(&repo.path) else {
+                    self.show_flash("No GitHub remote", true);
+                    return;
+                };
+                let form = IssueForm {
+                    path: repo.path.clone(),
+                    repo_name: gh.full_name(),
+                    field: IssueField::Title,
+                    title: String::new(),
+                    body: String::new(),
+                    templates: github::load_issue_templates(&repo.path),
+                    template_cursor: 0,
+                    labels: Vec::new(),
+                    label_cursor: 0,
+                    assignees: Vec::new(),
+                    assignee_cursor: 0,
+                    loading_options: true,
+                    submitting: false,
+                    error: None,
+                    body_file: None,
+                };
+                let mut form = Box::new(form);
+                if !form.templates.is_empty() {
+                    form.field = IssueField::Template;
+                }
+                github::spawn_issue_options_fetch(form.path.clone(), gh, target, self.tx.clone());
+                self.overlay = Some(Overlay::IssueForm(form));
+            }
+            Message::IssueOptionsLoaded { path, result } => match &mut self.overlay {
+                Some(Overlay::IssueForm(form)) if form.path == path => {
+                    form.loading_options = false;
+                    match result {
+                        Ok((labels, assignees)) => form.set_options(labels, assignees),
+                        Err(e) => form.error = Some(format!("Could not load labels: {e}")),
+                    }
+                }
+                Some(Overlay::LabelPicker(picker)) if picker.path == path => {
+                    picker.loading = false;
+                    match result {
+                        Ok((labels, _)) => {
+                            let current = selected_names(&picker.choices);
+                            picker.choices = labels
+                                .into_iter()
+                                .map(|name| Choice {
+                                    name,
+                                    selected: false,
+                                })
+                                .collect();
+                            select_choices(&mut picker.choices, &current);
+                        }
+                        Err(e) => {
+                            self.overlay = None;
+                            self.show_flash(format!("Could not load labels: {e}"), true);
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Message::ItemActionDone {
+                path,
+                number,
+                result,
+            } => {
+                let merging = matches!(
+                    &self.overlay,
+                    Some(Overlay::MergeForm(f))
+                        if f.path == path && f.number == number && f.submitting
+                );
+                let outcome = match result {
+                    Ok(outcome) => outcome,
+                    Err(e) if merging => {
+                        if let Some(Overlay::MergeForm(form)) = &mut self.overlay {
+                            form.submitting = false;
+                            form.error = Some(e);
+                        }
+                        return;
+                    }
+                    Err(e) => {
+                        self.show_flash(format!("#{number}: {e}"), true);
+                        return;
+                    }
+                };
+                if merging {
+                    self.overlay = None;
+                }
+                self.apply_item_outcome(&path, number, outcome);
+            }
+            Message::MergeSettingsLoaded {
+                path,
+                number,
+                result,
+            } => {
+                let Some(Overlay::MergeForm(form)) = &mut self.overlay else {
+                    return;
+                };
+                if form.path != path || form.number != number {
+                    return;
+                }
+                form.loading_settings = false;
+                match result {
+                    Ok(settings) => form.set_settings(settings),
+                    Err(e) => form.error = Some(format!("Could not load merge settings: {e}")),
+                }
+            }
+            Message::WorkflowRunsLoaded { path, result } => {
+                let Some(actions) = self.actions.as_mut().filter(|a| a.path == path) else {
+                    return;
+                };
+                match result {
+                    Ok(runs) => {
+                        actions.cursor = actions.cursor.min(runs.len().saturating_sub(1));
+                        actions.runs = Some(runs);
+                        actions.error = None;
+                    }
+                    Err(e) => actions.error = Some(e),
+                }
+            }
+            Message::RunLogsLoaded {
+                path,
+                run_id,
+                result,
+            } => {
+                let log = self
+                    .actions
+                    .as_mut()
+                    .filter(|a| a.path == path)
+                    .and_then(|a| a.log.as_mut())
+                    .filter(|l| l.run_id == run_id);
+                if let Some(log) = log {
+                    log.jobs = Some(result);
+                }
+            }
+            Message::RunRerunRequested {
+                path,
+                run_id,
+                result,
+            } => match result {
+                Ok(()) => {
+                    self.show_flash(format!("Re-running failed jobs of run {run_id}"), false);
+                    if self.actions.as_ref().is_some_and(|a| a.path == path) {
+                        self.load_workflow_runs();
+                    }
+                }
+                Err(e) => self.show_flash(format!("Re-run: {e}"), true),
+            },
+            Message::BranchPrLoaded {
+                path,
+                branch,
+                result,
+            } => {
+                self.branch_pr_fetching.remove(&path);
+                self.branch_prs.insert(path, (branch, result));
+            }
+            Message::CreateBranchPr => {
+                let Some(repo) = self.selected_repo() else {
+                    return;
+                };
+                match self.branch_prs.get(&repo.path) {
+                    Some((branch, Ok(BranchPr::Missing { create_url })))
+                        if *branch == repo.current_branch =>
+                    {
+                        let _ = open::that(create_url);
+                    }
+                    Some((_, Ok(BranchPr::Open(pr)))) => {
+                        self.show_flash(
+                            format!("PR #{} is already open for this branch", pr.number),
+                            true,
+                        );
+                    }
+                    Some((_, Ok(BranchPr::DefaultBranch))) => {
+                        self.show_flash("Switch to a feature branch to open a PR", true);
+                    }
+                    _ => self.show_flash("No PR information for this branch yet", true),
+                }
+            }
+            Message::ReleasesLoaded { path, result } => {
+                if let Some(view) = self.releases.as_mut().filter(|v| v.path == path) {
+                    view.info = Some(result);
+                }
+            }
+            Message::ReleaseCreated { path, result } => {
+                if let Some(view) = self.releases.as_mut().filter(|v| v.path == path) {
+                    view.creating = false;
+                }
+                match result {
+                    Ok(release) => {
+                        self.show_flash(format!("Created draft release {}", release.tag), false);
+                        if self.releases.as_ref().is_some_and(|v| v.path == path) {
+                            self.load_releases();
+                        }
+                    }
+                    Err(e) => self.show_flash(format!("Draft release: {e}"), true),
+                }
+            }
+            Message::ToggleInbox => {
+                if self.inbox.take().is_none() {
+                    self.notifications_open = false;
+                    self.load_inbox();
+                }
+            }
+            Message::ToggleNotifications => {
+                self.notifications_open = !self.notifications_open;
+                if self.notifications_open {
+                    self.inbox = None;
+                    self.notification_cursor = 0;
+                    self.poll_notifications();
+                }
+            }
+            Message::NotificationsLoaded {
+                host,
+                repos,
+                result,
+            } => match result {
+                Ok(threads) => {
+                    self.notifications.retain(|n| !repos.contains(&n.repo));
+                    self.notifications.extend(threads);
+                    self.notifications
+                        .sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+                    self.notification_cursor = self
+                        .notification_cursor
+                        .min(self.notifications.len().saturating_sub(1));
+                    self.notification_error = None;
+                }
+                Err(e) => self.notification_error = Some(format!("{host}: {e}")),
+            },
+            Message::NotificationDismissed {
+                id,
+                unsubscribe,
+                result,
+            } => {
+                if let Err(e) = result {
+                    self.show_flash(format!("Notification: {e}"), true);
+                    return;
+                }
+                self.notifications.retain(|n| n.id != id);
+                self.notification_cursor = self
+                    .notification_cursor
+                    .min(self.notifications.len().saturating_sub(1));
+                self.show_flash(
+                    if unsubscribe {
+                        "Unsubscribed"
+                    } else {
+                        "Marked as read"
+                    },
+                    false,
+                );
+            }
+            Message::InboxLoaded {
+                generation,
+                host,
+                result,
+            } => {
+                if generation != self.inbox_generation {
+                    return;
+                }
+                let Some(inbox) = &mut self.inbox else {
+                    return;
+                };
+                inbox.pending = inbox.pending.saturating_sub(1);
+                match result {
+                    Ok(items) => {
+                        inbox.items.extend(items);
+                        inbox.items.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+                    }
+                    Err(e) => inbox.errors.push(format!("{host}: {e}")),
+                }
+            }
+            Message::PrHeadLoaded {
+                path,
+                number,
+                worktree,
+                result,
+            } => {
+                if self.selected_repo().map(|r| &r.path) != Some(&path) {
+                    return;
+                }
+                match result {
+                    Ok(head) => {
+                        self.flash = None;
+                        self.prompt_pr_checkout(path, number, head, worktree);
+                    }
+                    Err(e) => self.show_flash(format!("Loading PR #{number}: {e}"), true),
+                }
+            }
+            Message::PrCheckedOut(result) => match result {
+                Ok(msg) => self.show_flash(msg, false),
+                Err(e) => self.show_flash(format!("Checkout failed: {e}"), true),
+            },
+            Message::ItemPageLoaded {
+                path,
+                is_pr,
+                filter,
+                page,
+                result,
+            } => {
+                let Some(list) = &mut self.item_list else {
+                    return;
+                };
+                if list.path != path || list.is_pr != is_pr || list.filter != filter {
+                    return;
+                }
+                list.loading = false;
+                match result {
+                    Ok(loaded) => {
+                        list.items.extend(loaded.items);
+                        list.total = Some(loaded.total);
+                        list.next_page = loaded.more.then_some(page + 1);
+                    }
+                    Err(e) => list.error = Some(e),
+                }
+            }
+            Message::IssueCreated { path, result } => {
+                let item = match result {
+                    Ok(item) => item,
+                    Err(e) => {
+                        if let Some(Overlay::IssueForm(form)) = &mut self.overlay {
+                            form.submitting = false;
+                            form.error = Some(e);
+                        }
+                        return;
+                    }
+                };
+                if matches!(&self.overlay, Some(Overlay::IssueForm(f)) if f.path == path) {
+                    self.overlay = None;
+                }
+                self.show_flash(format!("Created issue #{}", item.number), false);
+                if let Some(repo) = self.repos.iter_mut().find(|r| r.path == path) {
+                    if let (Some(data), Some(gh)) = (&mut repo.github_data, &repo.github_repo) {
+                        data.open_issues += 1;
+                        data.recent_issues.insert(0, item);
+                        data.recent_issues.truncate(github::RECENT_ITEMS as usize);
+                        github::update_cached(gh, data);
+                    }
+                }
+                if let Some(filter) = self
+                    .item_list
+                    .as_ref()
+                    .filter(|l| l.path == path && !l.is_pr)
+                    .map(|l| l.filter.clone())
+                {
+                    self.load_item_list(filter);
+                }
+            }
+            Message::IssueCommentsLoaded {
+                path,
+                number,
+                result,
+            } => {
+                let Some(view) = &mut self.item_view else {
+                    return;
+                };
+                if view.path != path || view.number != number {
+                    return;
+                }
+                view.loading_more = false;
+                match (result, &mut view.detail) {
+                    (Ok((comments, next)), Some(ItemDetail::Issue(issue))) => {
+                        issue.comments.extend(comments);
+                        issue.next_comment_page = next;
+                    }
+                    (Err(e), _) => self.show_flash(format!("Could not load comments: {e}"), true),
+                    _ => {}
+                }
+            }
+            Message::BulkResult { path, result } => {
+                if let Some(Overlay::BulkProgress(progress)) = &mut self.overlay {
+                    if let Some(entry) = progress.entries.iter_mut().find(|e| e.path == path) {
+                        entry.state = match result {
+                            Ok(msg) => BulkState::Done(msg),
+                            Err(err) => BulkState::Failed(err),
+                        };
+                    }
+                }
+            }
+        }
+    }
+
+    /// Open the inbox and query every account that has scanned GitHub repos.
+    fn load_inbox(&mut self) {
+        let by_target = self.github_repos_by_target();
+        if by_target.is_empty() {
+            self.show_flash("No repositories with a GitHub remote", true);
+            return;
+        }
+        self.inbox_generation += 1;
+        self.inbox = Some(Inbox {
+            pending: by_target.len(),
+            ..Default::default()
+        });
+        for (target, repos) in by_target {
+            github::spawn_inbox_fetch(repos, target, self.inbox_generation, self.tx.clone());
+        }
+    }
+
+    fn handle_inbox_key(&mut self, key: KeyEvent) {
+        let Some(inbox) = &mut self.inbox else {
+            return;
+        };
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => inbox.cursor = inbox.cursor.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') if inbox.cursor + 1 < inbox.items.len() => {
+                inbox.cursor += 1;
+            }
+            KeyCode::Char('o') => {
+                if let Some(item) = inbox.items.get(inbox.cursor) {
+                    let _ = open::that(&item.html_url);
+                }
+            }
+            KeyCode::Char('r') => self.load_inbox(),
+            KeyCode::Enter => self.jump_to_inbox_item(),
+            KeyCode::Esc | KeyCode::Char('w') => self.inbox = None,
+            KeyCode::Char('q') => self.should_quit = true,
+            _ => {}
+        }
+    }
+
+    fn jump_to_inbox_item(&mut self) {
+        let Some(item) = self
+            .inbox
+            .as_ref()
+            .and_then(|i| i.items.get(i.cursor))
+            .cloned()
+        else {
+            return;
+        };
+        self.jump_to_item(&item.repo, item.number, item.is_pr);
+    }
+
+    /// Close the inbox or notifications, select the item's repository and
+    /// open the item in its Issues or PRs tab.
+    fn jump_to_item(&mut self, repo: &crate::repo::GitHubRepo, number: u64, is_pr: bool) {
+        let Some(idx) = self.repos.iter().position(|r| {
+            r.github_repo
+                .as_ref()
+                .is_some_and(|gh| gh.eq_ignore_case(repo))
+        }) else {
+            return;
+        };
+        self.inbox = None;
+        self.notifications_open = false;
+        self.table_state.select(Some(idx));
+        self.reset_detail();
+        self.maybe_fetch_selected_github();
+        self.switch_tab(if is_pr {
+            DetailTab::Prs
+        } else {
+            DetailTab::Issues
+        });
+        self.active_pane = ActivePane::Detail;
+        self.open_item(self.repos[idx].path.clone(), number, is_pr);
+    }
+
+    /// Local GitHub repos grouped by the API target (host and token) used
+    /// for them, so each account is asked about its own repos.
+    fn github_repos_by_target(&self) -> Vec<(github::HostTarget, Vec<crate::repo::GitHubRepo>)> {
+        let mut groups: Vec<(github::HostTarget, Vec<crate::repo::GitHubRepo>)> = Vec::new();
+        for gh in self.repos.iter().filter_map(|r| r.github_repo.as_ref()) {
+            let target = self.github_target(&gh.host, Some(&gh.owner));
+            let group = match groups
+                .iter()
+                .position(|(t, _)| t.host == target.host && t.token == target.token)
+            {
+                Some(i) => &mut groups[i].1,
+                None => {
+                    groups.push((target, Vec::new()));
+                    &mut groups.last_mut().expect("just pushed").1
+                }
+            };
+            if !group.contains(gh) {
+                group.push(gh.clone());
+            }
+        }
+        groups
+    }
+
+    /// Refresh unread notifications for every host with a token; the
+    /// notifications API is unavailable anonymously.
+    fn poll_notifications(&mut self) {
+        self.notifications_polled = Some(std::time::Instant::now());
+        if self.config.github.offline {
+            return;
+        }
+        for (target, repos) in self.github_repos_by_target() {
+            if target.token.is_some() {
+                github::spawn_notifications_fetch(repos, target, self.tx.clone());
+            }
+        }
+    }
+
+    fn handle_notifications_key(&mut self, key: KeyEvent) {
+        let selected = self.notifications.get(self.notification_cursor).cloned();
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.notification_cursor = self.notification_cursor.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j')
+                if self.notification_cursor + 1 < self.notifications.len() =>
+            {
+                self.notification_cursor += 1;
+            }
+            KeyCode::Enter => {
+                if let Some(n) = selected {
+                    match n.number {
+                        Some(number) => self.jump_to_item(&n.repo, number, n.is_pr()),
+                        None => {
+                            let _ = open::that(&n.web_url);
+                        }
+                    }
+                }
+            }
+            KeyCode::Char('o') => {
+                if let Some(n) = selected {
+                    let _ = open::that(&n.web_url);
+                }
+            }
+            KeyCode::Char('d') | KeyCode::Char('u') => {
+                if let Some(n) = selected {
+                    let target = self.github_target(&n.repo.host, Some(&n.repo.owner));
+                    let unsubscribe = key.code == KeyCode::Char('u');
+                    github::spawn_dismiss_notification(n.id, unsubscribe, target, self.tx.clone());
+                }
+            }
+            KeyCode::Char('r') => self.poll_notifications(),
+            KeyCode::Esc | KeyCode::Char('N') => self.notifications_open = false,
+            KeyCode::Char('q') => self.should_quit = true,
+            _ => {}
+        }
+    }
+
+    fn handle_issue_form_key(&mut self, key: KeyEvent) {
+        let Some(Overlay::IssueForm(form)) = &mut self.overlay else {
+            return;
+        };
+        if form.submitting {
+            return;
+        }
+        match (form.field, key.code) {
+            (_, KeyCode::Esc) => self.overlay = None,
+            (_, KeyCode::Tab) => form.move_field(true),
+            (_, KeyCode::BackTab) => form.move_field(false),
+            (IssueField::Template, KeyCode::Up) => {
+                form.template_cursor = form.template_cursor.saturating_sub(1);
+            }
+            (IssueField::Template, KeyCode::Down)
+                if form.template_cursor + 1 < form.templates.len() =>
+            {
+                form.template_cursor += 1;
+            }
+            (IssueField::Template, KeyCode::Enter) => form.apply_template(),
+            (IssueField::Labels, KeyCode::Up) => {
+                form.label_cursor = form.label_cursor.saturating_sub(1)
+            }
+            (IssueField::Labels, KeyCode::Down) if form.label_cursor + 1 < form.labels.len() => {
+                form.label_cursor += 1;
+            }
+            (IssueField::Labels, KeyCode::Char(' ')) => {
+                if let Some(choice) = form.labels.get_mut(form.label_cursor) {
+                    choice.selected = !choice.selected;
+                }
+            }
+            (IssueField::Assignees, KeyCode::Up) => {
+                form.assignee_cursor = form.assignee_cursor.saturating_sub(1);
+            }
+            (IssueField::Assignees, KeyCode::Down)
+                if form.assignee_cursor + 1 < form.assignees.len() =>
+            {
+                form.assignee_cursor += 1;
+            }
+            (IssueField::Assignees, KeyCode::Char(' ')) => {
+                if let Some(choice) = form.assignees.get_mut(form.assignee_cursor) {
+                    choice.selected = !choice.selected;
+                }
+            }
+            (IssueField::Title, KeyCode::Char(c)) => form.title.push(c),
+            (IssueField::Title, KeyCode::Backspace) => {
+                form.title.pop();
+            }
+            (IssueField::Title | IssueField::Body, KeyCode::Up) => form.move_field(false),
+            (IssueField::Title | IssueField::Body | IssueField::Submit, KeyCode::Down) => {
+                form.move_field(true);
+            }
+            (IssueField::Body, KeyCode::Enter | KeyCode::Char('e')) => {
+                let (body, cwd) = (form.body.clone(), form.path.clone());
+                let result = self.edit_scratch("issue", &body, &cwd);
+                if let Some(Overlay::IssueForm(form)) = &mut self.overlay {
+                    match result {
+                        Ok(file) => form.body_file = Some(file),
+                        Err(e) => form.error = Some(e),
+                    }
+                }
+            }
+            (IssueField::Submit, KeyCode::Enter) => {
+                let issue = form.new_issue();
+                if issue.title.is_empty() {
+                    form.error = Some("A title is required".to_string());
+                    form.field = IssueField::Title;
+                    return;
+                }
+                let path = form.path.clone();
+                let Some((gh, target)) = self.github_repo_target(&path) else {
+                    return;
+                };
+                if let Some(Overlay::IssueForm(form)) = &mut self.overlay {
+                    form.submitting = true;
+                    form.error = None;
+                }
+                github::spawn_create_issue(path, gh, issue, target, self.tx.clone());
+            }
+            (_, KeyCode::Enter) => form.move_field(true),
+            _ => {}
+        }
+    }
+
+    fn handle_merge_form_key(&mut self, key: KeyEvent) {
+        let Some(Overlay::MergeForm(form)) = &mut self.overlay else {
+            return;
+        };
+        if form.submitting {
+            return;
+        }
+        match (form.field, key.code) {
+            (_, KeyCode::Esc) => self.overlay = None,
+            (_, KeyCode::Tab) => form.move_field(true),
+            (_, KeyCode::BackTab) => form.move_field(false),
+            (MergeField::Method, KeyCode::Left) => form.cycle_method(false),
+            (MergeField::Method, KeyCode::Right | KeyCode::Char(' ')) => form.cycle_method(true),
+            (MergeField::Title, KeyCode::Char(c)) => form.title.push(c),
+            (MergeField::Title, KeyCode::Backspace) => {
+                form.title.pop();
+            }
+            (MergeField::DeleteBranch, KeyCode::Char(' ')) => {
+                if let Some(delete) = &mut form.delete_branch {
+                    *delete = !*delete;
+                }
+            }
+            (MergeField::Message, KeyCode::Enter | KeyCode::Char('e')) => {
+                let (message, cwd) = (form.message.clone(), form.path.clone());
+                let result = self.edit_scratch("merge", &message, &cwd);
+                if let Some(Overlay::MergeForm(form)) = &mut self.overlay {
+                    match result {
+                        Ok(file) => form.message_file = Some(file),
+                        Err(e) => form.error = Some(e),
+                    }
+                }
+            }
+            (_, KeyCode::Up) => form.move_field(false),
+            (_, KeyCode::Down) => form.move_field(true),
+            (MergeField::Submit, KeyCode::Enter) => {
+                let request = form.request();
+                if request.method.takes_message() && request.title.is_empty() {
+                    form.error = Some("A commit title is required".to_string());
+                    form.field = MergeField::Title;
+                    return;
+                }
+                let (path, number) = (form.path.clone(), form.number);
+                let found = self.github_repo_target(&path);
+                let Some(Overlay::MergeForm(form)) = &mut self.overlay else {
+                    return;
+                };
+                let Some((gh, target)) = found else {
+                    form.error = Some("The repository no longer has a GitHub remote".to_string());
+                    return;
+                };
+                form.submitting = true;
+                form.error = None;
+                github::spawn_item_action(
+                    path,
+                    gh,
+                    number,
+                    ItemAction::Merge(request),
+                    target,
+                    self.tx.clone(),
+                );
+            }
+            (_, KeyCode::Enter) => form.move_field(true),
+            _ => {}
+        }
+    }
+
+    /// Write actions on the open issue or PR. Returns whether `key` was one.
+    fn handle_item_key(&mut self, key: KeyEvent) -> bool {
+        if self.active_pane != ActivePane::Detail {
+            return false;
+        }
+        let Some(view) = &self.item_view else {
+            return false;
+        };
+        let (open, labels) = match &view.detail {
+            Some(ItemDetail::Issue(issue)) => (issue.open, issue.labels.clone()),
+            Some(ItemDetail::Pr(pr)) => (pr.open, pr.labels.clone()),
+            None => return false,
+        };
+        match key.code {
+            KeyCode::Char('c') => {
+                let cwd = view.path.clone();
+                match self.edit_scratch("comment", "", &cwd) {
+                    Ok(file) => {
+                        if let Some(view) = &mut self.item_view {
+                            view.comment_file = Some(file);
+                        }
+                    }
+                    Err(e) => self.show_flash(e, true),
+                }
+            }
+            KeyCode::Char(c @ ('C' | 'W')) => {
+                let Some(ItemDetail::Pr(pr)) = &view.detail else {
+                    return false;
+                };
+                let (path, number, head) = (view.path.clone(), pr.number, pr.head.clone());
+                self.prompt_pr_checkout(path, number, head, c == 'W');
+            }
+            KeyCode::Char('g') => {
+                let Some(ItemDetail::Pr(pr)) = &view.detail else {
+                    return false;
+                };
+                if !pr.open {
+                    self.show_flash(format!("PR #{} is not open", pr.number), true);
+                    return true;
+                }
+                let path = view.path.clone();
+                let Some((gh, target)) = self.github_repo_target(&path) else {
+                    self.show_flash("No GitHub remote", true);
+                    return true;
+                };
+                let form = MergeForm::new(path.clone(), pr.clone(), gh.owner.clone());
+                github::spawn_merge_settings_fetch(path, gh, form.number, target, self.tx.clone());
+                self.overlay = Some(Overlay::MergeForm(Box::new(form)));
+            }
+            KeyCode::Char('x') => self.run_item_action(ItemAction::SetOpen(!open)),
+            KeyCode::Char('m') => self.run_item_action(ItemAction::AssignMe(true)),
+            KeyCode::Char('M') => self.run_item_action(ItemAction::AssignMe(false)),
+            KeyCode::Char('l') => {
+                let Some((gh, target)) = self.github_repo_target(&view.path) else {
+                    return true;
+                };
+                let mut choices = Vec::new();
+                select_choices(&mut choices, &labels);
+                let path = view.path.clone();
+                self.overlay = Some(Overlay::LabelPicker(LabelPicker {
+                    path: path.clone(),
+                    number: view.number,
+                    choices,
+                    cursor: 0,
+                    loading: true,
+                }));
+                github::spawn_issue_options_fetch(path, gh, target, self.tx.clone());
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Ask for the branch, or worktree path, to check PR `number` out as.
+    fn prompt_pr_checkout(&mut self, path: PathBuf, number: u64, head: String, worktree: bool) {
+        let prompt = if worktree {
+            let dir = git::pr_worktree_path(&path, number);
+            Prompt::new(
+                format!("Worktree for PR #{number}"),
+                PromptAction::PrWorktree { number, head },
+            )
+            .with_input(dir.display().to_string())
+        } else {
+            let branch = git::pr_branch_name(number, &head);
+            Prompt::new(
+                format!("Check out PR #{number} as branch"),
+                PromptAction::CheckoutPr { number, head },
+            )
+            .with_input(branch)
+        };
+        self.open_prompt(prompt);
+    }
+
+    /// Check PR `number` out in the selected repo in the background, then
+    /// rescan it and list the new worktree, if any.
+    fn checkout_pr(
+        &mut self,
+        number: u64,
+        head: String,
+        branch: String,
+        worktree: Option<PathBuf>,
+    ) {
+        let Some(path) = self.selected_repo().map(|r| r.path.clone()) else {
+            return;
+        };
+        let hosts = self.config.github.extra_hosts();
+        let tx = self.tx.clone();
+        self.show_flash(format!("Checking out PR #{number}..."), false);
+        tokio::spawn(async move {
+            let result = tokio::task::spawn_blocking(move || {
+                // Fork heads are `owner:branch`; only same-repo branches are tracked
+                let head_branch = (!head.contains(':')).then_some(head.as_str());
+                let result =
+                    git::checkout_pr(&path, number, head_branch, &branch, worktree.as_deref())?;
+                let infos = [Some(path.as_path()), worktree.as_deref()]
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|p| crate::repo::rescan_repo(p, &hosts))
+                    .collect::<Vec<_>>();
+                Ok((result, infos))
+            })
+            .await
+            .unwrap_or_else(|e| Err(e.to_string()));
+            let result = result.map(|(result, infos)| {
+                for (i, info) in infos.into_iter().enumerate() {
+                    let msg = if i == 0 {
+                        Message::RepoUpdated
+                    } else {
+                        Message::RepoCloned
+                    };
+                    let _ = tx.send(msg(Box::new(info)));
+                }
+                result
+            });
+            let _ = tx.send(Message::PrCheckedOut(result));
+        });
+    }
+
+    /// Apply `action` to the open issue or PR in the background.
+    fn run_item_action(&mut self, action: ItemAction) {
+        let Some(view) = &self.item_view else {
+            return;
+        };
+        let Some((gh, target)) = self.github_repo_target(&view.path) else {
+            return;
+        };
+        let number = view.number;
+        let progress = match &action {
+            ItemAction::Comment(_) => format!("Commenting on #{number}..."),
+            ItemAction::SetOpen(true) => format!("Reopening #{number}..."),
+            ItemAction::SetOpen(false) => format!("Closing #{number}..."),
+            ItemAction::SetLabels(_) => format!("Setting labels on #{number}..."),
+            ItemAction::AssignMe(true) => format!("Assigning you to #{number}..."),
+            ItemAction::AssignMe(false) => format!("Unassigning you from #{number}..."),
+            ItemAction::Merge(_) => format!("Merging #{number}..."),
+        };
+        github::spawn_item_action(
+            view.path.clone(),
+            gh,
+            number,
+            action,
+            target,
+            self.tx.clone(),
+        );
+        self.show_flash(progress, false);
+    }
+
+    /// Reflect a completed write action in the open view and, for state
+    /// changes, in the repo's cached issue and PR lists.
+    fn apply_item_outcome(&mut self, path: &std::path::Path, number: u64, outcome: ActionOutcome) {
+        // A merge closes the PR like any other state change
+        let state = match &outcome {
+            ActionOutcome::StateChanged { open } => Some(*open),
+            ActionOutcome::Merged(_) => Some(false),
+            _ => None,
+        };
+        let mut reopened: Option<(bool, String)> = None;
+        if let Some(view) = self
+            .item_view
+            .as_mut()
+            .filter(|v| v.path == path && v.number == number)
+        {
+            match (&mut view.detail, &outcome) {
+                (Some(ItemDetail::Issue(issue)), ActionOutcome::Commented(comment)) => {
+                    // Only append once the whole thread is loaded, so paging stays in order
+                    if issue.next_comment_page.is_none() {
+                        issue.comments.push(comment.clone());
+                    }
+                    issue.total_comments += 1;
+                }
+                (Some(ItemDetail::Issue(issue)), ActionOutcome::StateChanged { open }) => {
+                    issue.open = *open;
+                    reopened = Some((false, issue.title.clone()));
+                }
+                (
+                    Some(ItemDetail::Pr(pr)),
+                    ActionOutcome::StateChanged { .. } | ActionOutcome::Merged(_),
+                ) => {
+                    pr.open = state == Some(true);
+                    reopened = Some((true, pr.title.clone()));
+                }
+                (Some(ItemDetail::Issue(issue)), ActionOutcome::Labels(labels)) => {
+                    issue.labels = labels.clone();
+                }
+                (Some(ItemDetail::Pr(pr)), ActionOutcome::Labels(labels)) => {
+                    pr.labels = labels.clone()
+                }
+                (Some(ItemDetail::Issue(issue)), ActionOutcome::Assignees(assignees)) => {
+                    issue.assignees = assignees.clone();
+                }
+                (Some(ItemDetail::Pr(pr)), ActionOutcome::Assignees(assignees)) => {
+                    pr.assignees = assignees.clone();
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(open) = state {
+            if let Some(repo) = self.repos.iter_mut().find(|r| r.path == path) {
+                if let (Some(data), Some(gh)) = (&mut repo.github_data, &repo.github_repo) {
+                    let is_pr = reopened
+                        .as_ref()
+                        .map(|(is_pr, _)| *is_pr)
+                        .unwrap_or_else(|| data.recent_prs.iter().any(|p| p.number == number));
+                    let (list, count) = if is_pr {
+                        (&mut data.recent_prs, &mut data.open_prs)
+                    } else {
+                        (&mut data.recent_issues, &mut data.open_issues)
+                    };
+                    if open {
+                        if let Some((_, title)) = reopened {
+                            if !list.iter().any(|i| i.number == number) {
+                                list.insert(0, GitHubItem { number, title });
+                                list.truncate(github::RECENT_ITEMS as usize);
+                            }
+                        }
+                        *count += 1;
+                    } else {
+                        list.retain(|i| i.number != number);
+                        *count = count.saturating_sub(1);
+                    }
+                    github::update_cached(gh, data);
+                }
+            }
+            if let Some(list) = self.item_list.as_mut().filter(|l| l.path == path) {
+                let shown = match list.filter.state {
+                    github::ItemState::Open => open,
+                    github::ItemState::Closed => !open,
+                    github::ItemState::All => true,
+                };
+                if let Some(item) = list.items.iter_mut().find(|i| i.number == number) {
+                    item.open = open;
+                }
+                if !shown {
+                    let before = list.items.len();
+                    list.items.retain(|i| i.number != number);
+                    let removed = (before - list.items.len()) as u64;
+                    list.total = list.total.map(|t| t.saturating_sub(removed));
+                }
+            }
+            let len = self.selected_items().len();
+            self.item_cursor = self.item_cursor.min(len.saturating_sub(1));
+        }
+
+        let text = match outcome {
+            ActionOutcome::Commented(_) => format!("Commented on #{number}"),
+            ActionOutcome::StateChanged { open: true } => format!("Reopened #{number}"),
+            ActionOutcome::StateChanged { open: false } => format!("Closed #{number}"),
+            ActionOutcome::Labels(_) => format!("Updated labels on #{number}"),
+            ActionOutcome::Assignees(_) => format!("Updated assignees on #{number}"),
+            ActionOutcome::Merged(summary) => summary,
+        };
+        self.show_flash(text, false);
+    }
+
+    /// Write `initial` to a scratch markdown file and queue `$EDITOR` on it.
+    /// The caller reads it back with [`read_scratch`] once the editor exits.
+    fn edit_scratch(
+        &mut self,
+        kind: &str,
+        initial: &str,
+        cwd: &std::path::Path,
+    ) -> Result<PathBuf, String> {
+        let file =
+            std::env::temp_dir().join(format!("project-dash-{kind}-{}.md", std::process::id()));
+        std::fs::write(&file, initial).map_err(|e| format!("{}: {e}", file.display()))?;
+        self.pending_external = Some(actions::edit_file_invocation(
+            &self.config.open,
+            &file,
+            cwd,
+        )?);
+        Ok(file)
+    }
+
+    /// Back to the top of the Changes tab, as when another repo is selected.
+    fn reset_detail(&mut self) {
+        self.detail_scroll = 0;
+        self.detail_tab = DetailTab::Changes;
+        self.change_cursor = 0;
+        self.item_cursor = 0;
+        self.item_view = None;
+        self.item_list = None;
+        self.actions = None;
+        self.releases = None;
+    }
+
+    fn switch_tab(&mut self, tab: DetailTab) {
+        self.detail_tab = tab;
+        self.detail_scroll = 0;
+        self.item_cursor = 0;
+        self.item_view = None;
+        self.item_list = None;
+        self.actions = None;
+        self.releases = None;
+        match tab {
+            DetailTab::Issues | DetailTab::Prs => {
+                let is_pr = tab == DetailTab::Prs;
+                if let Some(filter) = self.current_item_filter(is_pr) {
+                    self.load_item_list(filter);
+                }
+            }
+            DetailTab::Actions => self.load_workflow_runs(),
+            DetailTab::Releases => self.load_releases(),
+            _ => {}
+        }
+    }
+
+    /// The filter for the selected repo's issues or PRs: as last used this
+    /// session, else as saved in config, else open items.
+    fn current_item_filter(&self, is_pr: bool) -> Option<ItemFilter> {
+        let repo = self.selected_repo()?;
+        let gh = repo.github_repo.as_ref()?;
+        let filter = match self.item_filters.get(&(repo.path.clone(), is_pr)) {
+            Some(filter) => filter.clone(),
+            None => self
+                .config
+                .saved_filter(&gh.full_name(), is_pr)
+                .map(ItemFilter::parse)
+                .unwrap_or_default(),
+        };
+        Some(filter)
+    }
+
+    /// Start the selected repo's issue or PR list over with `filter`.
+    fn load_item_list(&mut self, filter: ItemFilter) {
+        let Some(path) = self.selected_repo().map(|r| r.path.clone()) else {
+            return;
+        };
+        let is_pr = self.detail_tab == DetailTab::Prs;
+        self.item_filters
+            .insert((path.clone(), is_pr), filter.clone());
+        self.item_cursor = 0;
+        self.detail_scroll = 0;
+        self.item_list = Some(ItemList {
+            path,
+            is_pr,
+            filter,
+            items: Vec::new(),
+            total: None,
+            next_page: Some(1),
+            loading: false,
+            error: None,
+        });
+        self.load_next_item_page();
+    }
+
+    fn load_next_item_page(&mut self) {
+        let Some(list) = &self.item_list else {
+            return;
+        };
+        let Some(page) = list.next_page.filter(|_| !list.loading) else {
+            return;
+        };
+        if self.config.github.offline {
+            if let Some(list) = &mut self.item_list {
+                list.error = Some("offline mode".to_string());
+            }
+            return;
+        }
+        let Some((gh, target)) = self.github_repo_target(&list.path) else {
+            return;
+        };
+        let (path, is_pr, filter) = (list.path.clone(), list.is_pr, list.filter.clone());
+        if let Some(list) = &mut self.item_list {
+            list.loading = true;
+            list.error = None;
+        }
+        github::spawn_item_page_fetch(path, gh, is_pr, filter, page, target, self.tx.clone());
+    }
+
+    /// Keys of the Issues and PRs lists: edit the filter, cycle the state
+    /// shown and save the filter as the repo's default.
+    fn handle_item_list_key(&mut self, key: KeyEvent) -> bool {
+        if self.item_view.is_some() {
+            return false;
+        }
+        let Some(list) = &self.item_list else {
+            return false;
+        };
+        match key.code {
+            KeyCode::Char('/') => {
+                let query = list.filter.query();
+                self.open_prompt(
+                    Prompt::new("Filter (GitHub search syntax)", PromptAction::ItemFilter)
+                        .with_input(query),
+                );
+            }
+            KeyCode::Char(c @ ('C' | 'W')) if list.is_pr => {
+                let Some(&number) = self.selected_items().get(self.item_cursor) else {
+                    return true;
+                };
+                let path = list.path.clone();
+                let Some((gh, target)) = self.github_repo_target(&path) else {
+                    return true;
+                };
+                github::spawn_pr_head_fetch(path, gh, number, c == 'W', target, self.tx.clone());
+                self.show_flash(format!("Looking up PR #{number}..."), false);
+            }
+            KeyCode::Char('v') => {
+                let mut filter = list.filter.clone();
+                filter.state = filter.state.next();
+                self.load_item_list(filter);
+            }
+            KeyCode::Char('F') => {
+                let (is_pr, query) = (list.is_pr, list.filter.query());
+                let Some(gh) = self.selected_repo().and_then(|r| r.github_repo.clone()) else {
+                    return true;
+                };
+                match self.config.save_filter(&gh.full_name(), is_pr, &query) {
+                    Ok(()) => {
+                        self.show_flash(format!("Saved filter for {}", gh.full_name()), false)
+                    }
+                    Err(e) => self.show_flash(format!("Saving filter: {e}"), true),
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Whether the Actions tab shows its run list rather than a log.
+    fn run_list_active(&self) -> bool {
+        self.detail_tab == DetailTab::Actions
+            && self.actions.as_ref().is_some_and(|a| a.log.is_none())
+    }
+
+    fn selected_run(&self) -> Option<&WorkflowRun> {
+        let actions = self.actions.as_ref()?;
+        actions.runs.as_ref()?.get(actions.cursor)
+    }
+
+    /// (Re)load the selected repo's workflow runs, keeping the cursor.
+    fn load_workflow_runs(&mut self) {
+        let Some(path) = self.selected_repo().map(|r| r.path.clone()) else {
+            return;
+        };
+        let Some((gh, target)) = self.github_repo_target(&path) else {
+            return;
+        };
+        let cursor = self
+            .actions
+            .as_ref()
+            .filter(|a| a.path == path)
+            .map_or(0, |a| a.cursor);
+        self.actions = Some(ActionsView {
+            path: path.clone(),
+            runs: None,
+            error: None,
+            cursor,
+            log: None,
+        });
+        github::spawn_workflow_runs_fetch(path, gh, target, self.tx.clone());
+    }
+
+    fn open_run_logs(&mut self) {
+        let Some(run) = self.selected_run().cloned() else {
+            return;
+        };
+        let Some(actions) = &mut self.actions else {
+            return;
+        };
+        let path = actions.path.clone();
+        actions.log = Some(RunLogView {
+            run_id: run.id,
+            title: format!("{} #{}", run.workflow, run.run_number),
+            jobs: None,
+        });
+        self.detail_scroll = 0;
+        if let Some((gh, target)) = self.github_repo_target(&path) {
+            github::spawn_run_logs_fetch(path, gh, run.id, target, self.tx.clone());
+        }
+    }
+
+    /// Keys specific to the focused detail tab.
+    fn handle_tab_key(&mut self, key: KeyEvent) -> bool {
+        if self.active_pane != ActivePane::Detail {
+            return false;
+        }
+        match self.detail_tab {
+            DetailTab::Issues | DetailTab::Prs => self.handle_item_list_key(key),
+            DetailTab::Actions => self.handle_actions_key(key),
+            DetailTab::Releases => self.handle_releases_key(key),
+            _ => false,
+        }
+    }
+
+    fn load_releases(&mut self) {
+        let Some(path) = self.selected_repo().map(|r| r.path.clone()) else {
+            return;
+        };
+        let Some((gh, target)) = self.github_repo_target(&path) else {
+            return;
+        };
+        self.releases = Some(ReleasesView {
+            path: path.clone(),
+            info: None,
+            creating: false,
+        });
+        github::spawn_releases_fetch(path, gh, target, self.tx.clone());
+    }
+
+    /// `D` asks for the tag of a draft release built from the preview.
+    fn handle_releases_key(&mut self, key: KeyEvent) -> bool {
+        if key.code != KeyCode::Char('D') {
+            return false;
+        }
+        match &self.releases {
+            Some(ReleasesView { creating: true, .. }) => {
+                self.show_flash("A draft release is already being created", true);
+            }
+            Some(ReleasesView {
+                info: Some(Ok(info)),
+                ..
+            }) => {
+                let tag = info.preview.next_tag.clone();
+                self.open_prompt(
+                    Prompt::new("Tag for draft release", PromptAction::DraftRelease)
+                        .with_input(tag),
+                );
+            }
+            _ => self.show_flash("Releases are still loading", true),
+        }
+        true
+    }
+
+    /// Keys of the Actions tab: re-run failed jobs and open the run on GitHub.
+    fn handle_actions_key(&mut self, key: KeyEvent) -> bool {
+        let run_id = match &self.actions {
+            Some(ActionsView { log: Some(log), .. }) => Some(log.run_id),
+            _ => self.selected_run().map(|r| r.id),
+        };
+        let Some(run) = self
+            .actions
+            .as_ref()
+            .and_then(|a| a.runs.as_ref()?.iter().find(|r| Some(r.id) == run_id))
+            .cloned()
+        else {
+            return false;
+        };
+        match key.code {
+            KeyCode::Char('f') => {
+                if run.state != github::CheckState::Failure {
+                    self.show_flash("Run has no failed jobs to re-run", true);
+                    return true;
+                }
+                let Some(path) = self.actions.as_ref().map(|a| a.path.clone()) else {
+                    return true;
+                };
+                if let Some((gh, target)) = self.github_repo_target(&path) {
+                    github::spawn_rerun_failed_jobs(path, gh, run.id, target, self.tx.clone());
+                    self.show_flash(
+                        format!(
+                            "Requesting re-run of {} #{}...",
+                            run.workflow, run.run_number
+                        ),
+                        false,
+                    );
+                }
+            }
+            KeyCode::Char('o') => {
+                let _ = open::that(&run.html_url);
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Whether the detail pane shows a selectable issue or PR list.
+    fn item_list_active(&self) -> bool {
+        matches!(self.detail_tab, DetailTab::Issues | DetailTab::Prs) && self.item_view.is_none()
+    }
+
+    /// Numbers of the items listed in the current tab of the selected repo:
+    /// the filtered list once it has items, else the recent ones.
+    fn selected_items(&self) -> Vec<u64> {
+        if let Some(list) = self.item_list.as_ref().filter(|l| !l.items.is_empty()) {
+            return list.items.iter().map(|i| i.number).collect();
+        }
+        let Some(data) = self.selected_repo().and_then(|r| r.github_data.as_ref()) else {
+            return Vec::new();
+        };
+        let recent = match self.detail_tab {
+            DetailTab::Issues => &data.recent_issues,
+            DetailTab::Prs => &data.recent_prs,
+            _ => return Vec::new(),
+        };
+        // Only stand in for the list while it shows the default filter
+        if self
+            .item_list
+            .as_ref()
+            .is_some_and(|l| l.filter != ItemFilter::default())
+        {
+            return Vec::new();
+        }
+        recent.iter().map(|i| i.number).collect()
+    }
+
+    fn open_selected_item(&mut self) {
+        let Some(&number) = self.selected_items().get(self.item_cursor) else {
+            return;
+        };
+        let Some(path) = self.selected_repo().map(|r| r.path.clone()) else {
+            return;
+        };
+        self.open_item(path, number, self.detail_tab == DetailTab::Prs);
+    }
+
+    fn open_item(&mut self, path: PathBuf, number: u64, is_pr: bool) {
+        self.item_view = Some(ItemView {
+            path,
+            number,
+            is_pr,
+            detail: None,
+            error: None,
+            loading_more: false,
+            comment_file: None,
+        });
+        self.detail_scroll = 0;
+        self.fetch_item_view();
+    }
+
+    /// The GitHub repo and API target of the repository at `path`.
+    fn github_repo_target(
+        &self,
+        path: &std::path::Path,
+    ) -> Option<(crate::repo::GitHubRepo, github::HostTarget)> {
+        let gh = self
+            .repos
+            .iter()
+            .find(|r| r.path == path)
+            .and_then(|r| r.github_repo.clone())?;
+        let target = self.github_target(&gh.host, Some(&gh.owner));
+        Some((gh, target))
+    }
+
+    fn fetch_item_view(&self) {
+        let Some(view) = &self.item_view else {
+            return;
+        };
+        let Some((gh, target)) = self.github_repo_target(&view.path) else {
+            return;
+        };
+        github::spawn_item_detail_fetch(
+            view.path.clone(),
+            gh,
+            view.number,
+            view.is_pr,
+            target,
+            self.tx.clone(),
+        );
+    }
+
+    fn load_more_comments(&mut self) {
+        let Some(view) = &self.item_view else {
+            return;
+        };
+        let Some(ItemDetail::Issue(issue)) = &view.detail else {
+            return;
+        };
+        let Some(page) = issue.next_comment_page.filter(|_| !view.loading_more) else {
+            return;
+        };
+        let Some((gh, target)) = self.github_repo_target(&view.path) else {
+            return;
+        };
+        github::spawn_issue_comments_fetch(
+            view.path.clone(),
+            gh,
+            view.number,
+            page,
+            target,
+            self.tx.clone(),
+        );
+        if let Some(view) = &mut self.item_view {
+            view.loading_more = true;
+        }
+    }
+
+    /// Repositories a bulk action applies to: the marked ones, or the
+    /// selected repo when nothing is marked.
+    pub fn bulk_targets(&self) -> Vec<&RepoInfo> {
+        if self.marked.is_empty() {
+            self.selected_repo().into_iter().collect()
+        } else {
+            self.repos
+                .iter()
+                .filter(|r| self.marked.contains(&r.path))
+                .collect()
+        }
+    }
+
+    fn submit_prompt(&mut self, prompt: Prompt) {
+        let input = prompt.input.trim().to_string();
+        match prompt.action {
+            PromptAction::MarkMatching => {
+                let needle = input.to_lowercase();
+                for repo in &self.repos {
+                    if repo.name.to_lowercase().contains(&needle) {
+                        self.marked.insert(repo.path.clone());
+                    }
+                }
+            }
+            PromptAction::Bulk(action) => {
+                if !input.is_empty() {
+                    self.start_bulk(action.with_input(input));
+                }
+            }
+            PromptAction::CloneSource => match git::parse_clone_source(&input) {
+                Some((url, name)) => {
+                    let title = format!("Destination under {}", self.scan_path.display());
+                    self.open_prompt(
+                        Prompt::new(title, PromptAction::CloneDest { url }).with_input(name),
+                    );
+                }
+                None if !input.is_empty() => {
+                    self.open_prompt(
+                        Prompt::new(
+                            "Not a URL or owner/name — clone from",
+                            PromptAction::CloneSource,
+                        )
+                        .with_input(input),
+                    );
+                }
+                None => {}
+            },
+            PromptAction::CloneDest { url } => {
+                match git::resolve_clone_dest(&self.scan_path, &input) {
+                    Ok(dest) => self.start_clones(vec![(url, dest)]),
+                    Err(e) => {
+                        self.open_prompt(
+                            Prompt::new(e, PromptAction::CloneDest { url }).with_input(input),
+                        );
+                    }
+                }
+            }
+            PromptAction::RunCommand => {
+                if !input.is_empty() {
+                    self.run_command(input);
+                }
+            }
+            PromptAction::CheckoutPr { number, head } => {
+                if !input.is_empty() {
+                    self.checkout_pr(number, head, input, None);
+                }
+            }
+            PromptAction::PrWorktree { number, head } => {
+                let Some(path) = self.selected_repo().map(|r| r.path.clone()) else {
+                    return;
+                };
+                if !input.is_empty() {
+                    // Relative paths are taken from the repo's parent directory
+                    let parent = path.parent().unwrap_or(&path);
+                    let dir = parent.join(&input);
+                    self.checkout_pr(
+                        number,
+                        head.clone(),
+                        git::pr_branch_name(number, &head),
+                        Some(dir),
+                    );
+                }
+            }
+            PromptAction::ItemFilter => {
+                self.load_item_list(ItemFilter::parse(&input));
+            }
+            PromptAction::DraftRelease => {
+                let Some(view) = self.releases.as_mut() else {
+                    return;
+                };
+                let Some(Ok(info)) = &view.info else {
+                    return;
+                };
+                if input.is_empty() {
+                    return;
+                }
+                let (path, branch, notes) = (
+                    view.path.clone(),
+                    info.preview.branch.clone(),
+                    info.preview.notes(),
+                );
+                let Some((gh, target)) = self.github_repo_target(&path) else {
+                    self.show_flash("No GitHub remote", true);
+                    return;
+                };
+                if let Some(view) = self.releases.as_mut() {
+                    view.creating = true;
+                }
+                self.show_flash(format!("Creating draft release {input}..."), false);
+                github::spawn_create_draft_release(
+                    path,
+                    gh,
+                    input,
+                    branch,
+                    notes,
+                    target,
+                    self.tx.clone(),
+                );
+            }
+            PromptAction::ListOwner => {
+                if input.is_empty() {
+                    return;
+                }
+                self.overlay = Some(Overlay::RemoteRepos(RemoteRepoList {
+                    owner: input.clone(),
+                    loading: true,
+                    ..Default::default()
+                }));
+                let target = self.github_target(crate::repo::GITHUB_HOST, Some(&input));
+                github::spawn_list_owner_repos(input, target, self.tx.clone());
+            }
+        }
+    }
+
+    /// Clone each `(url, dest)` pair in the background, showing progress in
+    /// the bulk progress view and adding each repo to the list when done.
+    fn start_clones(&mut self, clones: Vec<(String, PathBuf)>) {
+        if clones.is_empty() {
+            return;
+        }
+        let scan_path = self.scan_path.clone();
+        self.overlay = Some(Overlay::BulkProgress(BulkProgress {
+            label: "Clone".to_string(),
+            entries: clones
+                .iter()
+                .map(|(_, dest)| BulkEntry {
+                    path: dest.clone(),
+                    name: dest
+                        .strip_prefix(&scan_path)
+                        .unwrap_or(dest)
+                        .display()
+                        .to_string(),
+                    state: BulkState::Running,
+                    progress: None,
+                })
+                .collect(),
+            scroll: 0,
+        }));
+
+        let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(BULK_CONCURRENCY));
+        for (url, dest) in clones {
+            let semaphore = semaphore.clone();
+            let hosts = self.config.github.extra_hosts();
+            let tx = self.tx.clone();
+            tokio::spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                let progress_tx = tx.clone();
+                let path = dest.clone();
+                let result = tokio::task::spawn_blocking(move || {
+                    git::clone_repo(&url, &dest, |progress| {
+                        let _ = progress_tx.send(Message::BulkProgressUpdate {
+                            path: dest.clone(),
+                            progress,
+                        });
+                    })?;
+                    crate::repo::rescan_repo(&dest, &hosts)
+                        .ok_or_else(|| "cloned, but could not read the repository".to_string())
+                })
+                .await
+                .unwrap_or_else(|e| Err(e.to_string()));
+                let result = match result {
+                    Ok(info) => {
+                        let _ = tx.send(Message::RepoCloned(Box::new(info)));
+                        Ok("cloned".to_string())
+                    }
+                    Err(e) => Err(e),
+                };
+                let _ = tx.send(Message::BulkResult { path, result });
+            });
+        }
+    }
+
+    pub fn show_flash(&mut self, text: impl Into<String>, error: bool) {
+        self.flash = Some(Flash {
+            text: text.into(),
+            error,
+            shown_at: std::time::Instant::now(),
+        });
+    }
+
+    fn open_prompt(&mut self, prompt: Prompt) {
+        self.overlay = Some(Overlay::Prompt(prompt));
+    }
+
+    /// Run a shell command in every bulk target concurrently, streaming its
+    /// output into the per-repo Output tab.
+    fn run_command(&mut self, command: String) {
+        self.command_history.retain(|c| c != &command);
+        self.command_history.push(command.clone());
+        if self.command_history.len() > MAX_COMMAND_HISTORY {
+            self.command_history.remove(0);
+        }
+
+        let paths: Vec<PathBuf> = self.bulk_targets().iter().map(|r| r.path.clone()).collect();
+        let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(BULK_CONCURRENCY));
+        for path in paths {
+            self.command_runs.insert(
+                path.clone(),
+                CommandRun {
+                    command: command.clone(),
+                    output: Vec::new(),
+                    status: RunStatus::Running,
+                },
+            );
+            let command = command.clone();
+            let semaphore = semaphore.clone();
+            let tx = self.tx.clone();
+            tokio::spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                let status = actions::stream_command(&path, &command, &tx).await;
+                let _ = tx.send(Message::CommandExited { path, status });
+            });
+        }
+
+        self.detail_tab = DetailTab::Output;
+        self.detail_scroll = 0;
+    }
+
+    fn start_bulk(&mut self, action: BulkAction) {
+        if let BulkAction::RunCommand(command) = action {
+            self.run_command(command);
+            return;
+        }
+
+        let targets: Vec<(PathBuf, String)> = self
+            .bulk_targets()
+            .iter()
+            .map(|r| (r.path.clone(), r.name.clone()))
+            .collect();
+        if targets.is_empty() {
+            return;
+        }
+
+        self.overlay = Some(Overlay::BulkProgress(BulkProgress {
+            label: action.label(),
+            entries: targets
+                .iter()
+                .map(|(path, name)| BulkEntry {
+                    path: path.clone(),
+                    name: name.clone(),
+                    state: BulkState::Running,
+                    progress: None,
+                })
+                .collect(),
+            scroll: 0,
+        }));
+
+        let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(BULK_CONCURRENCY));
+        for (path, _) in targets {
+            let action = action.clone();
+            let config = self.config.clone();
+            let hosts = config.github.extra_hosts();
+            let semaphore = semaphore.clone();
+            let tx = self.tx.clone();
+            tokio::spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                let run_path = path.clone();
+                let rescan = action.modifies_repo();
+                let result = tokio::task::spawn_blocking(move || {
+                    let result = action.run(&run_path, &config);
+                    let info = if rescan {
+                        crate::repo::rescan_repo(&run_path, &hosts)
+                    } else {
+                        None
+                    };
+                    (result, info)
+                })
+                .await;
+                let (result, info) = match result {
+                    Ok(r) => r,
+                    Err(e) => (Err(e.to_string()), None),
+                };
+                if let Some(info) = info {
+                    let _ = tx.send(Message::RepoUpdated(Box::new(info)));
+                }
+                let _ = tx.send(Message::BulkResult { path, result });
+            });
+        }
+    }
+
+    fn handle_overlay_key(&mut self, key: KeyEvent) {
+        let Some(overlay) = &mut self.overlay else {
+            return;
+        };
+        match overlay {
+            Overlay::BulkMenu { cursor } => {
+                let menu = BulkAction::menu();
+                match key.code {
+                    KeyCode::Esc | KeyCode::Char('q') => self.overlay = None,
+                    KeyCode::Up | KeyCode::Char('k') => *cursor = cursor.saturating_sub(1),
+                    KeyCode::Down | KeyCode::Char('j') if *cursor + 1 < menu.len() => {
+                        *cursor += 1;
+                    }
+                    KeyCode::Enter => {
+                        let action = menu[*cursor].clone();
+                        match action.prompt() {
+                            Some(_) if matches!(action, BulkAction::RunCommand(_)) => {
+                                self.update(Message::OpenCommandPrompt);
+                            }
+                            Some(title) => {
+                                self.open_prompt(Prompt::new(title, PromptAction::Bulk(action)));
+                            }
+                            None => self.start_bulk(action),
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Overlay::Prompt(prompt) => match key.code {
+                KeyCode::Esc => self.overlay = None,
+                KeyCode::Enter => {
+                    if let Some(Overlay::Prompt(prompt)) = self.overlay.take() {
+                        self.submit_prompt(prompt);
+                    }
+                }
+                KeyCode::Backspace => {
+                    prompt.input.pop();
+                }
+                KeyCode::Char(c) => prompt.input.push(c),
+                KeyCode::Up if matches!(prompt.action, PromptAction::RunCommand) => {
+                    let pos = match prompt.history_pos {
+                        Some(p) => p.saturating_sub(1),
+                        None => self.command_history.len().saturating_sub(1),
+                    };
+                    if let Some(cmd) = self.command_history.get(pos) {
+                        prompt.input = cmd.clone();
+                        prompt.history_pos = Some(pos);
+                    }
+                }
+                KeyCode::Down if matches!(prompt.action, PromptAction::RunCommand) => {
+                    if let Some(pos) = prompt.history_pos {
+                        if pos + 1 < self.command_history.len() {
+                            prompt.input = self.command_history[pos + 1].clone();
+                            prompt.history_pos = Some(pos + 1);
+                        } else {
+                            prompt.input.clear();
+                            prompt.history_pos = None;
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Overlay::RemoteRepos(list) => match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => self.overlay = None,
+                KeyCode::Up | KeyCode::Char('k') => list.cursor = list.cursor.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') if list.cursor + 1 < list.entries.len() => {
+                    list.cursor += 1;
+                }
+                KeyCode::Char(' ') => {
+                    if let Some(entry) = list.entries.get_mut(list.cursor) {
+                        entry.selected = !entry.selected;
+                    }
+                }
+                KeyCode::Char('a') => {
+                    let select = !list.entries.iter().all(|e| e.selected);
+                    for entry in &mut list.entries {
+                        entry.selected = select;
+                    }
+                }
+                KeyCode::Enter => {
+                    let clones: Vec<(String, PathBuf)> = list
+                        .entries
+                        .iter()
+                        .filter(|e| e.selected)
+                        .map(|e| (e.repo.clone_url.clone(), self.scan_path.join(&e.repo.name)))
+                        .collect();
+                    self.start_clones(clones);
+                }
+                _ => {}
+            },
+            Overlay::IssueForm(_) => self.handle_issue_form_key(key),
+            Overlay::MergeForm(_) => self.handle_merge_form_key(key),
+            Overlay::LabelPicker(picker) => match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => self.overlay = None,
+                KeyCode::Up | KeyCode::Char('k') => picker.cursor = picker.cursor.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') if picker.cursor + 1 < picker.choices.len() => {
+                    picker.cursor += 1;
+                }
+                KeyCode::Char(' ') => {
+                    if let Some(choice) = picker.choices.get_mut(picker.cursor) {
+                        choice.selected = !choice.selected;
+                    }
+                }
+                KeyCode::Enter => {
+                    let labels = selected_names(&picker.choices);
+                    self.overlay = None;
+                    self.run_item_action(ItemAction::SetLabels(labels));
+                }
+                _ => {}
+            },
+            Overlay::BulkProgress(progress) => match key.code {
+                KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => self.overlay = None,
+                KeyCode::Up | KeyCode::Char('k') => {
+                    progress.scroll = progress.scroll.saturating_sub(1);
+                }
+                KeyCode::Down | KeyCode::Char('j')
+                    if progress.scroll + 1 < progress.entries.len() =>
+                {
+                    progress.scroll += 1;
+                }
+                _ => {}
+            },
+            Overlay::BranchCleanup(cleanup) if cleanup.confirm_unmerged => {
+                cleanup.confirm_unmerged = false;
+                if key.code == KeyCode::Char('y') {
+                    self.delete_selected_branches(true);
+                }
+            }
+            Overlay::BranchCleanup(cleanup) => match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => self.overlay = None,
+                KeyCode::Up | KeyCode::Char('k') => {
+                    cleanup.cursor = cleanup.cursor.saturating_sub(1);
+                }
+                KeyCode::Down | KeyCode::Char('j')
+                    if cleanup.cursor + 1 < cleanup.entries.len() =>
+                {
+                    cleanup.cursor += 1;
+                }
+                KeyCode::Char(' ') => {
+                    if let Some(entry) = cleanup.entries.get_mut(cleanup.cursor) {
+                        entry.selected = !entry.selected;
+                    }
+                }
+                KeyCode::Char('a') => {
+                    let select = !cleanup.entries.iter().all(|e| e.selected);
+                    for entry in &mut cleanup.entries {
+                        entry.selected = select;
+                    }
+                }
+                KeyCode::Enter => {
+                    if cleanup.loading {
+                        return;
+                    }
+                    if cleanup
+                        .entries
+                        .iter()
+                        .any(|e| e.selected && !e.branch.merged)
+                    {
+                        cleanup.confirm_unmerged = true;
+                        return;
+                    }
+                    self.delete_selected_branches(false);
+                }
+                _ => {}
+            },
+        }
+    }
+
+    /// Delete the branches selected in the cleanup overlay. Unmerged ones
+    /// are refused unless `force`, given once the user has confirmed.
+    fn delete_selected_branches(&mut self, force: bool) {
+        let Some(Overlay::BranchCleanup(cleanup)) = &mut self.overlay else {
+            return;
+        };
+        let targets: Vec<StaleBranch> = cleanup
+            .entries
+            .iter()
+            .filter(|e| e.selected)
+            .map(|e| e.branch.clone())
+            .collect();
+        if targets.is_empty() {
+            return;
+        }
+        cleanup.loading = true;
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            let results = tokio::task::spawn_blocking(move || {
+                targets
+                    .into_iter()
+                    .map(|b| {
+                        let result = git::delete_branch(&b.repo_path, &b.branch, force);
+                        (b, result)
+                    })
+                    .collect()
+            })
+            .await
+            .unwrap_or_default();
+            let _ = tx.send(Message::BranchesDeleted(results));
+        });
+    }
+
+    /// Re-analyze one repository in the background, bypassing the scan cache.
+    fn spawn_repo_rescan(&self, path: PathBuf) {
+        let hosts = self.config.github.extra_hosts();
+        let tx = self.tx.clone();
+        tokio::spawn(async move {
+            let info = tokio::task::spawn_blocking(move || crate::repo::rescan_repo(&path, &hosts))
+                .await
+                .ok()
+                .flatten();
+            if let Some(info) = info {
+                let _ = tx.send(Message::RepoUpdated(Box::new(info)));
+            }
+        });
+    }
+
+    fn maybe_fetch_selected_github(&mut self) {
+        self.maybe_fetch_branch_pr();
+        let Some(repo) = self
+            .table_state
+            .selected()
+            .and_then(|i| self.repos.get_mut(i))
+        else {
+            return;
+        };
+        let Some(gh) = repo.github_repo.clone() else {
+            return;
+        };
+
+        // Skip if already refreshed, errored, or in-flight
+        if (repo.github_data.is_some() && self.github_fresh.contains(&repo.path))
+            || repo.github_error.is_some()
+            || self.github_fetching.contains(&repo.path)
+        {
+            return;
+        }
+
+        // Show the last known data right away while it is refreshed
+        if repo.github_data.is_none() {
+            repo.github_data = github::cached_data(&gh);
+        }
+        if self.config.github.offline {
+            if repo.github_data.is_none() {
+                repo.github_error = Some("Offline — nothing cached for this repo".to_string());
+            }
+            return;
+        }
+        // Fetched once the tokens are known
+        if self.discovering_tokens {
+            return;
+        }
+
+        let path = repo.path.clone();
+        self.github_fetching.insert(path.clone());
+        let target = self.github_target(&gh.host, Some(&gh.owner));
+        github::spawn_github_fetch(path, gh, target, self.tx.clone());
+    }
+
+    /// Fetch the selected repo's GitHub data on its own, ahead of the
+    /// queue, then prefetch the rest.
+    fn start_github_fetches(&mut self) {
+        self.maybe_fetch_selected_github();
+        self.prefetch_github();
+    }
+
+    /// Hosts of scanned repos that some repo has no configured token for,
+    /// where a discovered one would be used. None when offline.
+    fn hosts_without_token(&self) -> Vec<String> {
+        if self.config.github.offline {
+            return Vec::new();
+        }
+        let mut hosts: Vec<String> = Vec::new();
+        for gh in self.repos.iter().filter_map(|r| r.github_repo.as_ref()) {
+            let target = github::host_target(
+                &self.config.github,
+                &gh.host,
+                Some(&gh.owner),
+                self.github_token.as_ref(),
+            );
+            if target.token.is_none() && !hosts.contains(&target.host) {
+                hosts.push(target.host);
+            }
+        }
+        hosts
+    }
+
+    /// Show cached GitHub data for every repo and refresh it in the
+    /// background, a few repos at a time, so the list can show counts
+    /// without each repo being selected first. Queued repos only count as
+    /// in flight once their turn comes, so selecting one fetches it at once.
+    fn prefetch_github(&mut self) {
+        let concurrency = self.config.github.prefetch_concurrency();
+        let offline = self.config.github.offline;
+        let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(concurrency.max(1)));
+        let mut jobs: Vec<(PathBuf, crate::repo::GitHubRepo)> = Vec::new();
+        for repo in &mut self.repos {
+            let Some(gh) = &repo.github_repo else {
+                continue;
+            };
+            if repo.github_data.is_none() {
+                repo.github_data = github::cached_data(gh);
+            }
+            if concurrency > 0 && !offline && !self.github_fetching.contains(&repo.path) {
+                jobs.push((repo.path.clone(), gh.clone()));
+            }
+        }
+
+        // One batch per account, since GraphQL queries can't mix tokens
+        let mut batches: Vec<(github::HostTarget, Vec<(PathBuf, crate::repo::GitHubRepo)>)> =
+            Vec::new();
+        for (path, gh) in jobs {
+            let target = self.github_target(&gh.host, Some(&gh.owner));
+            match batches
+                .iter_mut()
+                .find(|(t, _)| t.host == target.host && t.token == target.token)
+            {
+                Some((_, repos)) => repos.push((path, gh)),
+                None => batches.push((target, vec![(path, gh)])),
+            }
+        }
+        for (target, repos) in batches {
+            github::spawn_github_prefetch(repos, target, semaphore.clone(), self.tx.clone());
+        }
+    }
+
+    /// Whether a repo's GitHub data is being fetched.
+    pub fn github_refreshing(&self, path: &std::path::Path) -> bool {
+        self.github_fetching.contains(path)
+    }
+
+    /// Look up the PR for the selected repo's current branch, unless known
+    /// for that branch already or in flight.
+    fn maybe_fetch_branch_pr(&mut self) {
+        let Some(repo) = self.selected_repo() else {
+            return;
+        };
+        let Some(gh) = repo.github_repo.clone() else {
+            return;
+        };
+        let branch = repo.current_branch.clone();
+        if self.config.github.offline
+            || self.discovering_tokens
+            || branch.starts_with("detached")
+            || self.branch_pr_fetching.contains(&repo.path)
+            || self
+                .branch_prs
+                .get(&repo.path)
+                .is_some_and(|(b, _)| *b == branch)
+        {
+            return;
+        }
+
+        // A fork workflow pushes the branch to the fork, which then owns the PR head
+        let hosts = self.config.github.extra_hosts();
+        let head_owner = git::branch_push_url(&repo.path, &branch)
+            .and_then(|url| crate::repo::parse_github_url(&url, &hosts))
+            .filter(|pushed| pushed.host.eq_ignore_ascii_case(&gh.host))
+            .map_or_else(|| gh.owner.clone(), |pushed| pushed.owner);
+
+        let path = repo.path.clone();
+        self.branch_pr_fetching.insert(path.clone());
+        let target = self.github_target(&gh.host, Some(&gh.owner));
+        github::spawn_branch_pr_fetch(path, gh, head_owner, branch, target, self.tx.clone());
+    }
+
+    /// API endpoint and token for a GitHub host: from config or the CLI
+    /// token, falling back to one the `gh` CLI or a git credential helper
+    /// was found to have after the scan.
+    pub fn github_target(&self, host: &str, owner: Option<&str>) -> github::HostTarget {
+        github::resolve_target(&self.config.github, host, owner, self.github_token.as_ref())
+    }
+}
+
+/// Contents of a scratch file written by the editor, which is then removed.
+fn read_scratch(file: &std::path::Path) -> Option<String> {
+    let contents = std::fs::read_to_string(file).ok();
+    let _ = std::fs::remove_file(file);
+    contents.map(|c| c.trim_end().to_string())
+}
+
+/// Map a key press on the main dashboard to a message.
+fn map_key(key: KeyEvent) -> Option<Message> {
+    match key.code {
+        KeyCode::Char('q') => Some(Message::Quit),
+        KeyCode::Up | KeyCode::Char('k') => Some(Message::MoveUp),
+        KeyCode::Down | KeyCode::Char('j') => Some(Message::MoveDown),
+        KeyCode::Char('r') => Some(Message::Refresh),
+        KeyCode::Char('R') => Some(Message::ForceRefresh),
+        KeyCode::Tab => Some(Message::SwitchPane),
+        KeyCode::Enter => Some(Message::Activate),
+        KeyCode::Esc => Some(Message::FocusList),
+        KeyCode::Char(']') => Some(Message::NextTab),
+        KeyCode::Char('[') => Some(Message::PrevTab),
+        KeyCode::Char('b') => Some(Message::OpenBranchCleanup { all_repos: false }),
+        KeyCode::Char('B') => Some(Message::OpenBranchCleanup { all_repos: true }),
+        KeyCode::Char(' ') => Some(Message::ToggleMark),
+        KeyCode::Char('a') => Some(Message::ToggleMarkAll),
+        KeyCode::Char('*') => Some(Message::OpenMarkPrompt),
+        KeyCode::Char('x') => Some(Message::OpenBulkMenu),
+        KeyCode::Char('c') => Some(Message::OpenClonePrompt),
+        KeyCode::Char('C') => Some(Message::OpenOwnerPrompt),
+        KeyCode::Char('!') => Some(Message::OpenCommandPrompt),
+        KeyCode::Char('e') => Some(Message::OpenEditor),
+        KeyCode::Char('s') => Some(Message::OpenShell),
+        KeyCode::Char('t') => Some(Message::OpenTmux),
+        KeyCode::Char('n') => Some(Message::LoadMoreComments),
+        KeyCode::Char('i') => Some(Message::OpenIssueForm),
+        KeyCode::Char('w') => Some(Message::ToggleInbox),
+        KeyCode::Char('N') => Some(Message::ToggleNotifications),
+        KeyCode::Char('P') => Some(Message::CreateBranchPr),
+        _ => None,
     }
 }
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+
+/// Where the token used for a GitHub host came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenSource {
+    Flag,
+    Env,
+    /// A host's `token` in config.
+    Config,
+    /// An entry of a host's `owner_tokens` in config.
+    ConfigOwner(String),
+    GhCli,
+    GitCredential,
+}
+
+impl TokenSource {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Flag => "--github-token".to_string(),
+            Self::Env => "GITHUB_TOKEN".to_string(),
+            Self::Config => "config".to_string(),
+            Self::ConfigOwner(owner) => format!("config ({owner})"),
+            Self::GhCli => "gh CLI".to_string(),
+            Self::GitCredential => "git credential".to_string(),
+        }
+    }
+}
+
+/// A token and where it came from.
+pub type Token = (String, TokenSource);
+
+/// Tokens found outside our own config, per host, so the gh config is read
+/// and the credential helper asked at most once per host.
+static DISCOVERED: std::sync::LazyLock<Mutex<HashMap<String, Option<Token>>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// A token for `host` from the `gh` CLI's `hosts.yml`, or failing that
+/// from `git credential fill`. This blocks on the credential helper, so
+/// it runs off the UI thread; [`known_token`] reads the result.
+pub fn discovered_token(host: &str) -> Option<Token> {
+    let host = host.to_lowercase();
+    if let Some(found) = DISCOVERED.lock().ok()?.get(&host) {
+        return found.clone();
+    }
+    let found = gh_cli_token(&host)
+        .map(|t| (t, TokenSource::GhCli))
+        .or_else(|| git_credential_token(&host).map(|t| (t, TokenSource::GitCredential)));
+    if let Ok(mut discovered) = DISCOVERED.lock() {
+        discovered.insert(host, found.clone());
+    }
+    found
+}
+
+/// The token [`discovered_token`] found for `host`, if it ran already.
+pub fn known_token(host: &str) -> Option<Token> {
+    DISCOVERED
+        .lock()
+        .ok()?
+        .get(&host.to_lowercase())
+        .cloned()
+        .flatten()
+}
+
+/// `$GH_CONFIG_DIR/hosts.yml`, falling back to `gh`'s default config
+/// directory.
+fn gh_hosts_path() -> Option<PathBuf> {
+    let dir = std::env::var_os("GH_CONFIG_DIR")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("XDG_CONFIG_HOME")
+                .filter(|v| !v.is_empty())
+                .map(|base| PathBuf::from(base).join("gh"))
+        })
+        .or_else(|| {
+            std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config").join("gh"))
+        })?;
+    Some(dir.join("hosts.yml"))
+}
+
+fn gh_cli_token(host: &str) -> Option<String> {
+    let contents = std::fs::read_to_string(gh_hosts_path()?).ok()?;
+    parse_gh_hosts(&contents, host)
+}
+
+/// The `oauth_token` directly under `host` in a `hosts.yml`. Newer `gh`
+/// versions keep the token in the system keyring instead, leaving none.
+fn parse_gh_hosts(contents: &str, host: &str) -> Option<String> {
+    let unquote = |s: &str| s.trim().trim_matches(['"', '\'']).to_string();
+    let mut in_host = false;
+    let mut child_indent = None;
+    for line in contents.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        if indent == 0 {
+            in_host = unquote(trimmed.trim_end_matches(':')).eq_ignore_ascii_case(host);
+            child_indent = None;
+            continue;
+        }
+        if !in_host || *child_indent.get_or_insert(indent) != indent {
+            continue;
+        }
+        if let Some(token) = trimmed.strip_prefix("oauth_token:").map(unquote) {
+            if !token.is_empty() {
+                return Some(token);
+            }
+        }
+    }
+    None
+}
+
+/// Ask the configured git credential helpers for `host`'s HTTPS password,
+/// never prompting: a missing credential just yields nothing.
+fn git_credential_token(host: &str) -> Option<String> {
+    let mut child = Command::new("git")
+        .args(["-c", "credential.interactive=false", "credential", "fill"])
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .env("GCM_INTERACTIVE", "never")
+        .env("GIT_ASKPASS", "true")
+        .env("SSH_ASKPASS", "true")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    child
+        .stdin
+        .take()?
+        .write_all(format!("protocol=https\nhost={host}\n\n").as_bytes())
+        .ok()?;
+    let output = child.wait_with_output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_credential(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn parse_credential(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find_map(|line| line.strip_prefix("password="))
+        .filter(|p| !p.is_empty())
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gh_hosts() {
+        let hosts = r#"
+github.com:
+    users:
+        octo:
+            oauth_token: gho_nested
+    oauth_token: gho_dotcom
+    user: octo
+"github.mycorp.com":
+    oauth_token: "gho_corp"
+keyring.example.com:
+    user: octo
+"#;
+        assert_eq!(
+            parse_gh_hosts(hosts, "github.com").as_deref(),
+            Some("gho_dotcom")
+        );
+        assert_eq!(
+            parse_gh_hosts(hosts, "GitHub.MyCorp.com").as_deref(),
+            Some("gho_corp")
+        );
+        assert_eq!(parse_gh_hosts(hosts, "keyring.example.com"), None);
+        assert_eq!(parse_gh_hosts(hosts, "other.example.com"), None);
+    }
+
+    #[test]
+    fn test_parse_credential() {
+        let output = "protocol=https\nhost=github.com\nusername=octo\npassword=ghp_secret\n";
+        assert_eq!(parse_credential(output).as_deref(), Some("ghp_secret"));
+        assert_eq!(parse_credential("protocol=https\nhost=github.com\n"), None);
+    }
+}
@@ -0,0 +1,367 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+/// User configuration loaded from `config.toml`.
+/// Missing files or fields fall back to defaults.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Branch names that the cleanup flow never offers for deletion.
+    pub protected_branches: Vec<String>,
+    pub open: OpenConfig,
+    pub github: GitHubConfig,
+    /// Saved issue and PR filters, keyed by `owner/name`.
+    pub filters: HashMap<String, SavedFilters>,
+    /// The file this was loaded from, or would be saved to.
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+}
+
+/// Filters for one repo's Issues and PRs tabs, in GitHub search syntax
+/// (e.g. `is:open label:bug author:@me`).
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SavedFilters {
+    pub issues: Option<String>,
+    pub prs: Option<String>,
+}
+
+const DEFAULT_PREFETCH_CONCURRENCY: usize = 4;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct GitHubConfig {
+    /// Per-host API settings, keyed by the host name in git remotes
+    /// (e.g. `github.mycorp.com`). Listing a host makes its remotes count
+    /// as GitHub repos; an entry for `github.com` overrides its defaults.
+    pub hosts: HashMap<String, GitHubHostConfig>,
+    /// Never call the API; show only data cached on disk.
+    pub offline: bool,
+    /// Repos whose data is prefetched at once in the background; 0 turns
+    /// prefetching off.
+    pub prefetch_concurrency: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct GitHubHostConfig {
+    /// REST API base URL. Defaults to `https://api.github.com` for
+    /// github.com and `https://<host>/api/v3` for Enterprise hosts.
+    pub api_url: Option<String>,
+    pub token: Option<String>,
+    /// Tokens for particular owners (users or orgs) on this host, e.g. a
+    /// work account's token for the company org next to a personal one.
+    pub owner_tokens: HashMap<String, String>,
+}
+
+impl GitHubConfig {
+    /// Enterprise hosts whose remotes should be treated as GitHub repos.
+    pub fn extra_hosts(&self) -> Vec<String> {
+        self.hosts
+            .keys()
+            .filter(|h| !h.eq_ignore_ascii_case(crate::repo::GITHUB_HOST))
+            .cloned()
+            .collect()
+    }
+
+    pub fn prefetch_concurrency(&self) -> usize {
+        self.prefetch_concurrency
+            .unwrap_or(DEFAULT_PREFETCH_CONCURRENCY)
+    }
+
+    pub fn host(&self, host: &str) -> Option<&GitHubHostConfig> {
+        self.hosts
+            .iter()
+            .find(|(h, _)| h.eq_ignore_ascii_case(host))
+            .map(|(_, c)| c)
+    }
+}
+
+/// Programs used to open a repository outside the dashboard.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct OpenConfig {
+    /// Editor command; defaults to `$VISUAL`, then `$EDITOR`.
+    pub editor: Option<String>,
+    /// Whether the editor is a GUI app that should be launched without
+    /// suspending the dashboard. Guessed from the program name when unset.
+    pub gui_editor: Option<bool>,
+    /// Shell for subshells; defaults to `$SHELL`.
+    pub shell: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            protected_branches: vec![
+                "main".to_string(),
+                "master".to_string(),
+                "develop".to_string(),
+            ],
+            open: OpenConfig::default(),
+            github: GitHubConfig::default(),
+            filters: HashMap::new(),
+            path: None,
+        }
+    }
+}
+
+impl Config {
+    /// Load the config from an explicit path, or from the default location
+    /// when `path` is `None`. A missing default file is not an error.
+    pub fn load(path: Option<&Path>) -> color_eyre::Result<Self> {
+        let (path, required) = match path {
+            Some(p) => (p.to_path_buf(), true),
+            None => match default_config_path() {
+                Some(p) => (p, false),
+                None => return Ok(Self::default()),
+            },
+        };
+
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(c) => c,
+            Err(e) if !required && e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Self {
+                    path: Some(path),
+                    ..Self::default()
+                });
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut config = Self::parse(&contents)?;
+        config.path = Some(path);
+        Ok(config)
+    }
+
+    /// The saved filter for a repo's issues (`is_pr` false) or PRs.
+    pub fn saved_filter(&self, full_name: &str, is_pr: bool) -> Option<&str> {
+        let saved = self.filters.get(full_name)?;
+        if is_pr {
+            saved.prs.as_deref()
+        } else {
+            saved.issues.as_deref()
+        }
+    }
+
+    /// Save a repo's filter to the config file, editing it in place so the
+    /// rest of the hand-written file stays as it is.
+    pub fn save_filter(
+        &mut self,
+        full_name: &str,
+        is_pr: bool,
+        query: &str,
+    ) -> color_eyre::Result<()> {
+        let path = self
+            .path
+            .clone()
+            .ok_or_else(|| color_eyre::eyre::eyre!("no config file location"))?;
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(c) => c,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+        let key = if is_pr { "prs" } else { "issues" };
+        let updated = set_filter(&contents, full_name, key, query)?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, updated)?;
+
+        let saved = self.filters.entry(full_name.to_string()).or_default();
+        let slot = if is_pr {
+            &mut saved.prs
+        } else {
+            &mut saved.issues
+        };
+        *slot = Some(query.to_string());
+        Ok(())
+    }
+
+    pub fn parse(contents: &str) -> color_eyre::Result<Self> {
+        Ok(toml::from_str(contents)?)
+    }
+
+    pub fn is_protected(&self, branch: &str) -> bool {
+        self.protected_branches.iter().any(|b| b == branch)
+    }
+}
+
+/// Set `filters.<full_name>.<key> = query` in a TOML document, wherever
+/// and however the user wrote those tables (headers, dotted keys or inline
+/// tables), keeping comments and layout.
+fn set_filter(
+    contents: &str,
+    full_name: &str,
+    key: &str,
+    query: &str,
+) -> color_eyre::Result<String> {
+    let mut doc: toml_edit::DocumentMut = contents.parse()?;
+    let filters = doc.entry("filters").or_insert_with(|| {
+        let mut table = toml_edit::Table::new();
+        // Only the `[filters."owner/name"]` headers show up
+        table.set_implicit(true);
+        toml_edit::Item::Table(table)
+    });
+    let inline = filters.is_inline_table();
+    let filters = filters
+        .as_table_like_mut()
+        .ok_or_else(|| color_eyre::eyre::eyre!("`filters` in the config is not a table"))?;
+    let repo = filters.entry(full_name).or_insert_with(|| {
+        if inline {
+            toml_edit::Item::Value(toml_edit::InlineTable::new().into())
+        } else {
+            toml_edit::table()
+        }
+    });
+    let repo = repo
+        .as_table_like_mut()
+        .ok_or_else(|| color_eyre::eyre::eyre!("`filters.\"{full_name}\"` is not a table"))?;
+    repo.insert(key, toml_edit::value(query));
+    Ok(doc.to_string())
+}
+
+/// `$XDG_CACHE_HOME/project-dash/github.json`, falling back to
+/// `~/.cache/project-dash/github.json`.
+pub fn default_cache_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CACHE_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".cache")))?;
+    Some(base.join("project-dash").join("github.json"))
+}
+
+/// `$XDG_CONFIG_HOME/project-dash/config.toml`, falling back to
+/// `~/.config/project-dash/config.toml`.
+pub fn default_config_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))?;
+    Some(base.join("project-dash").join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_protected_branches() {
+        let config = Config::parse("protected_branches = [\"main\", \"release\"]").unwrap();
+        assert!(config.is_protected("release"));
+        assert!(!config.is_protected("develop"));
+    }
+
+    #[test]
+    fn test_parse_empty_uses_defaults() {
+        let config = Config::parse("").unwrap();
+        assert!(config.is_protected("main"));
+        assert!(config.is_protected("master"));
+        assert!(config.open.editor.is_none());
+    }
+
+    #[test]
+    fn test_parse_open_section() {
+        let config = Config::parse("[open]\neditor = \"code -w\"\ngui_editor = true\n").unwrap();
+        assert_eq!(config.open.editor.as_deref(), Some("code -w"));
+        assert_eq!(config.open.gui_editor, Some(true));
+    }
+
+    #[test]
+    fn test_parse_github_hosts() {
+        let config = Config::parse(
+            r#"
+[github.hosts."github.mycorp.com"]
+api_url = "https://github.mycorp.com/api/v3"
+token = "t"
+
+[github.hosts."github.com"]
+api_url = "http://localhost:9999"
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.github.extra_hosts(),
+            vec!["github.mycorp.com".to_string()]
+        );
+        let host = config.github.host("GitHub.MyCorp.com").unwrap();
+        assert_eq!(host.token.as_deref(), Some("t"));
+        assert_eq!(
+            config.github.host("github.com").unwrap().api_url.as_deref(),
+            Some("http://localhost:9999")
+        );
+    }
+
+    #[test]
+    fn test_save_filter_keeps_the_rest() {
+        let file = std::env::temp_dir().join(format!(
+            "project-dash-test-filters-{}.toml",
+            std::process::id()
+        ));
+        let save = |contents: &str, full_name: &str, is_pr: bool, query: &str| {
+            std::fs::write(&file, contents).unwrap();
+            let mut config = Config::load(Some(&file)).unwrap();
+            config.save_filter(full_name, is_pr, query).unwrap();
+            let saved = std::fs::read_to_string(&file).unwrap();
+            (Config::load(Some(&file)).unwrap(), saved)
+        };
+
+        let (config, saved) = save(
+            "# my settings\nprotected_branches = [\"main\"]\n",
+            "octo/app",
+            false,
+            "label:bug",
+        );
+        assert_eq!(
+            saved,
+            "# my settings\nprotected_branches = [\"main\"]\n\n[filters.\"octo/app\"]\nissues = \"label:bug\"\n"
+        );
+        assert_eq!(config.saved_filter("octo/app", false), Some("label:bug"));
+
+        // A header the user wrote with other quotes is reused, not repeated
+        let (config, saved) = save(
+            "[filters.'octo/app'] # mine\nprs = \"author:@me\"\n\n[open]\neditor = \"vim\"\n",
+            "octo/app",
+            false,
+            "is:closed",
+        );
+        assert!(saved.contains("[filters.'octo/app'] # mine\n"));
+        assert_eq!(saved.matches("octo/app").count(), 1);
+        assert_eq!(config.saved_filter("octo/app", false), Some("is:closed"));
+        assert_eq!(config.saved_filter("octo/app", true), Some("author:@me"));
+        assert_eq!(config.open.editor.as_deref(), Some("vim"));
+
+        let (config, saved) = save(
+            "filters = { \"octo/app\" = { prs = \"draft:false\" } }\n",
+            "octo/lib",
+            true,
+            "review:required",
+        );
+        assert!(!saved.contains('['));
+        assert_eq!(config.saved_filter("octo/app", true), Some("draft:false"));
+        assert_eq!(
+            config.saved_filter("octo/lib", true),
+            Some("review:required")
+        );
+
+        let (config, _) = save(
+            "[filters]\n\"octo/app\".issues = \"is:open\"\n",
+            "octo/app",
+            true,
+            "is:merged",
+        );
+        assert_eq!(config.saved_filter("octo/app", false), Some("is:open"));
+        assert_eq!(config.saved_filter("octo/app", true), Some("is:merged"));
+        let _ = std::fs::remove_file(&file);
+    }
+
+    #[test]
+    fn test_parse_prefetch_concurrency() {
+        assert_eq!(Config::parse("").unwrap().github.prefetch_concurrency(), 4);
+        let config = Config::parse("[github]\nprefetch_concurrency = 0\noffline = true").unwrap();
+        assert_eq!(config.github.prefetch_concurrency(), 0);
+        assert!(config.github.offline);
+    }
+}
@@ -0,0 +1,968 @@
+use std::path::{Path, PathBuf};
+
+use git2::{
+    BranchType, Cred, CredentialType, FetchOptions, PushOptions, RemoteCallbacks, Repository,
+    WorktreeAddOptions,
+};
+
+use crate::config::Config;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StaleReason {
+    /// Fully merged into the default branch.
+    Merged,
+    /// Tracked an upstream branch that no longer exists.
+    UpstreamGone,
+}
+
+impl StaleReason {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Merged => "merged",
+            Self::UpstreamGone => "gone",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StaleBranch {
+    pub repo_path: PathBuf,
+    pub repo_name: String,
+    pub branch: String,
+    pub reason: StaleReason,
+    /// Fully merged into the default branch, so deleting it loses nothing.
+    /// Always true for `Merged`; a gone branch may still hold local work.
+    pub merged: bool,
+}
+
+/// Resolve the default branch name: `origin/HEAD` if set, otherwise the first
+/// of `main`/`master` that exists locally.
+pub fn default_branch(repo: &Repository) -> Option<String> {
+    if let Ok(reference) = repo.find_reference("refs/remotes/origin/HEAD") {
+        if let Some(target) = reference.symbolic_target() {
+            if let Some(name) = target.strip_prefix("refs/remotes/origin/") {
+                return Some(name.to_string());
+            }
+        }
+    }
+
+    ["main", "master"]
+        .into_iter()
+        .find(|name| repo.find_branch(name, BranchType::Local).is_ok())
+        .map(String::from)
+}
+
+/// Find local branches that are merged into the default branch or whose
+/// upstream was deleted. The current, default and protected branches are
+/// never returned.
+pub fn find_stale_branches(path: &Path, config: &Config) -> Vec<StaleBranch> {
+    let repo = match Repository::open(path) {
+        Ok(r) => r,
+        Err(_) => return Vec::new(),
+    };
+    let repo_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("unknown")
+        .to_string();
+
+    let default = match default_branch(&repo) {
+        Some(d) => d,
+        None => return Vec::new(),
+    };
+    let default_tip = default_tip(&repo, &default);
+
+    let current = repo
+        .head()
+        .ok()
+        .filter(|h| h.is_branch())
+        .and_then(|h| h.shorthand().map(String::from));
+
+    let branches = match repo.branches(Some(BranchType::Local)) {
+        Ok(b) => b,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut stale = Vec::new();
+    for (branch, _) in branches.flatten() {
+        let name = match branch.name().ok().flatten() {
+            Some(n) => n.to_string(),
+            None => continue,
+        };
+        if name == default || current.as_deref() == Some(&name) || config.is_protected(&name) {
+            continue;
+        }
+
+        let merged = match (default_tip, branch.get().target()) {
+            (Some(base), Some(tip)) => is_merged(&repo, tip, base),
+            _ => false,
+        };
+        let reason = if upstream_gone(&repo, &name) {
+            Some(StaleReason::UpstreamGone)
+        } else {
+            merged.then_some(StaleReason::Merged)
+        };
+
+        if let Some(reason) = reason {
+            stale.push(StaleBranch {
+                repo_path: path.to_path_buf(),
+                repo_name: repo_name.clone(),
+                branch: name,
+                reason,
+                merged,
+            });
+        }
+    }
+
+    stale.sort_by(|a, b| a.branch.cmp(&b.branch));
+    stale
+}
+
+/// The default branch's tip, preferring the remote one since the local
+/// branch is often behind.
+fn default_tip(repo: &Repository, default: &str) -> Option<git2::Oid> {
+    repo.find_branch(&format!("origin/{default}"), BranchType::Remote)
+        .or_else(|_| repo.find_branch(default, BranchType::Local))
+        .ok()
+        .and_then(|b| b.get().target())
+}
+
+/// Whether every commit of `tip` is reachable from `base`.
+fn is_merged(repo: &Repository, tip: git2::Oid, base: git2::Oid) -> bool {
+    tip == base || repo.graph_descendant_of(base, tip).unwrap_or(false)
+}
+
+/// A branch whose config names an upstream that can no longer be resolved.
+fn upstream_gone(repo: &Repository, branch: &str) -> bool {
+    let has_upstream_config = repo
+        .config()
+        .ok()
+        .and_then(|c| c.get_string(&format!("branch.{branch}.merge")).ok())
+        .is_some();
+    if !has_upstream_config {
+        return false;
+    }
+    repo.find_branch(branch, BranchType::Local)
+        .and_then(|b| b.upstream())
+        .is_err()
+}
+
+/// Delete a local branch, refusing if it is checked out, or unless `force`
+/// if it holds commits the default branch doesn't.
+pub fn delete_branch(path: &Path, branch: &str, force: bool) -> Result<(), String> {
+    let repo = open(path)?;
+    let mut b = repo
+        .find_branch(branch, BranchType::Local)
+        .map_err(|e| e.message().to_string())?;
+    if b.is_head() {
+        return Err("branch is checked out".to_string());
+    }
+    if !force {
+        let default = default_branch(&repo).ok_or("no default branch to compare with")?;
+        let merged = match (default_tip(&repo, &default), b.get().target()) {
+            (Some(base), Some(tip)) => is_merged(&repo, tip, base),
+            _ => false,
+        };
+        if !merged {
+            return Err(format!("not merged into {default}"));
+        }
+    }
+    b.delete().map_err(|e| e.message().to_string())
+}
+
+fn open(path: &Path) -> Result<Repository, String> {
+    Repository::open(path).map_err(|e| e.message().to_string())
+}
+
+/// Credential callbacks trying, in order, the ssh agent, git's configured
+/// credential helpers and the platform default. Each is tried once so a
+/// rejected credential fails instead of looping.
+fn remote_callbacks<'a>() -> RemoteCallbacks<'a> {
+    let mut tried_agent = false;
+    let mut tried_helper = false;
+    let mut tried_default = false;
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(move |url, username, allowed| {
+        if allowed.contains(CredentialType::SSH_KEY) && !tried_agent {
+            tried_agent = true;
+            return Cred::ssh_key_from_agent(username.unwrap_or("git"));
+        }
+        if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) && !tried_helper {
+            tried_helper = true;
+            if let Ok(config) = git2::Config::open_default() {
+                return Cred::credential_helper(&config, url, username);
+            }
+        }
+        if allowed.contains(CredentialType::DEFAULT) && !tried_default {
+            tried_default = true;
+            return Cred::default();
+        }
+        Err(git2::Error::from_str("no usable credentials"))
+    });
+    callbacks
+}
+
+/// The checked-out branch name, or an error for a detached HEAD.
+fn head_branch(repo: &Repository) -> Result<String, String> {
+    let head = repo.head().map_err(|e| e.message().to_string())?;
+    if !head.is_branch() {
+        return Err("HEAD is detached".to_string());
+    }
+    head.shorthand()
+        .map(String::from)
+        .ok_or_else(|| "invalid branch name".to_string())
+}
+
+/// The remote the current branch tracks, falling back to `origin`.
+fn tracking_remote(repo: &Repository, branch: &str) -> String {
+    repo.config()
+        .ok()
+        .and_then(|c| c.get_string(&format!("branch.{branch}.remote")).ok())
+        .unwrap_or_else(|| "origin".to_string())
+}
+
+fn fetch_remote(repo: &Repository, remote_name: &str) -> Result<usize, String> {
+    let mut remote = repo
+        .find_remote(remote_name)
+        .map_err(|e| e.message().to_string())?;
+    let mut options = FetchOptions::new();
+    options.remote_callbacks(remote_callbacks());
+    options.prune(git2::FetchPrune::On);
+    remote
+        .fetch::<&str>(&[], Some(&mut options), None)
+        .map_err(|e| e.message().to_string())?;
+    Ok(remote.stats().received_objects())
+}
+
+/// Fetch the current branch's remote (or `origin`), pruning deleted refs.
+pub fn fetch(path: &Path) -> Result<String, String> {
+    let repo = open(path)?;
+    let remote = match head_branch(&repo) {
+        Ok(branch) => tracking_remote(&repo, &branch),
+        Err(_) => "origin".to_string(),
+    };
+    let received = fetch_remote(&repo, &remote)?;
+    Ok(format!("fetched {remote} ({received} objects)"))
+}
+
+/// Fetch and fast-forward the current branch to its upstream. Diverged
+/// branches are left alone.
+pub fn pull(path: &Path) -> Result<String, String> {
+    let repo = open(path)?;
+    let branch = head_branch(&repo)?;
+    let remote = tracking_remote(&repo, &branch);
+    fetch_remote(&repo, &remote)?;
+
+    let upstream = repo
+        .find_branch(&branch, BranchType::Local)
+        .and_then(|b| b.upstream())
+        .map_err(|_| format!("{branch} has no upstream"))?;
+    let upstream_oid = upstream
+        .get()
+        .target()
+        .ok_or_else(|| "upstream has no target".to_string())?;
+    let annotated = repo
+        .find_annotated_commit(upstream_oid)
+        .map_err(|e| e.message().to_string())?;
+    let (analysis, _) = repo
+        .merge_analysis(&[&annotated])
+        .map_err(|e| e.message().to_string())?;
+
+    if analysis.is_up_to_date() {
+        return Ok("already up to date".to_string());
+    }
+    if !analysis.is_fast_forward() {
+        return Err("branches have diverged; merge or rebase manually".to_string());
+    }
+
+    let target = repo
+        .find_object(upstream_oid, None)
+        .map_err(|e| e.message().to_string())?;
+    repo.checkout_tree(&target, Some(git2::build::CheckoutBuilder::new().safe()))
+        .map_err(|e| e.message().to_string())?;
+    repo.find_reference(&format!("refs/heads/{branch}"))
+        .and_then(|mut r| r.set_target(upstream_oid, "project-dash: fast-forward"))
+        .map_err(|e| e.message().to_string())?;
+
+    Ok(format!(
+        "fast-forwarded to {}",
+        &upstream_oid.to_string()[..7]
+    ))
+}
+
+/// Push the current branch to the branch it tracks (or the same name on
+/// `origin` when no upstream is configured).
+pub fn push(path: &Path) -> Result<String, String> {
+    let repo = open(path)?;
+    let branch = head_branch(&repo)?;
+    let remote_name = tracking_remote(&repo, &branch);
+    let dest = repo
+        .config()
+        .ok()
+        .and_then(|c| c.get_string(&format!("branch.{branch}.merge")).ok())
+        .unwrap_or_else(|| format!("refs/heads/{branch}"));
+
+    let mut remote = repo
+        .find_remote(&remote_name)
+        .map_err(|e| e.message().to_string())?;
+
+    let rejection = std::cell::RefCell::new(None);
+    let mut callbacks = remote_callbacks();
+    callbacks.push_update_reference(|_, status| {
+        if let Some(msg) = status {
+            *rejection.borrow_mut() = Some(msg.to_string());
+        }
+        Ok(())
+    });
+    let mut options = PushOptions::new();
+    options.remote_callbacks(callbacks);
+
+    remote
+        .push(&[format!("refs/heads/{branch}:{dest}")], Some(&mut options))
+        .map_err(|e| e.message().to_string())?;
+    drop(options);
+
+    match rejection.into_inner() {
+        Some(msg) => Err(format!("push rejected: {msg}")),
+        None => Ok(format!("pushed {branch} to {remote_name}")),
+    }
+}
+
+/// Switch to `branch`, creating a tracking branch from `origin/<branch>`
+/// when it only exists on the remote.
+pub fn checkout_branch(path: &Path, branch: &str) -> Result<String, String> {
+    let repo = open(path)?;
+
+    let (commit, created) = match repo.find_branch(branch, BranchType::Local) {
+        Ok(b) => (
+            b.get()
+                .peel_to_commit()
+                .map_err(|e| e.message().to_string())?,
+            false,
+        ),
+        Err(_) => {
+            let remote_branch = repo
+                .find_branch(&format!("origin/{branch}"), BranchType::Remote)
+                .map_err(|_| format!("no branch named {branch}"))?;
+            let commit = remote_branch
+                .get()
+                .peel_to_commit()
+                .map_err(|e| e.message().to_string())?;
+            let mut local = repo
+                .branch(branch, &commit, false)
+                .map_err(|e| e.message().to_string())?;
+            local
+                .set_upstream(Some(&format!("origin/{branch}")))
+                .map_err(|e| e.message().to_string())?;
+            (commit, true)
+        }
+    };
+
+    repo.checkout_tree(
+        commit.as_object(),
+        Some(git2::build::CheckoutBuilder::new().safe()),
+    )
+    .map_err(|e| e.message().to_string())?;
+    repo.set_head(&format!("refs/heads/{branch}"))
+        .map_err(|e| e.message().to_string())?;
+
+    Ok(if created {
+        format!("created {branch} from origin/{branch}")
+    } else {
+        format!("switched to {branch}")
+    })
+}
+
+/// The local branch a pull request is checked out as: its head branch when
+/// that lives in the same repo, `pr/<number>` for forks (`head` is then
+/// `owner:branch`, whose branch name often clashes with local ones).
+pub fn pr_branch_name(number: u64, head: &str) -> String {
+    if head.contains(':') || head.is_empty() {
+        format!("pr/{number}")
+    } else {
+        head.to_string()
+    }
+}
+
+/// Where a new worktree for pull request `number` goes by default: next to
+/// the repository, as `<name>-pr-<number>`.
+pub fn pr_worktree_path(path: &Path, number: u64) -> PathBuf {
+    let name = path
+        .file_name()
+        .map_or_else(|| "repo".into(), |n| n.to_string_lossy());
+    path.with_file_name(format!("{name}-pr-{number}"))
+}
+
+/// Fetch pull request `number` from `origin` into the local `branch` and
+/// switch to it, or check it out in a new worktree at `worktree`. An
+/// existing `branch` is only fast-forwarded. The branch tracks `head_branch`
+/// when the PR comes from the same repo, else `refs/pull/<number>/head`.
+pub fn checkout_pr(
+    path: &Path,
+    number: u64,
+    head_branch: Option<&str>,
+    branch: &str,
+    worktree: Option<&Path>,
+) -> Result<String, String> {
+    if !git2::Branch::name_is_valid(branch).unwrap_or(false) {
+        return Err(format!("invalid branch name: {branch}"));
+    }
+    if let Some(dir) = worktree.filter(|d| d.exists()) {
+        return Err(format!("{} already exists", dir.display()));
+    }
+    let repo = open(path)?;
+    let pull_ref = format!("refs/pull/{number}/head");
+
+    let mut refspecs = vec![pull_ref.clone()];
+    if let Some(head) = head_branch {
+        refspecs.push(format!("+refs/heads/{head}:refs/remotes/origin/{head}"));
+    }
+    let mut remote = repo
+        .find_remote("origin")
+        .map_err(|e| e.message().to_string())?;
+    let mut options = FetchOptions::new();
+    options.remote_callbacks(remote_callbacks());
+    remote
+        .fetch(&refspecs, Some(&mut options), None)
+        .map_err(|e| e.message().to_string())?;
+
+    let mut fetched = None;
+    let _ = repo.fetchhead_foreach(|name, _, oid, _| {
+        if name == pull_ref {
+            fetched = Some(*oid);
+        }
+        true
+    });
+    let oid = fetched.ok_or_else(|| format!("origin has no {pull_ref}"))?;
+    let commit = repo.find_commit(oid).map_err(|e| e.message().to_string())?;
+
+    let existing = repo
+        .find_branch(branch, BranchType::Local)
+        .ok()
+        .and_then(|b| b.get().target());
+    if let Some(current) = existing.filter(|&c| c != oid) {
+        if !repo.graph_descendant_of(oid, current).unwrap_or(false) {
+            return Err(format!(
+                "{branch} already exists and has diverged from PR #{number}"
+            ));
+        }
+    }
+    let is_head = repo
+        .find_branch(branch, BranchType::Local)
+        .is_ok_and(|b| b.is_head());
+    if worktree.is_none() {
+        // Update the working tree first, so local changes in the way leave
+        // the branch untouched
+        repo.checkout_tree(
+            commit.as_object(),
+            Some(git2::build::CheckoutBuilder::new().safe()),
+        )
+        .map_err(|e| e.message().to_string())?;
+    } else if is_head && existing != Some(oid) {
+        return Err(format!(
+            "{branch} is checked out here; switch away before updating it in a worktree"
+        ));
+    }
+    repo.reference(
+        &format!("refs/heads/{branch}"),
+        oid,
+        true,
+        &format!("project-dash: PR #{number}"),
+    )
+    .map_err(|e| e.message().to_string())?;
+
+    let mut config = repo.config().map_err(|e| e.message().to_string())?;
+    let merge = head_branch.map_or(pull_ref, |head| format!("refs/heads/{head}"));
+    config
+        .set_str(&format!("branch.{branch}.remote"), "origin")
+        .and_then(|_| config.set_str(&format!("branch.{branch}.merge"), &merge))
+        .map_err(|e| e.message().to_string())?;
+
+    let Some(dir) = worktree else {
+        repo.set_head(&format!("refs/heads/{branch}"))
+            .map_err(|e| e.message().to_string())?;
+        return Ok(format!("checked out PR #{number} as {branch}"));
+    };
+    let name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .ok_or_else(|| format!("invalid worktree path: {}", dir.display()))?;
+    let reference = repo
+        .find_reference(&format!("refs/heads/{branch}"))
+        .map_err(|e| e.message().to_string())?;
+    let mut add = WorktreeAddOptions::new();
+    add.reference(Some(&reference));
+    repo.worktree(&name, dir, Some(&add))
+        .map_err(|e| e.message().to_string())?;
+    Ok(format!(
+        "checked out PR #{number} as {branch} in {}",
+        dir.display()
+    ))
+}
+
+/// Turn user input into a clone URL and a default directory name. Accepts
+/// full URLs, scp-style `git@host:path` remotes and GitHub `owner/name`.
+pub fn parse_clone_source(input: &str) -> Option<(String, String)> {
+    let input = input.trim();
+    if input.is_empty() || input.contains(char::is_whitespace) {
+        return None;
+    }
+
+    if input.contains("://") || input.starts_with("git@") {
+        let trimmed = input.trim_end_matches('/');
+        let trimmed = trimmed.strip_suffix(".git").unwrap_or(trimmed);
+        let name = trimmed
+            .rsplit(['/', ':'])
+            .next()
+            .filter(|n| !n.is_empty())?;
+        return Some((input.to_string(), name.to_string()));
+    }
+
+    let (owner, name) = input.split_once('/')?;
+    if owner.is_empty() || name.is_empty() || name.contains('/') {
+        return None;
+    }
+    let name = name.strip_suffix(".git").unwrap_or(name);
+    Some((
+        format!("https://github.com/{owner}/{name}.git"),
+        name.to_string(),
+    ))
+}
+
+/// Resolve a clone destination relative to the scan root, refusing paths
+/// that escape it or already exist.
+pub fn resolve_clone_dest(scan_path: &Path, relative: &str) -> Result<PathBuf, String> {
+    let relative = Path::new(relative.trim());
+    if relative.as_os_str().is_empty() {
+        return Err("destination is empty".to_string());
+    }
+    if !relative
+        .components()
+        .all(|c| matches!(c, std::path::Component::Normal(_)))
+    {
+        return Err("destination must be a relative path inside the scan root".to_string());
+    }
+    let dest = scan_path.join(relative);
+    if dest.exists() {
+        return Err(format!("{} already exists", dest.display()));
+    }
+    Ok(dest)
+}
+
+/// Clone `url` into `dest`, reporting transfer progress as it changes.
+pub fn clone_repo(url: &str, dest: &Path, mut progress: impl FnMut(String)) -> Result<(), String> {
+    let mut last_percent = None;
+    let mut callbacks = remote_callbacks();
+    callbacks.transfer_progress(move |stats| {
+        let total = stats.total_objects().max(1);
+        let percent = stats.received_objects() * 100 / total;
+        if last_percent != Some(percent) {
+            last_percent = Some(percent);
+            progress(format!(
+                "{percent}% ({}/{} objects)",
+                stats.received_objects(),
+                stats.total_objects()
+            ));
+        }
+        true
+    });
+    let mut options = FetchOptions::new();
+    options.remote_callbacks(callbacks);
+
+    git2::build::RepoBuilder::new()
+        .fetch_options(options)
+        .clone(url, dest)
+        .map(|_| ())
+        .map_err(|e| e.message().to_string())
+}
+
+/// URL of the remote `branch` pushes to: its `pushRemote`, the repo's
+/// `remote.pushDefault`, its upstream's remote, then `origin`. For a fork
+/// workflow this is the fork, where a PR's head branch lives.
+pub fn branch_push_url(path: &Path, branch: &str) -> Option<String> {
+    let repo = Repository::open(path).ok()?;
+    let config = repo.config().ok()?;
+    let remote = config
+        .get_string(&format!("branch.{branch}.pushRemote"))
+        .or_else(|_| config.get_string("remote.pushDefault"))
+        .ok()
+        .or_else(|| {
+            let upstream = repo
+                .branch_upstream_remote(&format!("refs/heads/{branch}"))
+                .ok()?;
+            upstream.as_str().map(String::from)
+        })
+        .unwrap_or_else(|| "origin".to_string());
+    let url = repo.find_remote(&remote).ok()?.url().map(String::from);
+    url
+}
+
+/// First line of the working-tree version of `file` that differs from the
+/// index or, for staged-only changes, from HEAD.
+pub fn first_changed_line(path: &Path, file: &str) -> Option<u32> {
+    let repo = Repository::open(path).ok()?;
+    let mut opts = git2::DiffOptions::new();
+    opts.pathspec(file).context_lines(0);
+
+    let first_hunk = |diff: &git2::Diff| {
+        let mut line = None;
+        let _ = diff.foreach(
+            &mut |_, _| true,
+            None,
+            Some(&mut |_, hunk| {
+                line.get_or_insert(hunk.new_start().max(1));
+                false
+            }),
+            None,
+        );
+        line
+    };
+
+    let unstaged = repo.diff_index_to_workdir(None, Some(&mut opts)).ok()?;
+    if let Some(line) = first_hunk(&unstaged) {
+        return Some(line);
+    }
+    let head_tree = repo.head().ok().and_then(|h| h.peel_to_tree().ok());
+    let staged = repo
+        .diff_tree_to_index(head_tree.as_ref(), None, Some(&mut opts))
+        .ok()?;
+    first_hunk(&staged)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Create an empty commit on HEAD, returning its id.
+    pub(crate) fn commit(repo: &Repository, message: &str) -> git2::Oid {
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let parents: Vec<git2::Commit> = repo
+            .head()
+            .ok()
+            .and_then(|h| h.target())
+            .and_then(|oid| repo.find_commit(oid).ok())
+            .into_iter()
+            .collect();
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parent_refs)
+            .unwrap()
+    }
+
+    pub(crate) fn temp_repo(name: &str) -> (PathBuf, Repository) {
+        let dir = std::env::temp_dir().join(format!("project-dash-test-{name}"));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut opts = git2::RepositoryInitOptions::new();
+        opts.initial_head("main");
+        let repo = Repository::init_opts(&dir, &opts).unwrap();
+        (dir, repo)
+    }
+
+    #[test]
+    fn test_find_stale_branches() {
+        let (dir, repo) = temp_repo("stale-branches");
+        let base = commit(&repo, "initial");
+        let base_commit = repo.find_commit(base).unwrap();
+
+        // merged: points at main's tip
+        repo.branch("feature-merged", &base_commit, false).unwrap();
+        // protected: also merged, but listed in config
+        repo.branch("develop", &base_commit, false).unwrap();
+        // gone: upstream configured but the remote branch doesn't exist
+        repo.branch("feature-gone", &base_commit, false).unwrap();
+        let mut cfg = repo.config().unwrap();
+        cfg.set_str("branch.feature-gone.remote", "origin").unwrap();
+        cfg.set_str("branch.feature-gone.merge", "refs/heads/feature-gone")
+            .unwrap();
+
+        // unmerged: has a commit main doesn't
+        repo.set_head("refs/heads/main").unwrap();
+        commit(&repo, "on main");
+        repo.branch("feature-wip", &base_commit, false).unwrap();
+        repo.set_head("refs/heads/feature-wip").unwrap();
+        commit(&repo, "wip");
+        // gone but unmerged: the upstream was deleted with local work left
+        repo.branch("feature-gone-wip", &base_commit, false)
+            .unwrap();
+        cfg.set_str("branch.feature-gone-wip.remote", "origin")
+            .unwrap();
+        cfg.set_str(
+            "branch.feature-gone-wip.merge",
+            "refs/heads/feature-gone-wip",
+        )
+        .unwrap();
+        repo.set_head("refs/heads/feature-gone-wip").unwrap();
+        commit(&repo, "local only");
+        repo.set_head("refs/heads/main").unwrap();
+
+        let stale = find_stale_branches(&dir, &Config::default());
+        let found: Vec<(&str, StaleReason, bool)> = stale
+            .iter()
+            .map(|s| (s.branch.as_str(), s.reason, s.merged))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("feature-gone", StaleReason::UpstreamGone, true),
+                ("feature-gone-wip", StaleReason::UpstreamGone, false),
+                ("feature-merged", StaleReason::Merged, true),
+            ]
+        );
+
+        delete_branch(&dir, "feature-merged", false).unwrap();
+        assert!(repo
+            .find_branch("feature-merged", BranchType::Local)
+            .is_err());
+        assert!(delete_branch(&dir, "main", true).is_err());
+        assert_eq!(
+            delete_branch(&dir, "feature-gone-wip", false).unwrap_err(),
+            "not merged into main"
+        );
+        delete_branch(&dir, "feature-gone-wip", true).unwrap();
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_push_pull_and_checkout_through_bare_remote() {
+        let (work, work_repo) = temp_repo("sync-work");
+        commit(&work_repo, "initial");
+        let base = std::env::temp_dir().join("project-dash-test-sync");
+        let _ = std::fs::remove_dir_all(&base);
+        let bare = base.join("origin.git");
+        git2::build::RepoBuilder::new()
+            .bare(true)
+            .clone(work.to_str().unwrap(), &bare)
+            .unwrap();
+
+        let clone_a = base.join("a");
+        let clone_b = base.join("b");
+        let repo_a = Repository::clone(bare.to_str().unwrap(), &clone_a).unwrap();
+        Repository::clone(bare.to_str().unwrap(), &clone_b).unwrap();
+
+        let pushed = commit(&repo_a, "from a");
+        assert_eq!(push(&clone_a).unwrap(), "pushed main to origin");
+
+        assert!(fetch(&clone_b).unwrap().starts_with("fetched origin"));
+        assert!(pull(&clone_b).unwrap().starts_with("fast-forwarded"));
+        let repo_b = Repository::open(&clone_b).unwrap();
+        assert_eq!(repo_b.head().unwrap().target(), Some(pushed));
+        assert_eq!(pull(&clone_b).unwrap(), "already up to date");
+
+        // A branch that only exists on the remote gets a tracking branch
+        let head = repo_a.find_commit(pushed).unwrap();
+        repo_a.branch("topic", &head, false).unwrap();
+        repo_a.set_head("refs/heads/topic").unwrap();
+        push(&clone_a).unwrap();
+        fetch(&clone_b).unwrap();
+        assert_eq!(
+            checkout_branch(&clone_b, "topic").unwrap(),
+            "created topic from origin/topic"
+        );
+        assert_eq!(head_branch(&repo_b).unwrap(), "topic");
+        assert!(checkout_branch(&clone_b, "missing").is_err());
+
+        let _ = std::fs::remove_dir_all(&work);
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_checkout_pr_from_pull_refs() {
+        let (work, work_repo) = temp_repo("pr-work");
+        let base_oid = commit(&work_repo, "initial");
+        work_repo.set_head("refs/heads/feature").unwrap();
+        let feature = commit(&work_repo, "same-repo feature");
+        work_repo.set_head("refs/heads/fork").unwrap();
+        work_repo
+            .reset(
+                work_repo.find_commit(base_oid).unwrap().as_object(),
+                git2::ResetType::Soft,
+                None,
+            )
+            .unwrap();
+        let fork_v1 = commit(&work_repo, "fork change");
+        let fork_v2 = commit(&work_repo, "fork follow-up");
+        work_repo.set_head("refs/heads/main").unwrap();
+
+        let base = std::env::temp_dir().join("project-dash-test-pr");
+        let _ = std::fs::remove_dir_all(&base);
+        let bare_path = base.join("origin.git");
+        let bare = git2::build::RepoBuilder::new()
+            .bare(true)
+            .clone(work.to_str().unwrap(), &bare_path)
+            .unwrap();
+        // GitHub exposes every PR's head as refs/pull/N/head; a fork's
+        // branch only exists there
+        bare.reference("refs/heads/feature", feature, true, "branch")
+            .unwrap();
+        bare.reference("refs/pull/7/head", fork_v1, true, "pr")
+            .unwrap();
+        bare.reference("refs/pull/8/head", feature, true, "pr")
+            .unwrap();
+
+        let local = base.join("app");
+        let local_repo = Repository::clone(bare_path.to_str().unwrap(), &local).unwrap();
+
+        assert_eq!(pr_branch_name(7, "someone:main"), "pr/7");
+        assert_eq!(pr_branch_name(8, "feature"), "feature");
+        assert_eq!(
+            checkout_pr(&local, 7, None, "pr/7", None).unwrap(),
+            "checked out PR #7 as pr/7"
+        );
+        assert_eq!(head_branch(&local_repo).unwrap(), "pr/7");
+        assert_eq!(local_repo.head().unwrap().target(), Some(fork_v1));
+        let config = local_repo.config().unwrap();
+        assert_eq!(
+            config.get_string("branch.pr/7.merge").unwrap(),
+            "refs/pull/7/head"
+        );
+
+        // New pushes to the PR fast-forward the branch; a rewrite is refused
+        bare.reference("refs/pull/7/head", fork_v2, true, "pr")
+            .unwrap();
+        checkout_pr(&local, 7, None, "pr/7", None).unwrap();
+        assert_eq!(local_repo.head().unwrap().target(), Some(fork_v2));
+        bare.reference("refs/pull/7/head", feature, true, "pr")
+            .unwrap();
+        assert!(checkout_pr(&local, 7, None, "pr/7", None)
+            .unwrap_err()
+            .contains("diverged"));
+
+        // A same-repo PR in a worktree tracks its head branch
+        let worktree = pr_worktree_path(&local, 8);
+        assert_eq!(worktree, base.join("app-pr-8"));
+        checkout_pr(&local, 8, Some("feature"), "feature", Some(&worktree)).unwrap();
+        let wt_repo = Repository::open(&worktree).unwrap();
+        assert_eq!(head_branch(&wt_repo).unwrap(), "feature");
+        assert_eq!(wt_repo.head().unwrap().target(), Some(feature));
+        let upstream = local_repo
+            .find_branch("feature", BranchType::Local)
+            .unwrap()
+            .upstream()
+            .unwrap();
+        assert_eq!(upstream.name().unwrap(), Some("origin/feature"));
+        assert_eq!(head_branch(&local_repo).unwrap(), "pr/7");
+
+        assert!(checkout_pr(&local, 9, None, "pr/9", None)
+            .unwrap_err()
+            .contains("refs/pull/9/head"));
+
+        let _ = std::fs::remove_dir_all(&work);
+        let _ = std::fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_parse_clone_source() {
+        assert_eq!(
+            parse_clone_source("octo/widgets"),
+            Some((
+                "https://github.com/octo/widgets.git".to_string(),
+                "widgets".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_clone_source("git@github.com:octo/widgets.git"),
+            Some((
+                "git@github.com:octo/widgets.git".to_string(),
+                "widgets".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_clone_source("https://gitlab.com/group/sub/tool/"),
+            Some((
+                "https://gitlab.com/group/sub/tool/".to_string(),
+                "tool".to_string()
+            ))
+        );
+        assert_eq!(parse_clone_source("widgets"), None);
+        assert_eq!(parse_clone_source("a/b/c"), None);
+    }
+
+    #[test]
+    fn test_clone_repo_into_scan_root() {
+        let (origin, origin_repo) = temp_repo("clone-origin");
+        commit(&origin_repo, "initial");
+        let root = std::env::temp_dir().join("project-dash-test-clone-root");
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+
+        assert!(resolve_clone_dest(&root, "../escape").is_err());
+        assert!(resolve_clone_dest(&root, "/abs").is_err());
+        let dest = resolve_clone_dest(&root, "group/copy").unwrap();
+
+        let mut updates = Vec::new();
+        clone_repo(origin.to_str().unwrap(), &dest, |p| updates.push(p)).unwrap();
+        assert!(Repository::open(&dest).is_ok());
+        assert!(resolve_clone_dest(&root, "group/copy").is_err());
+
+        let _ = std::fs::remove_dir_all(&origin);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_first_changed_line() {
+        let (dir, repo) = temp_repo("first-changed-line");
+        std::fs::write(dir.join("a.txt"), "one\ntwo\nthree\nfour\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("a.txt")).unwrap();
+        index.write().unwrap();
+        commit(&repo, "initial");
+
+        std::fs::write(dir.join("a.txt"), "one\ntwo\nTHREE\nfour\n").unwrap();
+        assert_eq!(first_changed_line(&dir, "a.txt"), Some(3));
+        assert_eq!(first_changed_line(&dir, "missing.txt"), None);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_branch_push_url_prefers_push_remote() {
+        let (dir, repo) = temp_repo("push-url");
+        commit(&repo, "init");
+        repo.remote("origin", "https://github.com/upstream/app.git")
+            .unwrap();
+        repo.remote("fork", "git@github.com:me/app.git").unwrap();
+        assert_eq!(
+            branch_push_url(&dir, "feature").as_deref(),
+            Some("https://github.com/upstream/app.git")
+        );
+
+        repo.config()
+            .unwrap()
+            .set_str("branch.feature.pushRemote", "fork")
+            .unwrap();
+        assert_eq!(
+            branch_push_url(&dir, "feature").as_deref(),
+            Some("git@github.com:me/app.git")
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_default_branch() {
+        let (dir, repo) = temp_repo("default-branch");
+        let head = commit(&repo, "init");
+        assert_eq!(default_branch(&repo).as_deref(), Some("main"));
+
+        // With both present, `main` is the guess
+        repo.branch("master", &repo.find_commit(head).unwrap(), false)
+            .unwrap();
+        assert_eq!(default_branch(&repo).as_deref(), Some("main"));
+
+        // origin/HEAD wins over guessing from local branch names
+        repo.reference("refs/remotes/origin/trunk", head, true, "test")
+            .unwrap();
+        repo.reference_symbolic(
+            "refs/remotes/origin/HEAD",
+            "refs/remotes/origin/trunk",
+            true,
+            "test",
+        )
+        .unwrap();
+        assert_eq!(default_branch(&repo).as_deref(), Some("trunk"));
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
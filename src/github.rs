@@ -3,170 +3,5171 @@ use std::path::PathBuf;
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
+use octocrab::models::pulls::{MergeableState, Review, ReviewState};
 use octocrab::Octocrab;
+use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 
 use crate::app::Message;
-use crate::repo::{GitHubData, GitHubItem};
+use crate::auth::TokenSource;
+use crate::config::GitHubConfig;
+use crate::repo::{GitHubData, GitHubItem, GitHubRepo, RepoMeta, GITHUB_HOST};
 
 const CACHE_TTL: Duration = Duration::from_secs(60 * 60);
-const RECENT_ITEMS: u8 = 5;
+pub const RECENT_ITEMS: u8 = 5;
+/// Open issues fetched to pick the recent ones from, since the issues
+/// endpoint mixes in pull requests.
+const ISSUE_SCAN_ITEMS: u8 = 30;
+/// Comments fetched per page of an issue thread.
+const COMMENT_PAGE_SIZE: u8 = 30;
+/// Workflow runs listed in the Actions tab.
+const WORKFLOW_RUNS: u8 = 20;
+/// Lines kept from the end of each failed job's log.
+const LOG_TAIL_LINES: usize = 300;
+/// Releases listed in the Releases tab.
+const RELEASES: u8 = 20;
 
 struct CacheEntry {
     data: GitHubData,
-    fetched_at: Instant,
+    /// `None` for data loaded from disk, which is shown but always
+    /// revalidated before being served as fresh.
+    fetched_at: Option<Instant>,
 }
 
 static CACHE: std::sync::LazyLock<Mutex<HashMap<String, CacheEntry>>> =
     std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
 
-fn cache_key(owner: &str, repo: &str) -> String {
-    format!("{owner}/{repo}")
+/// Where `CACHE` and `ETAGS` are persisted, once `load_disk_cache` ran.
+static DISK_CACHE_PATH: std::sync::OnceLock<PathBuf> = std::sync::OnceLock::new();
+
+/// Set when the caches changed since they were last written to disk.
+static CACHE_DIRTY: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// How often changed caches are written to disk.
+const DISK_FLUSH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Set in offline mode: no client is handed out, so nothing hits the network.
+static OFFLINE: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+fn cache_key(host: &str, owner: &str, repo: &str) -> String {
+    format!("{host}/{owner}/{repo}")
 }
 
-fn get_cached(owner: &str, repo: &str) -> Option<GitHubData> {
+fn get_cached(host: &str, owner: &str, repo: &str) -> Option<GitHubData> {
     let cache = CACHE.lock().ok()?;
-    let entry = cache.get(&cache_key(owner, repo))?;
-    if entry.fetched_at.elapsed() < CACHE_TTL {
+    let entry = cache.get(&cache_key(host, owner, repo))?;
+    if entry.fetched_at?.elapsed() < CACHE_TTL {
         Some(entry.data.clone())
     } else {
         None
     }
 }
 
-fn set_cached(owner: &str, repo: &str, data: &GitHubData) {
+fn set_cached(host: &str, owner: &str, repo: &str, data: &GitHubData) {
     if let Ok(mut cache) = CACHE.lock() {
         cache.insert(
-            cache_key(owner, repo),
+            cache_key(host, owner, repo),
             CacheEntry {
                 data: data.clone(),
-                fetched_at: Instant::now(),
+                fetched_at: Some(Instant::now()),
             },
         );
     }
+    CACHE_DIRTY.store(true, std::sync::atomic::Ordering::Relaxed);
 }
 
-pub fn invalidate_cached(owner: &str, repo: &str) {
+/// The last data known for a repo, however old, to show while it is
+/// refreshed.
+pub fn cached_data(repo: &GitHubRepo) -> Option<GitHubData> {
+    let cache = CACHE.lock().ok()?;
+    cache
+        .get(&cache_key(&repo.host, &repo.owner, &repo.name))
+        .map(|entry| entry.data.clone())
+}
+
+pub fn invalidate_cached(repo: &GitHubRepo) {
     if let Ok(mut cache) = CACHE.lock() {
-        cache.remove(&cache_key(owner, repo));
+        cache.remove(&cache_key(&repo.host, &repo.owner, &repo.name));
     }
 }
 
-pub struct GitHubClient {
-    client: Octocrab,
+/// Replace the cached data for a repo after changing it through the API,
+/// so a cache hit doesn't bring back the stale version.
+pub fn update_cached(repo: &GitHubRepo, data: &GitHubData) {
+    set_cached(&repo.host, &repo.owner, &repo.name, data);
 }
 
-impl GitHubClient {
-    pub fn new(token: Option<String>) -> color_eyre::Result<Self> {
-        let mut builder = Octocrab::builder();
-        if let Some(token) = token {
-            builder = builder.personal_token(token);
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, std::sync::atomic::Ordering::Relaxed);
+}
+
+pub fn is_offline() -> bool {
+    OFFLINE.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// The on-disk form of the caches.
+#[derive(Default, Serialize, Deserialize)]
+struct DiskCache {
+    repos: HashMap<String, GitHubData>,
+    etags: HashMap<String, EtagEntry>,
+}
+
+/// Fill the caches from `path` and keep it updated from now on, writing
+/// changes every [`DISK_FLUSH_INTERVAL`]. A missing or unreadable file just
+/// starts an empty cache.
+pub fn load_disk_cache(path: PathBuf) {
+    fill_caches(read_disk_cache(&path));
+    let _ = DISK_CACHE_PATH.set(path);
+    tokio::spawn(async {
+        let mut interval = tokio::time::interval(DISK_FLUSH_INTERVAL);
+        loop {
+            interval.tick().await;
+            let _ = tokio::task::spawn_blocking(flush_disk_cache).await;
         }
-        let client = builder.build()?;
-        Ok(Self { client })
+    });
+}
+
+/// Write the caches to disk if they changed since the last write. Runs in
+/// the background and once more on exit.
+pub fn flush_disk_cache() {
+    if CACHE_DIRTY.swap(false, std::sync::atomic::Ordering::Relaxed) {
+        save_disk_cache();
     }
+}
 
-    pub async fn fetch_repo_data(
-        &self,
-        owner: &str,
-        repo: &str,
-    ) -> color_eyre::Result<GitHubData> {
-        if let Some(cached) = get_cached(owner, repo) {
-            return Ok(cached);
+fn read_disk_cache(path: &std::path::Path) -> DiskCache {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn fill_caches(disk: DiskCache) {
+    if let Ok(mut cache) = CACHE.lock() {
+        for (key, data) in disk.repos {
+            cache.entry(key).or_insert(CacheEntry {
+                data,
+                fetched_at: None,
+            });
+        }
+    }
+    if let Ok(mut etags) = ETAGS.lock() {
+        for (key, entry) in disk.etags.into_iter().filter(|(_, e)| e.disk) {
+            etags.entry(key).or_insert(entry);
         }
+    }
+}
 
-        let issues_page = self
-            .client
-            .issues(owner, repo)
-            .list()
-            .state(octocrab::params::State::Open)
-            .per_page(RECENT_ITEMS)
-            .send()
-            .await?;
+fn save_disk_cache() {
+    let Some(path) = DISK_CACHE_PATH.get() else {
+        return;
+    };
+    let disk = DiskCache {
+        repos: CACHE
+            .lock()
+            .map(|cache| {
+                cache
+                    .iter()
+                    .map(|(k, e)| (k.clone(), e.data.clone()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        etags: ETAGS
+            .lock()
+            .map(|etags| {
+                etags
+                    .iter()
+                    .filter(|(_, e)| e.disk)
+                    .map(|(k, e)| (k.clone(), e.clone()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+    };
+    write_disk_cache(path, &disk);
+}
 
-        let prs_page = self
-            .client
-            .pulls(owner, repo)
-            .list()
-            .state(octocrab::params::State::Open)
-            .per_page(RECENT_ITEMS)
-            .send()
-            .await?;
+fn write_disk_cache(path: &std::path::Path, disk: &DiskCache) {
+    let Ok(json) = serde_json::to_string(disk) else {
+        return;
+    };
+    // Write a sibling file and rename it over, so a crash never leaves a
+    // half-written cache behind
+    static SAVING: Mutex<()> = Mutex::new(());
+    let _saving = SAVING.lock();
+    let tmp = path.with_extension("json.tmp");
+    let written = path
+        .parent()
+        .is_none_or(|dir| std::fs::create_dir_all(dir).is_ok())
+        && write_private(&tmp, json.as_bytes()).is_ok()
+        && std::fs::rename(&tmp, path).is_ok();
+    if !written {
+        let _ = std::fs::remove_file(&tmp);
+    }
+}
 
-        let total_issues =
-            issues_page.total_count.unwrap_or(issues_page.items.len() as u64);
-        let total_prs =
-            prs_page.total_count.unwrap_or(prs_page.items.len() as u64);
+/// Write `contents` to a new file only the user can read: the cache holds
+/// private repos' data.
+fn write_private(path: &std::path::Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
 
-        // GitHub issues endpoint includes PRs, so subtract for "pure" issues
-        let open_issues = (total_issues as usize).saturating_sub(total_prs as usize);
-        let open_prs = total_prs as usize;
+    // A leftover file would keep its own mode
+    let _ = std::fs::remove_file(path);
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options.open(path)?.write_all(contents)
+}
 
-        // Filter out PRs from the issues list (they have a pull_request field)
-        let recent_issues: Vec<GitHubItem> = issues_page
-            .items
-            .iter()
-            .filter(|i| i.pull_request.is_none())
-            .take(RECENT_ITEMS as usize)
-            .map(|i| GitHubItem {
-                number: i.number,
-                title: i.title.clone(),
-            })
-            .collect();
+/// Refresh a target's quota from `/rate_limit` (which is free) when the last
+/// reading is older than this.
+const RATE_LIMIT_REFRESH: Duration = Duration::from_secs(60);
 
-        let recent_prs: Vec<GitHubItem> = prs_page
-            .items
+/// Background prefetches leave this fraction (1/n) of a target's quota alone.
+const PREFETCH_RESERVE_DIVISOR: u32 = 10;
+
+/// A target's REST API quota, from the `X-RateLimit-*` response headers.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimit {
+    /// Zero when the host doesn't report a limit (e.g. Enterprise with
+    /// rate limiting disabled).
+    pub limit: u32,
+    pub remaining: u32,
+    /// When the quota resets, in seconds since the Unix epoch.
+    pub reset: u64,
+    checked: Instant,
+}
+
+impl RateLimit {
+    /// How long to hold requests back: until the reset when exhausted.
+    pub fn wait(&self) -> Option<Duration> {
+        if self.limit == 0 || self.remaining > 0 {
+            return None;
+        }
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        // One extra second so the request lands after the reset
+        (self.reset >= now).then(|| Duration::from_secs(self.reset - now + 1))
+    }
+}
+
+/// Quotas keyed by [`target_key`], since each token has its own, with the
+/// target's label for the status bar.
+static RATE_LIMITS: std::sync::LazyLock<Mutex<HashMap<String, (String, RateLimit)>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// The last known quota of the target with `key`.
+fn rate_limit(key: &str) -> Option<RateLimit> {
+    RATE_LIMITS.lock().ok()?.get(key).map(|(_, r)| *r)
+}
+
+/// The label of the target closest to running out of quota, and its quota,
+/// for the status bar.
+pub fn tightest_rate_limit() -> Option<(String, RateLimit)> {
+    let limits = RATE_LIMITS.lock().ok()?;
+    limits
+        .values()
+        .filter(|(_, r)| r.limit > 0)
+        .min_by_key(|(_, r)| r.remaining as u64 * 1000 / r.limit as u64)
+        .cloned()
+}
+
+fn set_rate_limit(key: &str, label: &str, limit: RateLimit) {
+    if let Ok(mut limits) = RATE_LIMITS.lock() {
+        limits.insert(key.to_string(), (label.to_string(), limit));
+    }
+}
+
+fn record_rate_limit(key: &str, label: &str, headers: &http::HeaderMap) {
+    let header = |name: &str| headers.get(name)?.to_str().ok()?.parse::<u64>().ok();
+    let limit = match (
+        header("x-ratelimit-limit"),
+        header("x-ratelimit-remaining"),
+        header("x-ratelimit-reset"),
+    ) {
+        (Some(limit), Some(remaining), Some(reset)) => RateLimit {
+            limit: limit as u32,
+            remaining: remaining as u32,
+            reset,
+            checked: Instant::now(),
+        },
+        _ => return,
+    };
+    set_rate_limit(key, label, limit);
+}
+
+/// A response body stored under its ETag for conditional requests.
+#[derive(Clone, Serialize, Deserialize)]
+struct EtagEntry {
+    etag: String,
+    body: String,
+    #[serde(flatten)]
+    pages: Pages,
+    /// Kept in the disk cache. Entries written before this existed weren't
+    /// limited to repo summaries, so they are dropped on load.
+    #[serde(default)]
+    disk: bool,
+    #[serde(skip, default = "Instant::now")]
+    stored: Instant,
+}
+
+/// Whether a GET keeps its response for conditional requests. Only
+/// metadata and listings that are fetched again and again do; one-off and
+/// per-item reads would just fill memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CachePolicy {
+    None,
+    /// Kept in memory, within [`ETAG_MAX_AGE`] and [`ETAG_MAX_ENTRIES`].
+    Memory,
+    /// Also written to the disk cache: only the repo summary routes, which
+    /// back the data shown at startup.
+    Disk,
+}
+
+/// In-memory ETag entries older than this are dropped.
+const ETAG_MAX_AGE: Duration = Duration::from_secs(30 * 60);
+
+/// At most this many in-memory ETag entries are kept, oldest dropped first.
+const ETAG_MAX_ENTRIES: usize = 200;
+
+/// Drop expired in-memory entries, then the oldest ones over the cap. Disk
+/// entries are bounded by the repos the dashboard shows.
+fn evict_etags(etags: &mut HashMap<String, EtagEntry>) {
+    etags.retain(|_, e| e.disk || e.stored.elapsed() < ETAG_MAX_AGE);
+    let mut memory: Vec<_> = etags
+        .iter()
+        .filter(|(_, e)| !e.disk)
+        .map(|(k, e)| (e.stored, k.clone()))
+        .collect();
+    if memory.len() > ETAG_MAX_ENTRIES {
+        memory.sort();
+        for (_, key) in &memory[..memory.len() - ETAG_MAX_ENTRIES] {
+            etags.remove(key);
+        }
+    }
+}
+
+/// The `next` and `last` pages a paginated listing links to.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+struct Pages {
+    #[serde(default)]
+    next_page: Option<u32>,
+    last_page: Option<u32>,
+}
+
+impl Pages {
+    fn from_headers(headers: &http::HeaderMap) -> Self {
+        Self {
+            next_page: linked_page(headers, "next"),
+            last_page: linked_page(headers, "last"),
+        }
+    }
+}
+
+static ETAGS: std::sync::LazyLock<Mutex<HashMap<String, EtagEntry>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Page number of the `rel` link in a `Link` header.
+fn linked_page(headers: &http::HeaderMap, rel: &str) -> Option<u32> {
+    let link = headers.get("link")?.to_str().ok()?;
+    let rel = format!("rel=\"{rel}\"");
+    let part = link.split(',').find(|part| part.contains(&rel))?;
+    let url = part.split(['<', '>']).nth(1)?;
+    url.split_once('?')?
+        .1
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("page="))?
+        .parse()
+        .ok()
+}
+
+/// Identifies an API target: requests to the same host with another API
+/// root or token are counted separately.
+fn target_key(target: &HostTarget) -> String {
+    format!("{}|{:?}|{:?}", target.host, target.api_url, target.token)
+}
+
+/// Clients shared by all background fetches, keyed by [`target_key`].
+static CLIENTS: std::sync::LazyLock<Mutex<HashMap<String, GitHubClient>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// A user-facing description of a failed API call. GitHub's own errors
+/// (permissions, locked conversations, validation) are explained in one
+/// line instead of octocrab's multi-line display.
+pub fn describe_error(e: &color_eyre::Report) -> String {
+    let Some(octocrab::Error::GitHub { source, .. }) = e.downcast_ref::<octocrab::Error>() else {
+        return e.to_string();
+    };
+    let message = source.message.trim_end_matches('.');
+    let details: Vec<String> = source
+        .errors
+        .iter()
+        .flatten()
+        .filter_map(|err| {
+            err.get("message")
+                .and_then(|m| m.as_str())
+                .map(String::from)
+        })
+        .collect();
+    let message = if details.is_empty() {
+        message.to_string()
+    } else {
+        format!("{message}: {}", details.join("; "))
+    };
+
+    match source.status_code.as_u16() {
+        401 => "Not authenticated: set a GitHub token for this host".to_string(),
+        403 if message.to_lowercase().contains("locked") => {
+            format!("Conversation is locked: {message}")
+        }
+        403 => format!("Permission denied: {message}"),
+        404 => "Not found, or the token has no access to it".to_string(),
+        410 => format!("Disabled for this repository: {message}"),
+        422 => format!("Rejected by GitHub: {message}"),
+        code => format!("GitHub error {code}: {message}"),
+    }
+}
+
+/// Explain why GitHub refused a merge: 405 carries the branch protection
+/// rule or conflict in the way, 409 a head that moved since review.
+fn merge_error(e: color_eyre::Report) -> color_eyre::Report {
+    let Some(octocrab::Error::GitHub { source, .. }) = e.downcast_ref::<octocrab::Error>() else {
+        return e;
+    };
+    match source.status_code.as_u16() {
+        405 => color_eyre::eyre::eyre!("Merge blocked: {}", source.message.trim_end_matches('.')),
+        409 => color_eyre::eyre::eyre!(
+            "The head branch changed since the PR was loaded; reload it and review again"
+        ),
+        _ => e,
+    }
+}
+
+/// The HTTP status of a failed API call, if GitHub answered.
+fn error_status(e: &color_eyre::Report) -> Option<u16> {
+    match e.downcast_ref::<octocrab::Error>()? {
+        octocrab::Error::GitHub { source, .. } => Some(source.status_code.as_u16()),
+        _ => None,
+    }
+}
+
+/// Where and with which credentials to reach one GitHub host's API.
+#[derive(Debug, Clone)]
+pub struct HostTarget {
+    pub host: String,
+    /// REST API base URL; `None` means api.github.com.
+    pub api_url: Option<String>,
+    pub token: Option<String>,
+    pub token_source: Option<TokenSource>,
+}
+
+/// Resolve the API target for `host` from config, preferring a token
+/// configured for `owner`. `default_token` (from the command line or
+/// environment) is only ever sent to github.com.
+pub fn host_target(
+    config: &GitHubConfig,
+    host: &str,
+    owner: Option<&str>,
+    default_token: Option<&crate::auth::Token>,
+) -> HostTarget {
+    let host_config = config.host(host);
+    let is_dotcom = host.eq_ignore_ascii_case(GITHUB_HOST);
+
+    let api_url = host_config
+        .and_then(|c| c.api_url.clone())
+        .or_else(|| (!is_dotcom).then(|| format!("https://{host}/api/v3")));
+    let owner_token = owner.zip(host_config).and_then(|(owner, c)| {
+        c.owner_tokens
             .iter()
-            .take(RECENT_ITEMS as usize)
-            .map(|pr| GitHubItem {
-                number: pr.number,
-                title: pr.title.as_deref().unwrap_or("(no title)").to_string(),
+            .find(|(o, _)| o.eq_ignore_ascii_case(owner))
+            .map(|(o, token)| (token.clone(), TokenSource::ConfigOwner(o.clone())))
+    });
+    let token = owner_token
+        .or_else(|| {
+            host_config
+                .and_then(|c| c.token.clone())
+                .map(|t| (t, TokenSource::Config))
+        })
+        .or_else(|| default_token.filter(|_| is_dotcom).cloned());
+
+    let (token, token_source) = token.unzip();
+    HostTarget {
+        host: host.to_lowercase(),
+        api_url,
+        token,
+        token_source,
+    }
+}
+
+/// `host_target`, falling back to a token the `gh` CLI or a git credential
+/// helper was found to have for the host by [`spawn_token_discovery`].
+pub fn resolve_target(
+    config: &GitHubConfig,
+    host: &str,
+    owner: Option<&str>,
+    default_token: Option<&crate::auth::Token>,
+) -> HostTarget {
+    let mut target = host_target(config, host, owner, default_token);
+    if target.token.is_none() && !config.offline {
+        if let Some((token, source)) = crate::auth::known_token(host) {
+            target.token = Some(token);
+            target.token_source = Some(source);
+        }
+    }
+    target
+}
+
+/// A repository on GitHub, as listed for an owner or org.
+#[derive(Debug, Clone)]
+pub struct RemoteRepo {
+    pub name: String,
+    pub full_name: String,
+    pub clone_url: String,
+    pub description: Option<String>,
+    pub archived: bool,
+}
+
+/// Combined outcome of a commit's check runs and statuses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CheckState {
+    Success,
+    Failure,
+    Pending,
+    /// Skipped, neutral or cancelled: neither passing nor failing.
+    Neutral,
+}
+
+impl CheckState {
+    fn from_check_run(status: &str, conclusion: Option<&str>) -> Self {
+        match (status, conclusion) {
+            ("completed", Some("success")) => Self::Success,
+            (
+                "completed",
+                Some("failure" | "timed_out" | "action_required" | "startup_failure"),
+            ) => Self::Failure,
+            ("completed", _) => Self::Neutral,
+            _ => Self::Pending,
+        }
+    }
+
+    /// From a GraphQL `StatusState`, as in a commit's `statusCheckRollup`.
+    fn from_rollup(state: &str) -> Self {
+        match state {
+            "SUCCESS" => Self::Success,
+            "FAILURE" | "ERROR" => Self::Failure,
+            _ => Self::Pending,
+        }
+    }
+
+    fn from_status(state: &str) -> Self {
+        match state {
+            "success" => Self::Success,
+            "failure" | "error" => Self::Failure,
+            _ => Self::Pending,
+        }
+    }
+
+    /// Any failure fails the whole set; otherwise anything still running
+    /// keeps it pending. `None` when there is nothing to combine.
+    pub fn combine(states: impl IntoIterator<Item = CheckState>) -> Option<CheckState> {
+        states.into_iter().fold(None, |acc, s| {
+            Some(match (acc, s) {
+                (Some(Self::Failure), _) | (_, Self::Failure) => Self::Failure,
+                (Some(Self::Pending), _) | (_, Self::Pending) => Self::Pending,
+                (Some(Self::Success), _) | (_, Self::Success) => Self::Success,
+                _ => Self::Neutral,
             })
-            .collect();
+        })
+    }
+}
 
-        let data = GitHubData {
-            open_issues,
-            open_prs,
-            recent_issues,
-            recent_prs,
-        };
+/// One CI check run or commit status on a PR's head commit.
+#[derive(Debug, Clone)]
+pub struct Check {
+    pub name: String,
+    pub state: CheckState,
+    pub url: Option<String>,
+}
 
-        set_cached(owner, repo, &data);
-        Ok(data)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewVerdict {
+    Approved,
+    ChangesRequested,
+    Commented,
+    Dismissed,
+}
+
+/// Whether GitHub would let the PR be merged right now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mergeability {
+    Clean,
+    /// Mergeable, but some checks are failing or pending.
+    Unstable,
+    Blocked,
+    Behind,
+    Conflicting,
+    Draft,
+    /// GitHub has not finished computing it yet.
+    Unknown,
+}
+
+impl Mergeability {
+    fn from_pr(mergeable: Option<bool>, state: Option<&MergeableState>) -> Self {
+        if mergeable == Some(false) {
+            return Self::Conflicting;
+        }
+        match state {
+            Some(MergeableState::Clean | MergeableState::HasHooks) => Self::Clean,
+            Some(MergeableState::Unstable) => Self::Unstable,
+            Some(MergeableState::Blocked) => Self::Blocked,
+            Some(MergeableState::Behind) => Self::Behind,
+            Some(MergeableState::Dirty) => Self::Conflicting,
+            Some(MergeableState::Draft) => Self::Draft,
+            _ => Self::Unknown,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Clean => "ready to merge",
+            Self::Unstable => "mergeable, checks not passing",
+            Self::Blocked => "blocked by branch protection",
+            Self::Behind => "behind base branch",
+            Self::Conflicting => "has conflicts",
+            Self::Draft => "draft",
+            Self::Unknown => "checking…",
+        }
     }
 }
 
-/// Spawn a single background task to fetch GitHub data for one repo.
-/// Result is sent back via the provided channel.
-pub fn spawn_github_fetch(
-    path: PathBuf,
-    owner: String,
-    repo: String,
-    token: Option<String>,
-    tx: mpsc::UnboundedSender<Message>,
-) {
-    tokio::spawn(async move {
-        let client = match GitHubClient::new(token) {
-            Ok(c) => c,
-            Err(e) => {
-                let _ = tx.send(Message::GitHubError {
-                    path,
-                    error: e.to_string(),
-                });
-                return;
-            }
+/// Everything the PR detail view shows, fetched when a PR is opened.
+#[derive(Debug, Clone)]
+pub struct PrDetail {
+    pub number: u64,
+    pub title: String,
+    pub author: String,
+    pub open: bool,
+    pub draft: bool,
+    pub base: String,
+    /// `owner:branch` for PRs from forks, otherwise the branch name.
+    pub head: String,
+    pub head_sha: String,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+    /// Reviewers (and `@org/team`s) asked for a review who have not given one.
+    pub requested_reviewers: Vec<String>,
+    /// Latest verdict per reviewer.
+    pub reviews: Vec<(String, ReviewVerdict)>,
+    pub checks: Vec<Check>,
+    pub mergeability: Mergeability,
+    pub additions: u64,
+    pub deletions: u64,
+    pub changed_files: u64,
+    pub body: String,
+    pub html_url: Option<String>,
+}
+
+impl PrDetail {
+    pub fn check_state(&self) -> Option<CheckState> {
+        CheckState::combine(self.checks.iter().map(|c| c.state))
+    }
+
+    /// What stands in the way of merging, as far as the PR's state, checks
+    /// and reviews tell. GitHub has the final say: admins may bypass some.
+    pub fn merge_blockers(&self) -> Vec<String> {
+        if !self.open {
+            return vec!["the PR is closed".to_string()];
+        }
+        let mut blockers = Vec::new();
+        if self.draft {
+            blockers.push("the PR is a draft".to_string());
+        }
+        if self.mergeability == Mergeability::Conflicting {
+            blockers.push(format!("conflicts with {}", self.base));
+        }
+        let checks = |state: CheckState| -> Vec<&str> {
+            self.checks
+                .iter()
+                .filter(|c| c.state == state)
+                .map(|c| c.name.as_str())
+                .collect()
+        };
+        let failing = checks(CheckState::Failure);
+        if !failing.is_empty() {
+            blockers.push(format!("failing checks: {}", failing.join(", ")));
+        }
+        let pending = checks(CheckState::Pending);
+        if !pending.is_empty() {
+            blockers.push(format!("checks still running: {}", pending.join(", ")));
+        }
+        let reviewers = |verdict: ReviewVerdict| -> Vec<&str> {
+            self.reviews
+                .iter()
+                .filter(|(_, v)| *v == verdict)
+                .map(|(r, _)| r.as_str())
+                .collect()
         };
+        let changes_requested = reviewers(ReviewVerdict::ChangesRequested);
+        if !changes_requested.is_empty() {
+            blockers.push(format!(
+                "changes requested by {}",
+                changes_requested.join(", ")
+            ));
+        }
+        if self.mergeability == Mergeability::Behind {
+            blockers.push(format!("behind {}; the branch needs updating", self.base));
+        }
+        if self.mergeability == Mergeability::Blocked {
+            if reviewers(ReviewVerdict::Approved).is_empty() {
+                blockers.push("required approving review missing".to_string());
+            } else if blockers.is_empty() {
+                blockers.push(Mergeability::Blocked.label().to_string());
+            }
+        }
+        blockers
+    }
+}
+
+/// How a PR's commits land on its base branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeMethod {
+    Merge,
+    Squash,
+    Rebase,
+}
+
+impl MergeMethod {
+    pub const ALL: [Self; 3] = [Self::Merge, Self::Squash, Self::Rebase];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Merge => "Create a merge commit",
+            Self::Squash => "Squash and merge",
+            Self::Rebase => "Rebase and merge",
+        }
+    }
+
+    /// Rebasing replays the PR's commits as they are, so it takes no
+    /// commit title or message.
+    pub fn takes_message(self) -> bool {
+        self != Self::Rebase
+    }
 
-        match client.fetch_repo_data(&owner, &repo).await {
-            Ok(data) => {
-                let _ = tx.send(Message::GitHubDataReceived { path, data });
+    /// GitHub's default commit title and message for merging `pr` of a
+    /// repo owned by `owner` this way.
+    pub fn default_commit(self, pr: &PrDetail, owner: &str) -> (String, String) {
+        match self {
+            Self::Merge => {
+                let head = match pr.head.split_once(':') {
+                    Some((head_owner, branch)) => format!("{head_owner}/{branch}"),
+                    None => format!("{owner}/{}", pr.head),
+                };
+                (
+                    format!("Merge pull request #{} from {head}", pr.number),
+                    pr.title.clone(),
+                )
             }
-            Err(e) => {
-                let _ = tx.send(Message::GitHubError {
-                    path,
-                    error: e.to_string(),
-                });
+            Self::Squash => (format!("{} (#{})", pr.title, pr.number), pr.body.clone()),
+            Self::Rebase => (String::new(), String::new()),
+        }
+    }
+
+    fn param(self) -> octocrab::params::pulls::MergeMethod {
+        match self {
+            Self::Merge => octocrab::params::pulls::MergeMethod::Merge,
+            Self::Squash => octocrab::params::pulls::MergeMethod::Squash,
+            Self::Rebase => octocrab::params::pulls::MergeMethod::Rebase,
+        }
+    }
+}
+
+/// A repo's merge settings, loaded when the merge form opens.
+#[derive(Debug, Clone)]
+pub struct MergeSettings {
+    pub methods: Vec<MergeMethod>,
+    /// The repo deletes head branches itself once merged.
+    pub delete_branch_on_merge: bool,
+}
+
+/// A merge as confirmed in the merge form.
+#[derive(Debug, Clone)]
+pub struct MergeRequest {
+    pub method: MergeMethod,
+    pub title: String,
+    pub message: String,
+    /// The head commit the PR was reviewed at; GitHub refuses the merge if
+    /// the branch has moved since.
+    pub sha: String,
+    /// The head branch to delete once merged.
+    pub delete_branch: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct IssueComment {
+    pub author: String,
+    pub created_at: String,
+    pub body: String,
+}
+
+/// An issue with the first page of its comment thread. Later pages are
+/// fetched on demand with [`GitHubClient::fetch_issue_comments`].
+#[derive(Debug, Clone)]
+pub struct IssueDetail {
+    pub number: u64,
+    pub title: String,
+    pub open: bool,
+    pub author: String,
+    pub created_at: String,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+    pub milestone: Option<String>,
+    pub body: String,
+    pub html_url: String,
+    pub comments: Vec<IssueComment>,
+    pub total_comments: u32,
+    /// Next comment page to fetch, if the thread continues.
+    pub next_comment_page: Option<u32>,
+}
+
+/// A markdown issue template from `.github/ISSUE_TEMPLATE`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IssueTemplate {
+    pub name: String,
+    pub title: String,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+    pub body: String,
+}
+
+/// Split a template into its YAML front matter keys and the body. Only the
+/// flat keys GitHub uses are understood; lists may be inline (`[a, b]`),
+/// comma separated or `- item` lines.
+pub fn parse_issue_template(name: &str, contents: &str) -> IssueTemplate {
+    let mut template = IssueTemplate {
+        name: name.to_string(),
+        ..Default::default()
+    };
+
+    let Some(rest) = contents.strip_prefix("---") else {
+        template.body = contents.to_string();
+        return template;
+    };
+    let Some(end) = rest.find("\n---") else {
+        template.body = contents.to_string();
+        return template;
+    };
+    let front = &rest[..end];
+    // Skip the rest of the closing `---` line
+    template.body = rest[end + 4..]
+        .split_once('\n')
+        .map_or("", |(_, body)| body)
+        .to_string();
+
+    let unquote = |v: &str| v.trim().trim_matches(['"', '\'']).to_string();
+    let mut current_list: Option<&str> = None;
+    for line in front.lines() {
+        if let Some(item) = line.trim_start().strip_prefix("- ") {
+            let item = unquote(item);
+            match current_list {
+                Some("labels") => template.labels.push(item),
+                Some("assignees") => template.assignees.push(item),
+                _ => {}
             }
+            continue;
         }
-    });
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let key = key.trim();
+        current_list = Some(key).filter(|_| value.trim().is_empty());
+        let items = || -> Vec<String> {
+            value
+                .trim()
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .split(',')
+                .map(unquote)
+                .filter(|v| !v.is_empty())
+                .collect()
+        };
+        match key {
+            "name" => template.name = unquote(value),
+            "title" => template.title = unquote(value),
+            "labels" => template.labels = items(),
+            "assignees" => template.assignees = items(),
+            _ => {}
+        }
+    }
+    template
+}
+
+/// Markdown issue templates in `<repo>/.github/ISSUE_TEMPLATE/` (or the
+/// single legacy `.github/ISSUE_TEMPLATE.md`), sorted by name. YAML issue
+/// forms are skipped since they need GitHub's form renderer.
+pub fn load_issue_templates(repo_path: &std::path::Path) -> Vec<IssueTemplate> {
+    let github_dir = repo_path.join(".github");
+    let mut templates: Vec<IssueTemplate> = std::fs::read_dir(github_dir.join("ISSUE_TEMPLATE"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|p| p.extension().is_some_and(|e| e.eq_ignore_ascii_case("md")))
+        .filter_map(|p| {
+            let contents = std::fs::read_to_string(&p).ok()?;
+            let stem = p.file_stem()?.to_string_lossy().to_string();
+            Some(parse_issue_template(&stem, &contents))
+        })
+        .collect();
+    if let Ok(contents) = std::fs::read_to_string(github_dir.join("ISSUE_TEMPLATE.md")) {
+        templates.push(parse_issue_template("Default", &contents));
+    }
+    templates.sort_by_key(|t| t.name.to_lowercase());
+    templates
+}
+
+/// What a new issue is created with.
+#[derive(Debug, Clone, Default)]
+pub struct NewIssue {
+    pub title: String,
+    pub body: String,
+    pub labels: Vec<String>,
+    pub assignees: Vec<String>,
+}
+
+/// A change made to an open issue or PR. PRs are issues as far as these
+/// endpoints are concerned.
+#[derive(Debug, Clone)]
+pub enum ItemAction {
+    Comment(String),
+    SetOpen(bool),
+    SetLabels(Vec<String>),
+    /// Assign (`true`) or unassign the authenticated user.
+    AssignMe(bool),
+    Merge(MergeRequest),
+}
+
+/// The item's new state after an [`ItemAction`].
+#[derive(Debug, Clone)]
+pub enum ActionOutcome {
+    Commented(IssueComment),
+    StateChanged {
+        open: bool,
+    },
+    Labels(Vec<String>),
+    Assignees(Vec<String>),
+    /// What happened, for the status line.
+    Merged(String),
+}
+
+/// Details loaded for an issue or PR opened in the detail pane.
+#[derive(Debug, Clone)]
+pub enum ItemDetail {
+    Issue(Box<IssueDetail>),
+    Pr(Box<PrDetail>),
+}
+
+/// Why an item shows up in the "My work" inbox.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InboxReason {
+    ReviewRequested,
+    Authored,
+    Assigned,
+}
+
+impl InboxReason {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ReviewRequested => "review",
+            Self::Authored => "mine",
+            Self::Assigned => "assigned",
+        }
+    }
+
+    /// Search qualifiers selecting the open items for this reason.
+    fn query(self) -> &'static str {
+        match self {
+            Self::ReviewRequested => "is:open is:pr review-requested:@me archived:false",
+            Self::Authored => "is:open is:pr author:@me archived:false",
+            Self::Assigned => "is:open is:issue assignee:@me archived:false",
+        }
+    }
+}
+
+/// GitHub rejects search queries longer than this.
+const SEARCH_QUERY_LIMIT: usize = 256;
+
+/// Search returns at most this many results for a query.
+const SEARCH_MAX_RESULTS: u32 = 1000;
+
+/// Authored PRs whose checks the inbox looks up at once.
+const INBOX_CHECK_LOOKUPS: usize = 8;
+
+/// `base` scoped to `repos` with `repo:` qualifiers, split into as few
+/// queries as fit in [`SEARCH_QUERY_LIMIT`].
+fn repo_scoped_queries(base: &str, repos: &[GitHubRepo]) -> Vec<String> {
+    let mut queries = Vec::new();
+    let mut query = base.to_string();
+    for repo in repos {
+        let qualifier = format!(" repo:{}/{}", repo.owner, repo.name);
+        if query.len() + qualifier.len() > SEARCH_QUERY_LIMIT && query.len() > base.len() {
+            queries.push(std::mem::replace(&mut query, base.to_string()));
+        }
+        query.push_str(&qualifier);
+    }
+    if query.len() > base.len() {
+        queries.push(query);
+    }
+    queries
+}
+
+/// Issues and PRs listed per page of a filtered tab.
+const ITEM_PAGE_SIZE: u8 = 30;
+
+/// Which issues or PRs a tab lists by state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ItemState {
+    #[default]
+    Open,
+    Closed,
+    All,
+}
+
+impl ItemState {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Open => Self::Closed,
+            Self::Closed => Self::All,
+            Self::All => Self::Open,
+        }
+    }
+
+    fn qualifier(self) -> &'static str {
+        match self {
+            Self::Open => "is:open",
+            Self::Closed => "is:closed",
+            Self::All => "state:all",
+        }
+    }
+}
+
+/// A filter over a repo's issues or PRs, written and shown in GitHub's
+/// search syntax, e.g. `is:closed label:bug author:@me crash`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ItemFilter {
+    pub state: ItemState,
+    pub labels: Vec<String>,
+    pub author: Option<String>,
+    pub assignee: Option<String>,
+    pub milestone: Option<String>,
+    /// Free text matched against titles and bodies.
+    pub text: Option<String>,
+}
+
+impl ItemFilter {
+    pub fn parse(input: &str) -> Self {
+        let mut filter = Self::default();
+        let mut text = Vec::new();
+        for token in split_query(input) {
+            match token.split_once(':') {
+                Some(("is" | "state", "open")) => filter.state = ItemState::Open,
+                Some(("is" | "state", "closed")) => filter.state = ItemState::Closed,
+                Some(("is" | "state", "all")) => filter.state = ItemState::All,
+                Some(("label", value)) if !value.is_empty() => {
+                    filter
+                        .labels
+                        .extend(value.split(',').filter(|l| !l.is_empty()).map(String::from));
+                }
+                Some(("author", value)) if !value.is_empty() => {
+                    filter.author = Some(value.to_string())
+                }
+                Some(("assignee", value)) if !value.is_empty() => {
+                    filter.assignee = Some(value.to_string())
+                }
+                Some(("milestone", value)) if !value.is_empty() => {
+                    filter.milestone = Some(value.to_string())
+                }
+                _ => text.push(token),
+            }
+        }
+        filter.text = (!text.is_empty()).then(|| text.join(" "));
+        filter
+    }
+
+    /// The filter as typed into the filter bar; `parse` reads it back.
+    pub fn query(&self) -> String {
+        let mut parts = vec![self.state.qualifier().to_string()];
+        parts.extend(
+            self.labels
+                .iter()
+                .map(|l| format!("label:{}", quote_term(l))),
+        );
+        let qualifiers = [
+            ("author", &self.author),
+            ("assignee", &self.assignee),
+            ("milestone", &self.milestone),
+        ];
+        for (name, value) in qualifiers {
+            if let Some(value) = value {
+                parts.push(format!("{name}:{}", quote_term(value)));
+            }
+        }
+        parts.extend(self.text.clone());
+        parts.join(" ")
+    }
+
+    /// The search API query for this filter within `repo`.
+    fn search_query(&self, repo: &GitHubRepo, is_pr: bool) -> String {
+        let kind = if is_pr { "is:pr" } else { "is:issue" };
+        let query = self.query();
+        // `state:all` is our own: search lists both states by default
+        let query = query
+            .strip_prefix(ItemState::All.qualifier())
+            .unwrap_or(&query)
+            .trim();
+        format!("repo:{}/{} {kind} {query}", repo.owner, repo.name)
+            .trim_end()
+            .to_string()
+    }
+}
+
+/// Split on whitespace, keeping `"quoted phrases"` (also after a
+/// qualifier, as in `label:"good first issue"`) together, without quotes.
+fn split_query(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in input.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+fn quote_term(term: &str) -> String {
+    if term.contains(char::is_whitespace) {
+        format!("\"{term}\"")
+    } else {
+        term.to_string()
+    }
+}
+
+/// An issue or PR in a filtered list.
+#[derive(Debug, Clone)]
+pub struct ListedItem {
+    pub number: u64,
+    pub title: String,
+    pub open: bool,
+    pub author: String,
+    pub labels: Vec<String>,
+    pub comments: u32,
+    pub updated_at: String,
+}
+
+/// One page of a filtered list.
+#[derive(Debug, Clone)]
+pub struct ItemPage {
+    pub items: Vec<ListedItem>,
+    pub total: u64,
+    /// Whether another page follows.
+    pub more: bool,
+}
+
+/// An open issue or PR needing the authenticated user's attention.
+#[derive(Debug, Clone)]
+pub struct InboxItem {
+    pub reason: InboxReason,
+    pub repo: GitHubRepo,
+    pub number: u64,
+    pub title: String,
+    pub is_pr: bool,
+    /// `YYYY-MM-DD HH:MM` in UTC, so it sorts as text.
+    pub updated_at: String,
+    /// Combined checks on the head commit, for PRs the user authored.
+    pub checks: Option<CheckState>,
+    pub html_url: String,
+}
+
+/// `owner` and `name` from an API repository URL (`.../repos/{owner}/{name}`).
+fn repo_from_api_url(host: &str, url: &str) -> Option<GitHubRepo> {
+    let mut segments = url.trim_end_matches('/').rsplit('/');
+    let name = segments.next()?;
+    let owner = segments.next()?;
+    (segments.next()? == "repos").then(|| GitHubRepo {
+        host: host.to_string(),
+        owner: owner.to_string(),
+        name: name.to_string(),
+    })
+}
+
+/// An unread notification thread in one of the scanned repositories.
+#[derive(Debug, Clone)]
+pub struct NotificationThread {
+    pub id: u64,
+    /// Why the user was notified: `review_requested`, `mention`, ...
+    pub reason: String,
+    pub repo: GitHubRepo,
+    pub title: String,
+    /// Subject type: `Issue`, `PullRequest`, `Release`, ...
+    pub kind: String,
+    /// Issue or PR number, when the subject is one.
+    pub number: Option<u64>,
+    /// `YYYY-MM-DD HH:MM` in UTC.
+    pub updated_at: String,
+    pub web_url: String,
+}
+
+impl NotificationThread {
+    pub fn is_pr(&self) -> bool {
+        self.kind == "PullRequest"
+    }
+}
+
+/// Browser URL of a notification subject, from its API URL. Subjects
+/// without a page of their own (or no URL at all) link to the repository.
+fn subject_web_url(repo: &GitHubRepo, kind: &str, number: Option<u64>) -> String {
+    match (kind, number) {
+        ("PullRequest", Some(n)) => format!("{}/pull/{n}", repo.web_url()),
+        ("Issue", Some(n)) => format!("{}/issues/{n}", repo.web_url()),
+        ("Release", _) => format!("{}/releases", repo.web_url()),
+        ("Discussion", _) => format!("{}/discussions", repo.web_url()),
+        _ => repo.web_url(),
+    }
+}
+
+/// The open PR for a local branch, if any.
+#[derive(Debug, Clone)]
+pub enum BranchPr {
+    /// The branch is the repo's default branch, which PRs merge into.
+    DefaultBranch,
+    Open(Box<PrDetail>),
+    /// No open PR; `create_url` opens GitHub's compare page to create one.
+    Missing {
+        create_url: String,
+    },
+}
+
+/// A published or draft release.
+#[derive(Debug, Clone)]
+pub struct Release {
+    pub tag: String,
+    pub name: Option<String>,
+    /// Publication date, or creation date for drafts.
+    pub date: String,
+    pub draft: bool,
+    pub prerelease: bool,
+    pub assets: usize,
+    pub html_url: String,
+}
+
+impl From<octocrab::models::repos::Release> for Release {
+    fn from(r: octocrab::models::repos::Release) -> Self {
+        Self {
+            date: r
+                .published_at
+                .or(r.created_at)
+                .map(|d| d.format("%Y-%m-%d").to_string())
+                .unwrap_or_default(),
+            name: r.name.filter(|n| !n.is_empty()),
+            tag: r.tag_name,
+            draft: r.draft,
+            prerelease: r.prerelease,
+            assets: r.assets.len(),
+            html_url: r.html_url.to_string(),
+        }
+    }
+}
+
+/// A PR merged since the latest release.
+#[derive(Debug, Clone)]
+pub struct MergedPr {
+    pub number: u64,
+    pub title: String,
+    pub author: String,
+}
+
+/// What the next release would contain: everything on the default branch
+/// since the latest published release's tag.
+#[derive(Debug, Clone)]
+pub struct ReleasePreview {
+    pub since_tag: Option<String>,
+    pub branch: String,
+    pub prs: Vec<MergedPr>,
+    /// Short hash and summary, newest first.
+    pub commits: Vec<(String, String)>,
+    pub next_tag: String,
+    pub compare_url: Option<String>,
+}
+
+impl ReleasePreview {
+    /// Release notes in GitHub's generated style.
+    pub fn notes(&self) -> String {
+        let mut notes = String::from("## What's Changed\n");
+        if self.prs.is_empty() {
+            for (hash, summary) in &self.commits {
+                notes.push_str(&format!("* {summary} ({hash})\n"));
+            }
+        } else {
+            for pr in &self.prs {
+                notes.push_str(&format!(
+                    "* {} by @{} in #{}\n",
+                    pr.title, pr.author, pr.number
+                ));
+            }
+        }
+        if let Some(url) = &self.compare_url {
+            notes.push_str(&format!("\n**Full Changelog**: {url}\n"));
+        }
+        notes
+    }
+}
+
+/// Releases of a repo plus the preview of the next one.
+#[derive(Debug, Clone)]
+pub struct ReleasesInfo {
+    pub releases: Vec<Release>,
+    pub preview: ReleasePreview,
+}
+
+/// Suggest the tag after `latest` by bumping its last number, keeping any
+/// prefix (`v1.2.3` → `v1.2.4`). Without a release, start at `v0.1.0`.
+pub fn next_version_tag(latest: Option<&str>) -> String {
+    let Some(latest) = latest else {
+        return "v0.1.0".to_string();
+    };
+    let digits = latest.len() - latest.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    if digits == 0 {
+        return String::new();
+    }
+    let (prefix, number) = latest.split_at(latest.len() - digits);
+    match number.parse::<u64>() {
+        Ok(n) => format!("{prefix}{}", n + 1),
+        Err(_) => String::new(),
+    }
+}
+
+/// A GitHub Actions workflow run, as listed in the Actions tab.
+#[derive(Debug, Clone)]
+pub struct WorkflowRun {
+    pub id: u64,
+    pub workflow: String,
+    pub run_number: u64,
+    pub branch: String,
+    pub event: String,
+    pub head_sha: String,
+    pub state: CheckState,
+    /// Conclusion once completed, otherwise the status (`queued`, ...).
+    pub outcome: String,
+    /// Wall time of a completed run, in seconds.
+    pub duration: Option<u64>,
+    pub html_url: String,
+}
+
+/// The tail of a failed job's log.
+#[derive(Debug, Clone)]
+pub struct JobLog {
+    pub name: String,
+    pub failed_step: Option<String>,
+    pub lines: Vec<String>,
+    /// Earlier lines were dropped to keep [`LOG_TAIL_LINES`].
+    pub truncated: bool,
+}
+
+/// A raw log line without its leading timestamp and ANSI color codes.
+fn clean_log_line(line: &str) -> String {
+    let line = match line.split_once(' ') {
+        Some((stamp, rest)) if stamp.ends_with('Z') && stamp.contains('T') => rest,
+        _ => line,
+    };
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // Skip a CSI sequence up to its final letter
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Fork PRs are labelled `owner:branch`; same-repo ones just `branch`.
+fn pr_head(owner: &str, pr: &octocrab::models::pulls::PullRequest) -> String {
+    match &pr.head.label {
+        Some(label) if !label.starts_with(&format!("{owner}:")) => label.clone(),
+        _ => pr.head.ref_field.clone(),
+    }
+}
+
+/// Reduce a PR's reviews, oldest first, to each reviewer's latest verdict.
+/// A comment doesn't replace an earlier approval or change request.
+fn latest_reviews(reviews: &[Review]) -> Vec<(String, ReviewVerdict)> {
+    let mut latest: Vec<(String, ReviewVerdict)> = Vec::new();
+    for review in reviews {
+        let Some(user) = &review.user else { continue };
+        let verdict = match review.state {
+            Some(ReviewState::Approved) => ReviewVerdict::Approved,
+            Some(ReviewState::ChangesRequested) => ReviewVerdict::ChangesRequested,
+            Some(ReviewState::Commented) => ReviewVerdict::Commented,
+            Some(ReviewState::Dismissed) => ReviewVerdict::Dismissed,
+            _ => continue,
+        };
+        match latest.iter_mut().find(|(login, _)| *login == user.login) {
+            Some((_, existing)) => {
+                let keep = verdict == ReviewVerdict::Commented
+                    && matches!(
+                        existing,
+                        ReviewVerdict::Approved | ReviewVerdict::ChangesRequested
+                    );
+                if !keep {
+                    *existing = verdict;
+                }
+            }
+            None => latest.push((user.login.clone(), verdict)),
+        }
+    }
+    latest
+}
+
+#[derive(Deserialize)]
+struct CheckRunList {
+    check_runs: Vec<CheckRunJson>,
+}
+
+#[derive(Deserialize)]
+struct CheckRunJson {
+    name: String,
+    status: String,
+    conclusion: Option<String>,
+    html_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct WorkflowRunList {
+    workflow_runs: Vec<WorkflowRunJson>,
+}
+
+#[derive(Deserialize)]
+struct WorkflowRunJson {
+    id: u64,
+    name: Option<String>,
+    run_number: u64,
+    head_branch: Option<String>,
+    head_sha: String,
+    event: String,
+    status: Option<String>,
+    conclusion: Option<String>,
+    run_started_at: Option<chrono::DateTime<chrono::Utc>>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+    html_url: String,
+}
+
+#[derive(Deserialize)]
+struct JobList {
+    jobs: Vec<JobJson>,
+}
+
+#[derive(Deserialize)]
+struct JobJson {
+    id: u64,
+    name: String,
+    conclusion: Option<String>,
+    #[serde(default)]
+    steps: Vec<StepJson>,
+}
+
+#[derive(Deserialize)]
+struct StepJson {
+    name: String,
+    conclusion: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CompareJson {
+    html_url: Option<String>,
+    commits: Vec<CommitJson>,
+}
+
+#[derive(Deserialize)]
+struct CommitJson {
+    sha: String,
+    commit: CommitMessageJson,
+}
+
+#[derive(Deserialize)]
+struct CommitMessageJson {
+    message: String,
+}
+
+impl CommitJson {
+    fn short(&self) -> (String, String) {
+        let summary = self.commit.message.lines().next().unwrap_or("").to_string();
+        (self.sha.chars().take(7).collect(), summary)
+    }
+}
+
+#[derive(Deserialize)]
+struct CombinedStatus {
+    statuses: Vec<StatusJson>,
+}
+
+#[derive(Deserialize)]
+struct StatusJson {
+    context: String,
+    state: String,
+    target_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SearchResults {
+    total_count: u64,
+    items: Vec<octocrab::models::issues::Issue>,
+}
+
+/// `value` percent-encoded for a URL query string.
+fn query_escape(value: &str) -> String {
+    let mut escaped = String::new();
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                escaped.push(byte as char)
+            }
+            _ => escaped.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    escaped
+}
+
+/// A git ref percent-encoded for a URL path, keeping the `/` separators
+/// GitHub expects in names like `release/1.0`.
+fn ref_escape(name: &str) -> String {
+    query_escape(name).replace("%2F", "/")
+}
+
+#[derive(Clone)]
+pub struct GitHubClient {
+    client: Octocrab,
+    /// Rooted where the GraphQL endpoint lives; `None` without a token,
+    /// since GraphQL doesn't allow anonymous access.
+    graphql: Option<Octocrab>,
+    host: String,
+    /// [`target_key`] of the target, which its quota is recorded under.
+    key: String,
+    /// The host, and where its token came from when there is one.
+    label: String,
+}
+
+/// Repositories asked for in one GraphQL query.
+const GRAPHQL_BATCH: usize = 25;
+
+#[derive(Deserialize)]
+struct GraphResponse {
+    data: Option<GraphData>,
+    #[serde(default)]
+    errors: Vec<GraphError>,
+}
+
+/// The aliased repos of a batch, and the point budget the query left.
+#[derive(Deserialize)]
+struct GraphData {
+    #[serde(rename = "rateLimit")]
+    rate_limit: Option<GraphRateLimit>,
+    #[serde(flatten)]
+    repos: HashMap<String, Option<GraphRepo>>,
+}
+
+/// GraphQL's point budget, which is separate from the REST quota.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphRateLimit {
+    limit: u32,
+    remaining: u32,
+    reset_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Deserialize)]
+struct GraphError {
+    message: String,
+    #[serde(default)]
+    path: Vec<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphRepo {
+    description: Option<String>,
+    repository_topics: GraphTopics,
+    stargazer_count: u32,
+    fork_count: u32,
+    watchers: GraphCount,
+    visibility: String,
+    is_archived: bool,
+    is_disabled: bool,
+    license_info: Option<GraphLicense>,
+    pushed_at: Option<chrono::DateTime<chrono::Utc>>,
+    default_branch_ref: Option<GraphBranch>,
+    issues: GraphCount,
+    pull_requests: GraphCount,
+    recent_issues: GraphNodes,
+    recent_prs: GraphNodes,
+}
+
+#[derive(Deserialize)]
+struct GraphBranch {
+    name: String,
+    #[serde(default)]
+    target: Option<GraphCommit>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphCommit {
+    #[serde(default)]
+    status_check_rollup: Option<GraphRollup>,
+}
+
+#[derive(Deserialize)]
+struct GraphTopics {
+    nodes: Vec<GraphTopicNode>,
+}
+
+#[derive(Deserialize)]
+struct GraphTopicNode {
+    topic: GraphTopic,
+}
+
+#[derive(Deserialize)]
+struct GraphTopic {
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphLicense {
+    name: String,
+    spdx_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GraphRollup {
+    state: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GraphCount {
+    total_count: usize,
+}
+
+#[derive(Deserialize)]
+struct GraphNodes {
+    nodes: Vec<GitHubItem>,
+}
+
+impl From<GraphRepo> for GitHubData {
+    fn from(repo: GraphRepo) -> Self {
+        let ci = repo
+            .default_branch_ref
+            .as_ref()
+            .and_then(|b| b.target.as_ref()?.status_check_rollup.as_ref())
+            .map(|rollup| CheckState::from_rollup(&rollup.state));
+        let meta = RepoMeta {
+            description: repo.description.filter(|d| !d.is_empty()),
+            topics: repo
+                .repository_topics
+                .nodes
+                .into_iter()
+                .map(|n| n.topic.name)
+                .collect(),
+            stars: repo.stargazer_count,
+            forks: repo.fork_count,
+            watchers: repo.watchers.total_count as u32,
+            visibility: Some(repo.visibility.to_lowercase()),
+            archived: repo.is_archived,
+            disabled: repo.is_disabled,
+            license: repo
+                .license_info
+                .map(|l| license_label(l.spdx_id.as_deref(), &l.name)),
+            pushed_at: repo.pushed_at,
+        };
+        Self {
+            meta,
+            open_issues: repo.issues.total_count,
+            open_prs: repo.pull_requests.total_count,
+            recent_issues: repo.recent_issues.nodes,
+            recent_prs: repo.recent_prs.nodes,
+            fetched_at: chrono::Utc::now(),
+            default_branch: repo.default_branch_ref.map(|b| b.name),
+            ci,
+        }
+    }
+}
+
+impl From<&octocrab::models::Repository> for RepoMeta {
+    fn from(repo: &octocrab::models::Repository) -> Self {
+        Self {
+            description: repo.description.clone().filter(|d| !d.is_empty()),
+            topics: repo.topics.clone().unwrap_or_default(),
+            stars: repo.stargazers_count.unwrap_or(0),
+            forks: repo.forks_count.unwrap_or(0),
+            // `watchers_count` is a legacy alias of the star count
+            watchers: repo.subscribers_count.unwrap_or(0) as u32,
+            visibility: repo.visibility.clone(),
+            archived: repo.archived.unwrap_or(false),
+            disabled: repo.disabled.unwrap_or(false),
+            license: repo
+                .license
+                .as_ref()
+                .map(|l| license_label(Some(&l.spdx_id), &l.name)),
+            pushed_at: repo.pushed_at,
+        }
+    }
+}
+
+/// The SPDX identifier, unless GitHub couldn't tell the license apart.
+fn license_label(spdx_id: Option<&str>, name: &str) -> String {
+    match spdx_id {
+        Some(id) if !id.is_empty() && id != "NOASSERTION" => id.to_string(),
+        _ => name.to_string(),
+    }
+}
+
+/// The fields of `GitHubData`, in the same order as the REST listings.
+fn repo_summary_fragment() -> String {
+    let recent = format!("first: {RECENT_ITEMS}, orderBy: {{field: CREATED_AT, direction: DESC}}");
+    format!(
+        "fragment RepoSummary on Repository {{ \
+           description repositoryTopics(first: 20) {{ nodes {{ topic {{ name }} }} }} \
+           stargazerCount forkCount watchers {{ totalCount }} visibility isArchived isDisabled \
+           licenseInfo {{ name spdxId }} pushedAt \
+           defaultBranchRef {{ name target {{ ... on Commit {{ statusCheckRollup {{ state }} }} }} }} \
+           issues(states: OPEN) {{ totalCount }} \
+           pullRequests(states: OPEN) {{ totalCount }} \
+           recentIssues: issues(states: OPEN, {recent}) {{ nodes {{ number title }} }} \
+           recentPrs: pullRequests(states: OPEN, {recent}) {{ nodes {{ number title }} }} \
+         }}"
+    )
+}
+
+impl GitHubClient {
+    pub fn new(target: &HostTarget) -> color_eyre::Result<Self> {
+        let mut builder = Octocrab::builder();
+        if let Some(token) = &target.token {
+            builder = builder.personal_token(token.clone());
+        }
+        if let Some(api_url) = &target.api_url {
+            builder = builder.base_uri(api_url.as_str())?;
+        }
+        let client = builder.build()?;
+
+        let graphql = match &target.token {
+            Some(token) => {
+                let mut builder = Octocrab::builder().personal_token(token.clone());
+                if let Some(api_url) = &target.api_url {
+                    // Enterprise serves GraphQL at /api/graphql, beside the
+                    // /api/v3 REST root
+                    let api_url = api_url.trim_end_matches('/');
+                    builder = builder.base_uri(api_url.strip_suffix("/v3").unwrap_or(api_url))?;
+                }
+                Some(builder.build()?)
+            }
+            None => None,
+        };
+        let label = match &target.token_source {
+            Some(source) => format!("{} ({})", target.host, source.label()),
+            None => target.host.clone(),
+        };
+        Ok(Self {
+            client,
+            graphql,
+            host: target.host.clone(),
+            key: target_key(target),
+            label,
+        })
+    }
+
+    /// The shared client for `target`, once the host's quota allows
+    /// another request. When it is exhausted this waits for the reset
+    /// rather than failing.
+    pub async fn ready(target: &HostTarget) -> color_eyre::Result<Self> {
+        if is_offline() {
+            color_eyre::eyre::bail!("offline mode");
+        }
+        let key = target_key(target);
+        let client = {
+            let mut clients = CLIENTS
+                .lock()
+                .map_err(|_| color_eyre::eyre::eyre!("client pool poisoned"))?;
+            match clients.get(&key) {
+                Some(client) => client.clone(),
+                None => {
+                    let client = Self::new(target)?;
+                    clients.insert(key, client.clone());
+                    client
+                }
+            }
+        };
+        client.wait_for_quota().await;
+        Ok(client)
+    }
+
+    async fn wait_for_quota(&self) {
+        if rate_limit(&self.key).is_none_or(|r| r.checked.elapsed() >= RATE_LIMIT_REFRESH) {
+            self.refresh_rate_limit().await;
+        }
+        if let Some(wait) = rate_limit(&self.key).and_then(|r| r.wait()) {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    /// Hold a background request back until the reset when the target's
+    /// REST quota or GraphQL budget is down to its last tenth, so browsing
+    /// still has requests left.
+    async fn wait_for_reserve(&self) {
+        for key in [self.key.clone(), self.graphql_key()] {
+            let Some(limit) = rate_limit(&key).filter(|r| r.limit > 0) else {
+                continue;
+            };
+            if limit.remaining < limit.limit / PREFETCH_RESERVE_DIVISOR {
+                let exhausted = RateLimit {
+                    remaining: 0,
+                    ..limit
+                };
+                if let Some(wait) = exhausted.wait() {
+                    tokio::time::sleep(wait).await;
+                }
+            }
+        }
+    }
+
+    /// Where the GraphQL point budget is recorded in [`RATE_LIMITS`].
+    fn graphql_key(&self) -> String {
+        format!("{}|graphql", self.key)
+    }
+
+    async fn refresh_rate_limit(&self) {
+        let reported = match self.client._get("/rate_limit").await {
+            Ok(response) => {
+                record_rate_limit(&self.key, &self.label, response.headers());
+                response.headers().contains_key("x-ratelimit-limit")
+            }
+            Err(_) => false,
+        };
+        if !reported {
+            // Don't ask again on every request
+            set_rate_limit(
+                &self.key,
+                &self.label,
+                RateLimit {
+                    limit: 0,
+                    remaining: 0,
+                    reset: 0,
+                    checked: Instant::now(),
+                },
+            );
+        }
+    }
+
+    /// Send a request and read its response, recording the quota it
+    /// reports. A request refused because the quota is exhausted, or by a
+    /// secondary rate limit, is retried once the limit lifts instead of
+    /// failing with GitHub's 403. Errors other than `304 Not Modified`
+    /// fail.
+    async fn send<B: Serialize + ?Sized>(
+        &self,
+        method: http::Method,
+        route: &str,
+        headers: Option<http::HeaderMap>,
+        body: Option<&B>,
+    ) -> color_eyre::Result<http::Response<String>> {
+        loop {
+            self.wait_for_quota().await;
+            let response = match method {
+                http::Method::GET => self.client._get_with_headers(route, headers.clone()).await,
+                http::Method::POST => self.client._post(route, body).await,
+                http::Method::PATCH => self.client._patch(route, body).await,
+                http::Method::PUT => self.client._put(route, body).await,
+                http::Method::DELETE => self.client._delete(route, body).await,
+                _ => color_eyre::eyre::bail!("unsupported method {method}"),
+            }?;
+            record_rate_limit(&self.key, &self.label, response.headers());
+            if let Some(wait) = self.rate_limited(&response) {
+                tokio::time::sleep(wait).await;
+                continue;
+            }
+            let (status, headers) = (response.status(), response.headers().clone());
+            let text = if status == http::StatusCode::NOT_MODIFIED {
+                String::new()
+            } else {
+                let response = octocrab::map_github_error(response).await?;
+                self.client.body_to_string(response).await?
+            };
+            let mut response = http::Response::new(text);
+            *response.status_mut() = status;
+            *response.headers_mut() = headers;
+            return Ok(response);
+        }
+    }
+
+    /// How long to wait before retrying a response that was refused by a
+    /// rate limit.
+    fn rate_limited<T>(&self, response: &http::Response<T>) -> Option<Duration> {
+        if !matches!(
+            response.status(),
+            http::StatusCode::FORBIDDEN | http::StatusCode::TOO_MANY_REQUESTS
+        ) {
+            return None;
+        }
+        let retry_after = response
+            .headers()
+            .get(http::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok()?.parse().ok())
+            .map(Duration::from_secs);
+        retry_after.or_else(|| rate_limit(&self.key)?.wait())
+    }
+
+    /// GET `route`, also returning the pages a paginated listing links to.
+    /// Unless the policy is [`CachePolicy::None`], the request carries
+    /// `If-None-Match` from an earlier response, so an unchanged resource
+    /// costs no quota.
+    async fn get_body(
+        &self,
+        route: &str,
+        policy: CachePolicy,
+    ) -> color_eyre::Result<(String, Pages)> {
+        if policy == CachePolicy::None {
+            let response = self
+                .send(http::Method::GET, route, None, None::<&()>)
+                .await?;
+            let pages = Pages::from_headers(response.headers());
+            return Ok((response.into_body(), pages));
+        }
+
+        let key = format!("{}{route}", self.host);
+        let cached = ETAGS.lock().ok().and_then(|etags| etags.get(&key).cloned());
+        let mut headers = http::HeaderMap::new();
+        if let Some(etag) = cached.as_ref().and_then(|c| c.etag.parse().ok()) {
+            headers.insert(http::header::IF_NONE_MATCH, etag);
+        }
+
+        let response = self
+            .send(http::Method::GET, route, Some(headers), None::<&()>)
+            .await?;
+        if let (http::StatusCode::NOT_MODIFIED, Some(cached)) = (response.status(), cached) {
+            return Ok((cached.body, cached.pages));
+        }
+        let etag = response
+            .headers()
+            .get(http::header::ETAG)
+            .and_then(|v| v.to_str().ok())
+            .map(String::from);
+        let pages = Pages::from_headers(response.headers());
+        let body = response.into_body();
+        if let (Some(etag), Ok(mut etags)) = (etag, ETAGS.lock()) {
+            let disk = policy == CachePolicy::Disk;
+            etags.insert(
+                key,
+                EtagEntry {
+                    etag,
+                    body: body.clone(),
+                    pages,
+                    disk,
+                    stored: Instant::now(),
+                },
+            );
+            evict_etags(&mut etags);
+            if disk {
+                CACHE_DIRTY.store(true, std::sync::atomic::Ordering::Relaxed);
+            }
+        }
+        Ok((body, pages))
+    }
+
+    /// [`get_body`](Self::get_body) parsed as JSON.
+    async fn get_json<T: serde::de::DeserializeOwned>(
+        &self,
+        route: &str,
+        policy: CachePolicy,
+    ) -> color_eyre::Result<(T, Pages)> {
+        let (body, pages) = self.get_body(route, policy).await?;
+        Ok((serde_json::from_str(&body)?, pages))
+    }
+
+    /// Every item of a paginated listing, following its `next` links.
+    async fn get_all<T: serde::de::DeserializeOwned>(
+        &self,
+        route: &str,
+        policy: CachePolicy,
+    ) -> color_eyre::Result<Vec<T>> {
+        let separator = if route.contains('?') { '&' } else { '?' };
+        let mut items = Vec::new();
+        let mut page = 1;
+        loop {
+            let (batch, pages): (Vec<T>, _) = self
+                .get_json(&format!("{route}{separator}page={page}"), policy)
+                .await?;
+            items.extend(batch);
+            match pages.next_page {
+                Some(next) if next > page => page = next,
+                _ => return Ok(items),
+            }
+        }
+    }
+
+    /// One page of `/search/issues` results for `query`.
+    async fn search_issues(
+        &self,
+        query: &str,
+        sort: &str,
+        per_page: u8,
+        page: u32,
+    ) -> color_eyre::Result<SearchResults> {
+        let route = format!(
+            "/search/issues?q={}&sort={sort}&order=desc&per_page={per_page}&page={page}",
+            query_escape(query)
+        );
+        Ok(self.get_json(&route, CachePolicy::None).await?.0)
+    }
+
+    /// Send a write (POST, PATCH, PUT or DELETE) and return the body of
+    /// its successful response.
+    async fn write<B: Serialize + ?Sized>(
+        &self,
+        method: http::Method,
+        route: &str,
+        body: Option<&B>,
+    ) -> color_eyre::Result<String> {
+        Ok(self.send(method, route, None, body).await?.into_body())
+    }
+
+    /// [`write`](Self::write) with a JSON response.
+    async fn write_json<T: serde::de::DeserializeOwned, B: Serialize + ?Sized>(
+        &self,
+        method: http::Method,
+        route: &str,
+        body: &B,
+    ) -> color_eyre::Result<T> {
+        let text = self.write(method, route, Some(body)).await?;
+        Ok(serde_json::from_str(&text)?)
+    }
+
+    pub async fn fetch_repo_data(&self, owner: &str, repo: &str) -> color_eyre::Result<GitHubData> {
+        if let Some(cached) = get_cached(&self.host, owner, repo) {
+            return Ok(cached);
+        }
+
+        // The repository's open_issues_count covers issues and PRs alike
+        let base = format!("/repos/{owner}/{repo}");
+        let (metadata, _): (octocrab::models::Repository, _) =
+            self.get_json(&base, CachePolicy::Disk).await?;
+
+        // List endpoints don't report totals; with one item per page the
+        // `last` link's page number is the open PR count.
+        let (first_pr, pages): (Vec<serde::de::IgnoredAny>, _) = self
+            .get_json(
+                &format!("{base}/pulls?state=open&per_page=1"),
+                CachePolicy::Disk,
+            )
+            .await?;
+        let open_prs = pages.last_page.map_or(first_pr.len(), |n| n as usize);
+        let open_issues =
+            (metadata.open_issues_count.unwrap_or(0) as usize).saturating_sub(open_prs);
+
+        // The issues endpoint includes PRs, so over-fetch to still find
+        // RECENT_ITEMS real issues after filtering them out.
+        let (issues, _): (Vec<octocrab::models::issues::Issue>, _) = self
+            .get_json(
+                &format!("{base}/issues?state=open&per_page={ISSUE_SCAN_ITEMS}"),
+                CachePolicy::Disk,
+            )
+            .await?;
+        let (prs, _): (Vec<octocrab::models::pulls::PullRequest>, _) = self
+            .get_json(
+                &format!("{base}/pulls?state=open&per_page={RECENT_ITEMS}"),
+                CachePolicy::Disk,
+            )
+            .await?;
+
+        // Filter out PRs from the issues list (they have a pull_request field)
+        let recent_issues: Vec<GitHubItem> = issues
+            .iter()
+            .filter(|i| i.pull_request.is_none())
+            .take(RECENT_ITEMS as usize)
+            .map(|i| GitHubItem {
+                number: i.number,
+                title: i.title.clone(),
+            })
+            .collect();
+
+        let recent_prs: Vec<GitHubItem> = prs
+            .iter()
+            .take(RECENT_ITEMS as usize)
+            .map(|pr| GitHubItem {
+                number: pr.number,
+                title: pr.title.as_deref().unwrap_or("(no title)").to_string(),
+            })
+            .collect();
+
+        let data = GitHubData {
+            open_issues,
+            open_prs,
+            recent_issues,
+            recent_prs,
+            fetched_at: chrono::Utc::now(),
+            default_branch: metadata.default_branch.clone(),
+            ci: None,
+            meta: RepoMeta::from(&metadata),
+        };
+
+        set_cached(&self.host, owner, repo, &data);
+        Ok(data)
+    }
+
+    /// Fetch `GitHubData` for several repos of this host: batched into
+    /// GraphQL queries with a token, one repo at a time over REST without
+    /// one or if the GraphQL API fails. Also returns why GraphQL failed, so
+    /// the fallback isn't silent.
+    pub async fn fetch_repos_data(
+        &self,
+        repos: &[GitHubRepo],
+    ) -> (Vec<Result<GitHubData, String>>, Option<String>) {
+        let mut graphql_error = None;
+        if self.graphql.is_some() {
+            match self.fetch_repos_graphql(repos).await {
+                Ok(results) => return (results, None),
+                Err(e) => graphql_error = Some(describe_error(&e)),
+            }
+        }
+        let mut results = Vec::new();
+        for repo in repos {
+            results.push(
+                self.fetch_repo_data(&repo.owner, &repo.name)
+                    .await
+                    .map_err(|e| describe_error(&e)),
+            );
+        }
+        (results, graphql_error)
+    }
+
+    /// One aliased `repository` field per repo not cached yet, up to
+    /// GRAPHQL_BATCH per query. Repos the query couldn't resolve get their
+    /// own error; only a failed query as a whole is an `Err`.
+    async fn fetch_repos_graphql(
+        &self,
+        repos: &[GitHubRepo],
+    ) -> color_eyre::Result<Vec<Result<GitHubData, String>>> {
+        let Some(graphql) = &self.graphql else {
+            color_eyre::eyre::bail!("GraphQL needs a token");
+        };
+        let mut results: Vec<Option<Result<GitHubData, String>>> = repos
+            .iter()
+            .map(|r| get_cached(&self.host, &r.owner, &r.name).map(Ok))
+            .collect();
+        let missing: Vec<usize> = (0..repos.len()).filter(|&i| results[i].is_none()).collect();
+
+        for chunk in missing.chunks(GRAPHQL_BATCH) {
+            let mut params = Vec::new();
+            let mut fields = Vec::new();
+            let mut variables = serde_json::Map::new();
+            for (i, &idx) in chunk.iter().enumerate() {
+                params.push(format!("$o{i}: String!, $n{i}: String!"));
+                fields.push(format!(
+                    "r{i}: repository(owner: $o{i}, name: $n{i}) {{ ...RepoSummary }}"
+                ));
+                variables.insert(format!("o{i}"), repos[idx].owner.clone().into());
+                variables.insert(format!("n{i}"), repos[idx].name.clone().into());
+            }
+            let query = format!(
+                "query({}) {{ {} rateLimit {{ limit remaining resetAt }} }} {}",
+                params.join(", "),
+                fields.join(" "),
+                repo_summary_fragment()
+            );
+
+            if let Some(wait) = rate_limit(&self.graphql_key()).and_then(|r| r.wait()) {
+                tokio::time::sleep(wait).await;
+            }
+            let response: GraphResponse = graphql
+                .graphql(&serde_json::json!({ "query": query, "variables": variables }))
+                .await?;
+            let Some(mut data) = response.data else {
+                let message = response
+                    .errors
+                    .first()
+                    .map_or("no data", |e| e.message.as_str());
+                color_eyre::eyre::bail!("GraphQL query failed: {message}");
+            };
+            if let Some(budget) = data.rate_limit {
+                set_rate_limit(
+                    &self.graphql_key(),
+                    &format!("{} GraphQL", self.label),
+                    RateLimit {
+                        limit: budget.limit,
+                        remaining: budget.remaining,
+                        reset: budget.reset_at.timestamp().max(0) as u64,
+                        checked: Instant::now(),
+                    },
+                );
+            }
+
+            for (i, &idx) in chunk.iter().enumerate() {
+                let alias = format!("r{i}");
+                let repo = &repos[idx];
+                results[idx] = Some(match data.repos.remove(&alias).flatten() {
+                    Some(found) => {
+                        let found = GitHubData::from(found);
+                        set_cached(&self.host, &repo.owner, &repo.name, &found);
+                        Ok(found)
+                    }
+                    None => Err(response
+                        .errors
+                        .iter()
+                        .find(|e| e.path.first().and_then(|p| p.as_str()) == Some(alias.as_str()))
+                        .map_or_else(|| "Repository not found".to_string(), |e| e.message.clone())),
+                });
+            }
+        }
+        Ok(results
+            .into_iter()
+            .map(|r| r.unwrap_or_else(|| Err("not fetched".to_string())))
+            .collect())
+    }
+
+    /// Fetch a pull request with its reviews and the checks on its head.
+    pub async fn fetch_pr_detail(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> color_eyre::Result<PrDetail> {
+        let base = format!("/repos/{owner}/{repo}/pulls/{number}");
+        let (pr, _): (octocrab::models::pulls::PullRequest, _) =
+            self.get_json(&base, CachePolicy::None).await?;
+        let (reviews, _): (Vec<Review>, _) = self
+            .get_json(&format!("{base}/reviews?per_page=100"), CachePolicy::None)
+            .await?;
+        let checks = self.fetch_checks(owner, repo, &pr.head.sha).await;
+
+        let mut requested_reviewers: Vec<String> = pr
+            .requested_reviewers
+            .iter()
+            .flatten()
+            .map(|u| u.login.clone())
+            .collect();
+        requested_reviewers.extend(
+            pr.requested_teams
+                .iter()
+                .flatten()
+                .map(|t| format!("@{owner}/{}", t.slug)),
+        );
+
+        let head = pr_head(owner, &pr);
+
+        Ok(PrDetail {
+            number: pr.number,
+            title: pr.title.clone().unwrap_or_else(|| "(no title)".to_string()),
+            author: pr
+                .user
+                .as_ref()
+                .map(|u| u.login.clone())
+                .unwrap_or_default(),
+            open: pr.state == Some(octocrab::models::IssueState::Open),
+            draft: pr.draft.unwrap_or(false),
+            base: pr.base.ref_field.clone(),
+            head,
+            head_sha: pr.head.sha.clone(),
+            labels: pr.labels.iter().flatten().map(|l| l.name.clone()).collect(),
+            assignees: pr
+                .assignees
+                .iter()
+                .flatten()
+                .map(|u| u.login.clone())
+                .collect(),
+            requested_reviewers,
+            reviews: latest_reviews(&reviews),
+            checks,
+            mergeability: Mergeability::from_pr(pr.mergeable, pr.mergeable_state.as_ref()),
+            additions: pr.additions.unwrap_or(0),
+            deletions: pr.deletions.unwrap_or(0),
+            changed_files: pr.changed_files.unwrap_or(0),
+            body: pr.body.clone().unwrap_or_default(),
+            html_url: pr.html_url.as_ref().map(|u| u.to_string()),
+        })
+    }
+
+    /// A pull request's head as in `PrDetail::head`.
+    pub async fn fetch_pr_head(
+        &self,
+        repo: &GitHubRepo,
+        number: u64,
+    ) -> color_eyre::Result<String> {
+        let (pr, _): (octocrab::models::pulls::PullRequest, _) = self
+            .get_json(
+                &format!("/repos/{}/{}/pulls/{number}", repo.owner, repo.name),
+                CachePolicy::None,
+            )
+            .await?;
+        Ok(pr_head(&repo.owner, &pr))
+    }
+
+    /// Fetch an issue with the first page of its comments.
+    pub async fn fetch_issue_detail(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> color_eyre::Result<IssueDetail> {
+        let (issue, _): (octocrab::models::issues::Issue, _) = self
+            .get_json(
+                &format!("/repos/{owner}/{repo}/issues/{number}"),
+                CachePolicy::None,
+            )
+            .await?;
+        let (comments, next_comment_page) = if issue.comments > 0 {
+            self.fetch_issue_comments(owner, repo, number, 1).await?
+        } else {
+            (Vec::new(), None)
+        };
+
+        Ok(IssueDetail {
+            number: issue.number,
+            title: issue.title,
+            open: issue.state == octocrab::models::IssueState::Open,
+            author: issue.user.login,
+            created_at: issue.created_at.format("%Y-%m-%d").to_string(),
+            labels: issue.labels.into_iter().map(|l| l.name).collect(),
+            assignees: issue.assignees.into_iter().map(|u| u.login).collect(),
+            milestone: issue.milestone.map(|m| m.title),
+            body: issue.body.unwrap_or_default(),
+            html_url: issue.html_url.to_string(),
+            comments,
+            total_comments: issue.comments,
+            next_comment_page,
+        })
+    }
+
+    /// One page of an issue's comments, oldest first, and the number of
+    /// the page after it if there is one.
+    pub async fn fetch_issue_comments(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        page: u32,
+    ) -> color_eyre::Result<(Vec<IssueComment>, Option<u32>)> {
+        let (comments, pages): (Vec<octocrab::models::issues::Comment>, _) = self
+            .get_json(
+                &format!(
+                    "/repos/{owner}/{repo}/issues/{number}/comments\
+                 ?per_page={COMMENT_PAGE_SIZE}&page={page}"
+                ),
+                CachePolicy::None,
+            )
+            .await?;
+        let next = pages.next_page;
+        let comments = comments
+            .into_iter()
+            .map(|c| IssueComment {
+                author: c.user.login,
+                created_at: c.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                body: c.body.unwrap_or_default(),
+            })
+            .collect();
+        Ok((comments, next))
+    }
+
+    /// Label names and assignable users of a repository.
+    pub async fn fetch_issue_options(
+        &self,
+        owner: &str,
+        repo: &str,
+    ) -> color_eyre::Result<(Vec<String>, Vec<String>)> {
+        let base = format!("/repos/{owner}/{repo}");
+        let labels: Vec<octocrab::models::Label> = self
+            .get_all(&format!("{base}/labels?per_page=100"), CachePolicy::None)
+            .await?;
+        let assignees: Vec<octocrab::models::Author> = self
+            .get_all(&format!("{base}/assignees?per_page=100"), CachePolicy::None)
+            .await?;
+        Ok((
+            labels.into_iter().map(|l| l.name).collect(),
+            assignees.into_iter().map(|u| u.login).collect(),
+        ))
+    }
+
+    pub async fn create_issue(
+        &self,
+        owner: &str,
+        repo: &str,
+        issue: &NewIssue,
+    ) -> color_eyre::Result<GitHubItem> {
+        let mut fields = serde_json::json!({ "title": issue.title });
+        if !issue.body.trim().is_empty() {
+            fields["body"] = issue.body.clone().into();
+        }
+        if !issue.labels.is_empty() {
+            fields["labels"] = issue.labels.clone().into();
+        }
+        if !issue.assignees.is_empty() {
+            fields["assignees"] = issue.assignees.clone().into();
+        }
+        let created: octocrab::models::issues::Issue = self
+            .write_json(
+                http::Method::POST,
+                &format!("/repos/{owner}/{repo}/issues"),
+                &fields,
+            )
+            .await?;
+        Ok(GitHubItem {
+            number: created.number,
+            title: created.title,
+        })
+    }
+
+    pub async fn apply_item_action(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        action: &ItemAction,
+    ) -> color_eyre::Result<ActionOutcome> {
+        let route = format!("/repos/{owner}/{repo}/issues/{number}");
+        Ok(match action {
+            ItemAction::Comment(body) => {
+                let comment: octocrab::models::issues::Comment = self
+                    .write_json(
+                        http::Method::POST,
+                        &format!("{route}/comments"),
+                        &serde_json::json!({ "body": body }),
+                    )
+                    .await?;
+                ActionOutcome::Commented(IssueComment {
+                    author: comment.user.login,
+                    created_at: comment.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                    body: comment.body.unwrap_or_default(),
+                })
+            }
+            ItemAction::SetOpen(open) => {
+                let state = if *open { "open" } else { "closed" };
+                let issue: octocrab::models::issues::Issue = self
+                    .write_json(
+                        http::Method::PATCH,
+                        &route,
+                        &serde_json::json!({ "state": state }),
+                    )
+                    .await?;
+                ActionOutcome::StateChanged {
+                    open: issue.state == octocrab::models::IssueState::Open,
+                }
+            }
+            ItemAction::SetLabels(labels) => {
+                let labels: Vec<octocrab::models::Label> = self
+                    .write_json(
+                        http::Method::PUT,
+                        &format!("{route}/labels"),
+                        &serde_json::json!({ "labels": labels }),
+                    )
+                    .await?;
+                ActionOutcome::Labels(labels.into_iter().map(|l| l.name).collect())
+            }
+            ItemAction::AssignMe(assign) => {
+                let (me, _): (octocrab::models::Author, _) =
+                    self.get_json("/user", CachePolicy::None).await?;
+                let method = if *assign {
+                    http::Method::POST
+                } else {
+                    http::Method::DELETE
+                };
+                let issue: octocrab::models::issues::Issue = self
+                    .write_json(
+                        method,
+                        &format!("{route}/assignees"),
+                        &serde_json::json!({ "assignees": [me.login] }),
+                    )
+                    .await?;
+                ActionOutcome::Assignees(issue.assignees.into_iter().map(|u| u.login).collect())
+            }
+            ItemAction::Merge(request) => {
+                ActionOutcome::Merged(self.merge_pr(owner, repo, number, request).await?)
+            }
+        })
+    }
+
+    /// The merge methods `repo` allows, as offered by the merge form.
+    pub async fn fetch_merge_settings(
+        &self,
+        repo: &GitHubRepo,
+    ) -> color_eyre::Result<MergeSettings> {
+        let (metadata, _): (octocrab::models::Repository, _) = self
+            .get_json(
+                &format!("/repos/{}/{}", repo.owner, repo.name),
+                CachePolicy::Memory,
+            )
+            .await?;
+        let allowed = [
+            (MergeMethod::Merge, metadata.allow_merge_commit),
+            (MergeMethod::Squash, metadata.allow_squash_merge),
+            (MergeMethod::Rebase, metadata.allow_rebase_merge),
+        ];
+        // The settings are only shown to users who can push; offer every
+        // method then and let GitHub refuse
+        let methods = if allowed.iter().all(|(_, a)| a.is_none()) {
+            MergeMethod::ALL.to_vec()
+        } else {
+            allowed
+                .iter()
+                .filter(|(_, a)| *a == Some(true))
+                .map(|(m, _)| *m)
+                .collect()
+        };
+        Ok(MergeSettings {
+            methods,
+            delete_branch_on_merge: metadata.delete_branch_on_merge.unwrap_or(false),
+        })
+    }
+
+    /// Merge PR `number`, then delete its head branch if asked. A failed
+    /// deletion is reported in the summary rather than failing the merge.
+    async fn merge_pr(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        request: &MergeRequest,
+    ) -> color_eyre::Result<String> {
+        let mut fields = serde_json::json!({
+            "merge_method": request.method.param(),
+            "sha": request.sha,
+        });
+        if request.method.takes_message() {
+            fields["commit_title"] = request.title.clone().into();
+            fields["commit_message"] = request.message.clone().into();
+        }
+        let merged: octocrab::models::pulls::Merge = self
+            .write_json(
+                http::Method::PUT,
+                &format!("/repos/{owner}/{repo}/pulls/{number}/merge"),
+                &fields,
+            )
+            .await
+            .map_err(merge_error)?;
+        if !merged.merged {
+            color_eyre::eyre::bail!(
+                "{}",
+                merged
+                    .message
+                    .unwrap_or_else(|| "GitHub did not merge the PR".to_string())
+            );
+        }
+        let mut summary = format!("Merged #{number}");
+        if let Some(branch) = &request.delete_branch {
+            let route = format!(
+                "/repos/{owner}/{repo}/git/refs/heads/{}",
+                ref_escape(branch)
+            );
+            match self.write(http::Method::DELETE, &route, None::<&()>).await {
+                Ok(_) => summary.push_str(&format!(" and deleted {branch}")),
+                // Already gone: the repo deletes merged head branches itself
+                Err(e) if error_status(&e) == Some(422) => {
+                    summary.push_str(&format!(" and deleted {branch}"));
+                }
+                Err(e) => {
+                    let error = describe_error(&e);
+                    summary.push_str(&format!("; could not delete {branch}: {error}"));
+                }
+            }
+        }
+        Ok(summary)
+    }
+
+    /// One page (from 1) of a repo's issues or PRs matching `filter`,
+    /// newest first.
+    pub async fn fetch_item_page(
+        &self,
+        repo: &GitHubRepo,
+        is_pr: bool,
+        filter: &ItemFilter,
+        page: u32,
+    ) -> color_eyre::Result<ItemPage> {
+        let result = self
+            .search_issues(
+                &filter.search_query(repo, is_pr),
+                "created",
+                ITEM_PAGE_SIZE,
+                page,
+            )
+            .await?;
+        let total = result.total_count;
+        let items: Vec<ListedItem> = result
+            .items
+            .into_iter()
+            .map(|issue| ListedItem {
+                number: issue.number,
+                title: issue.title,
+                open: issue.state == octocrab::models::IssueState::Open,
+                author: issue.user.login,
+                labels: issue.labels.into_iter().map(|l| l.name).collect(),
+                comments: issue.comments,
+                updated_at: issue.updated_at.format("%Y-%m-%d").to_string(),
+            })
+            .collect();
+        let more = (page as u64) * (ITEM_PAGE_SIZE as u64) < total && !items.is_empty();
+        Ok(ItemPage { items, total, more })
+    }
+
+    /// Open PRs awaiting the user's review, PRs they authored and issues
+    /// assigned to them, limited to `repos`. Authored PRs get their check
+    /// status. Requires a token: searches are scoped with `@me`.
+    pub async fn fetch_inbox(&self, repos: &[GitHubRepo]) -> color_eyre::Result<Vec<InboxItem>> {
+        let mut items = Vec::new();
+        for reason in [
+            InboxReason::ReviewRequested,
+            InboxReason::Authored,
+            InboxReason::Assigned,
+        ] {
+            let mut issues = Vec::new();
+            for query in repo_scoped_queries(reason.query(), repos) {
+                issues.extend(self.search_all_issues(&query).await?);
+            }
+            for issue in issues {
+                let found = repo_from_api_url(&self.host, issue.repository_url.as_str());
+                let Some(repo) = found
+                    .and_then(|r| repos.iter().find(|l| l.eq_ignore_case(&r)))
+                    .cloned()
+                else {
+                    continue;
+                };
+                items.push(InboxItem {
+                    reason,
+                    repo,
+                    number: issue.number,
+                    title: issue.title,
+                    is_pr: issue.pull_request.is_some(),
+                    updated_at: issue.updated_at.format("%Y-%m-%d %H:%M").to_string(),
+                    checks: None,
+                    html_url: issue.html_url.to_string(),
+                });
+            }
+        }
+
+        let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(INBOX_CHECK_LOOKUPS));
+        let lookups: Vec<_> = items
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| i.reason == InboxReason::Authored)
+            .map(|(idx, item)| {
+                let client = self.clone();
+                let semaphore = semaphore.clone();
+                let (repo, number) = (item.repo.clone(), item.number);
+                let lookup = tokio::spawn(async move {
+                    let _permit = semaphore.acquire_owned().await.ok()?;
+                    client.pr_checks(&repo.owner, &repo.name, number).await
+                });
+                (idx, lookup)
+            })
+            .collect();
+        for (idx, lookup) in lookups {
+            items[idx].checks = lookup.await.ok().flatten();
+        }
+
+        items.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        Ok(items)
+    }
+
+    /// Every result of a search, following its pages as far as search goes.
+    async fn search_all_issues(
+        &self,
+        query: &str,
+    ) -> color_eyre::Result<Vec<octocrab::models::issues::Issue>> {
+        let mut issues = Vec::new();
+        for page in 1..=SEARCH_MAX_RESULTS / 100 {
+            let results = self.search_issues(query, "updated", 100, page).await?;
+            let done = results.items.len() < 100;
+            issues.extend(results.items);
+            if done || issues.len() as u64 >= results.total_count {
+                break;
+            }
+        }
+        Ok(issues)
+    }
+
+    /// The combined checks of a PR's head commit.
+    async fn pr_checks(&self, owner: &str, name: &str, number: u64) -> Option<CheckState> {
+        let route = format!("/repos/{owner}/{name}/pulls/{number}");
+        let (pr, _): (octocrab::models::pulls::PullRequest, _) =
+            self.get_json(&route, CachePolicy::None).await.ok()?;
+        let checks = self.fetch_checks(owner, name, &pr.head.sha).await;
+        CheckState::combine(checks.iter().map(|c| c.state))
+    }
+
+    /// Unread notification threads in `repos`, newest first.
+    pub async fn fetch_notifications(
+        &self,
+        repos: &[GitHubRepo],
+    ) -> color_eyre::Result<Vec<NotificationThread>> {
+        let notifications: Vec<octocrab::models::activity::Notification> = self
+            .get_all("/notifications?per_page=50", CachePolicy::Memory)
+            .await?;
+
+        let mut threads: Vec<NotificationThread> = notifications
+            .into_iter()
+            .filter(|n| n.unread)
+            .filter_map(|n| {
+                let owner = n.repository.owner.as_ref()?.login.clone();
+                let found = GitHubRepo {
+                    host: self.host.clone(),
+                    owner,
+                    name: n.repository.name.clone(),
+                };
+                let repo = repos.iter().find(|r| r.eq_ignore_case(&found))?.clone();
+                let number = n
+                    .subject
+                    .url
+                    .as_ref()
+                    .and_then(|u| u.path_segments()?.next_back()?.parse().ok())
+                    .filter(|_| matches!(n.subject.r#type.as_str(), "Issue" | "PullRequest"));
+                Some(NotificationThread {
+                    id: n.id.into_inner(),
+                    reason: n.reason,
+                    web_url: subject_web_url(&repo, &n.subject.r#type, number),
+                    repo,
+                    title: n.subject.title,
+                    kind: n.subject.r#type,
+                    number,
+                    updated_at: n.updated_at.format("%Y-%m-%d %H:%M").to_string(),
+                })
+            })
+            .collect();
+        threads.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+        Ok(threads)
+    }
+
+    /// Mark a thread as read; with `unsubscribe`, also stop notifications
+    /// for it.
+    pub async fn dismiss_notification(&self, id: u64, unsubscribe: bool) -> color_eyre::Result<()> {
+        let route = format!("/notifications/threads/{id}");
+        if unsubscribe {
+            self.write(
+                http::Method::DELETE,
+                &format!("{route}/subscription"),
+                None::<&()>,
+            )
+            .await?;
+        }
+        self.write(http::Method::PATCH, &route, None::<&()>).await?;
+        Ok(())
+    }
+
+    /// Find the open PR whose head is `head_owner:branch`. For a fork the
+    /// parent is searched first, as that is where its PRs are opened.
+    pub async fn fetch_branch_pr(
+        &self,
+        repo: &GitHubRepo,
+        head_owner: &str,
+        branch: &str,
+    ) -> color_eyre::Result<BranchPr> {
+        let (metadata, _): (octocrab::models::Repository, _) = self
+            .get_json(
+                &format!("/repos/{}/{}", repo.owner, repo.name),
+                CachePolicy::Memory,
+            )
+            .await?;
+        let default_branch = metadata
+            .default_branch
+            .clone()
+            .unwrap_or_else(|| "main".to_string());
+        if head_owner.eq_ignore_ascii_case(&repo.owner) && branch == default_branch {
+            return Ok(BranchPr::DefaultBranch);
+        }
+
+        let mut bases = Vec::new();
+        if let Some(parent) = metadata.parent.as_deref() {
+            if let Some(owner) = &parent.owner {
+                let base = parent
+                    .default_branch
+                    .clone()
+                    .unwrap_or_else(|| default_branch.clone());
+                bases.push((owner.login.clone(), parent.name.clone(), base));
+            }
+        }
+        bases.push((repo.owner.clone(), repo.name.clone(), default_branch));
+
+        for (owner, name, _) in &bases {
+            let head = query_escape(&format!("{head_owner}:{branch}"));
+            let (page, _): (Vec<octocrab::models::pulls::PullRequest>, _) = self
+                .get_json(
+                    &format!("/repos/{owner}/{name}/pulls?state=open&head={head}&per_page=1"),
+                    CachePolicy::None,
+                )
+                .await?;
+            if let Some(pr) = page.first() {
+                let detail = self.fetch_pr_detail(owner, name, pr.number).await?;
+                return Ok(BranchPr::Open(Box::new(detail)));
+            }
+        }
+
+        let (owner, name, base) = &bases[0];
+        Ok(BranchPr::Missing {
+            create_url: format!(
+                "https://{}/{owner}/{name}/compare/{base}...{head_owner}:{branch}?expand=1",
+                repo.host
+            ),
+        })
+    }
+
+    /// Recent releases and a preview of the next one: PRs merged into
+    /// the default branch and commits since the latest published release.
+    pub async fn fetch_releases(
+        &self,
+        owner: &str,
+        repo: &str,
+    ) -> color_eyre::Result<ReleasesInfo> {
+        let base = format!("/repos/{owner}/{repo}");
+        let (metadata, _): (octocrab::models::Repository, _) =
+            self.get_json(&base, CachePolicy::Memory).await?;
+        let branch = metadata
+            .default_branch
+            .unwrap_or_else(|| "main".to_string());
+        let (page, _): (Vec<octocrab::models::repos::Release>, _) = self
+            .get_json(
+                &format!("{base}/releases?per_page={RELEASES}"),
+                CachePolicy::Memory,
+            )
+            .await?;
+        let latest = page.iter().find(|r| !r.draft).cloned();
+        let releases: Vec<Release> = page.into_iter().map(Release::from).collect();
+
+        let mut query = format!("repo:{owner}/{repo} is:pr is:merged base:{branch}");
+        let (commits, compare_url) = match &latest {
+            Some(release) => {
+                if let Some(since) = release.published_at.or(release.created_at) {
+                    query.push_str(&format!(" merged:>{}", since.format("%Y-%m-%dT%H:%M:%SZ")));
+                }
+                let (tag, head) = (ref_escape(&release.tag_name), ref_escape(&branch));
+                let (compare, _): (CompareJson, _) = self
+                    .get_json(&format!("{base}/compare/{tag}...{head}"), CachePolicy::None)
+                    .await?;
+                let mut commits: Vec<(String, String)> =
+                    compare.commits.iter().map(CommitJson::short).collect();
+                // Compare lists oldest first
+                commits.reverse();
+                (commits, compare.html_url)
+            }
+            None => {
+                let (list, _): (Vec<CommitJson>, _) = self
+                    .get_json(
+                        &format!("{base}/commits?sha={}&per_page=50", query_escape(&branch)),
+                        CachePolicy::None,
+                    )
+                    .await?;
+                (list.iter().map(CommitJson::short).collect(), None)
+            }
+        };
+
+        let merged = self.search_issues(&query, "updated", 100, 1).await?;
+        let prs = merged
+            .items
+            .into_iter()
+            .map(|i| MergedPr {
+                number: i.number,
+                title: i.title,
+                author: i.user.login,
+            })
+            .collect();
+
+        let since_tag = latest.map(|r| r.tag_name);
+        Ok(ReleasesInfo {
+            releases,
+            preview: ReleasePreview {
+                next_tag: next_version_tag(since_tag.as_deref()),
+                since_tag,
+                branch,
+                prs,
+                commits,
+                compare_url,
+            },
+        })
+    }
+
+    /// Create a draft release for `tag`, which GitHub creates from the tip
+    /// of `target` when the release is published.
+    pub async fn create_draft_release(
+        &self,
+        owner: &str,
+        repo: &str,
+        tag: &str,
+        target: &str,
+        notes: &str,
+    ) -> color_eyre::Result<Release> {
+        let release: octocrab::models::repos::Release = self
+            .write_json(
+                http::Method::POST,
+                &format!("/repos/{owner}/{repo}/releases"),
+                &serde_json::json!({
+                    "tag_name": tag,
+                    "target_commitish": target,
+                    "name": tag,
+                    "body": notes,
+                    "draft": true,
+                }),
+            )
+            .await?;
+        Ok(release.into())
+    }
+
+    /// The most recent workflow runs of a repository.
+    pub async fn fetch_workflow_runs(
+        &self,
+        owner: &str,
+        repo: &str,
+    ) -> color_eyre::Result<Vec<WorkflowRun>> {
+        let (list, _): (WorkflowRunList, _) = self
+            .get_json(
+                &format!("/repos/{owner}/{repo}/actions/runs?per_page={WORKFLOW_RUNS}"),
+                CachePolicy::Memory,
+            )
+            .await?;
+        Ok(list
+            .workflow_runs
+            .into_iter()
+            .map(|run| {
+                let status = run.status.unwrap_or_else(|| "queued".to_string());
+                let completed = status == "completed";
+                WorkflowRun {
+                    id: run.id,
+                    workflow: run.name.unwrap_or_else(|| "(unnamed)".to_string()),
+                    run_number: run.run_number,
+                    branch: run.head_branch.unwrap_or_default(),
+                    event: run.event,
+                    head_sha: run.head_sha,
+                    state: CheckState::from_check_run(&status, run.conclusion.as_deref()),
+                    duration: run
+                        .run_started_at
+                        .filter(|_| completed)
+                        .map(|start| (run.updated_at - start).num_seconds().max(0) as u64),
+                    outcome: run.conclusion.filter(|_| completed).unwrap_or(status),
+                    html_url: run.html_url,
+                }
+            })
+            .collect())
+    }
+
+    /// Logs of the failed jobs in a run's latest attempt, each cut to its
+    /// last [`LOG_TAIL_LINES`] lines.
+    pub async fn fetch_failed_job_logs(
+        &self,
+        owner: &str,
+        repo: &str,
+        run_id: u64,
+    ) -> color_eyre::Result<Vec<JobLog>> {
+        let (jobs, _): (JobList, _) = self
+            .get_json(
+                &format!(
+                    "/repos/{owner}/{repo}/actions/runs/{run_id}/jobs?filter=latest&per_page=100"
+                ),
+                CachePolicy::None,
+            )
+            .await?;
+
+        let mut logs = Vec::new();
+        for job in jobs.jobs {
+            if CheckState::from_check_run("completed", job.conclusion.as_deref())
+                != CheckState::Failure
+            {
+                continue;
+            }
+            // The logs endpoint redirects to short-lived plain-text storage,
+            // so there is no ETag worth keeping
+            let route = format!("/repos/{owner}/{repo}/actions/jobs/{}/logs", job.id);
+            let text = self
+                .send(http::Method::GET, &route, None, None::<&()>)
+                .await?
+                .into_body();
+
+            let lines: Vec<&str> = text.lines().collect();
+            let start = lines.len().saturating_sub(LOG_TAIL_LINES);
+            logs.push(JobLog {
+                failed_step: job
+                    .steps
+                    .into_iter()
+                    .find(|s| {
+                        CheckState::from_check_run("completed", s.conclusion.as_deref())
+                            == CheckState::Failure
+                    })
+                    .map(|s| s.name),
+                name: job.name,
+                lines: lines[start..].iter().map(|l| clean_log_line(l)).collect(),
+                truncated: start > 0,
+            });
+        }
+        Ok(logs)
+    }
+
+    /// Re-run only the failed jobs of a workflow run.
+    pub async fn rerun_failed_jobs(
+        &self,
+        owner: &str,
+        repo: &str,
+        run_id: u64,
+    ) -> color_eyre::Result<()> {
+        let route = format!("/repos/{owner}/{repo}/actions/runs/{run_id}/rerun-failed-jobs");
+        self.write(http::Method::POST, &route, None::<&()>).await?;
+        Ok(())
+    }
+
+    /// Check runs and legacy commit statuses for a commit. Either API may be
+    /// unavailable (permissions, older Enterprise versions), so failures
+    /// just leave that half out.
+    async fn fetch_checks(&self, owner: &str, repo: &str, sha: &str) -> Vec<Check> {
+        let base = format!("/repos/{owner}/{repo}/commits/{sha}");
+        let runs: Option<CheckRunList> = self
+            .get_json(
+                &format!("{base}/check-runs?per_page=100"),
+                CachePolicy::None,
+            )
+            .await
+            .ok()
+            .map(|(runs, _)| runs);
+        let statuses: Option<CombinedStatus> = self
+            .get_json(&format!("{base}/status?per_page=100"), CachePolicy::None)
+            .await
+            .ok()
+            .map(|(statuses, _)| statuses);
+
+        let mut checks: Vec<Check> = runs
+            .into_iter()
+            .flat_map(|r| r.check_runs)
+            .map(|run| Check {
+                state: CheckState::from_check_run(&run.status, run.conclusion.as_deref()),
+                name: run.name,
+                url: run.html_url,
+            })
+            .collect();
+        checks.extend(
+            statuses
+                .into_iter()
+                .flat_map(|s| s.statuses)
+                .map(|status| Check {
+                    state: CheckState::from_status(&status.state),
+                    name: status.context,
+                    url: status.target_url,
+                }),
+        );
+        checks
+    }
+
+    /// List every repository of an organization, falling back to the user
+    /// endpoint when `owner` is not an org.
+    pub async fn list_owner_repos(&self, owner: &str) -> color_eyre::Result<Vec<RemoteRepo>> {
+        let repos: Vec<octocrab::models::Repository> = match self
+            .get_all(
+                &format!("/orgs/{owner}/repos?per_page=100"),
+                CachePolicy::None,
+            )
+            .await
+        {
+            Ok(repos) => repos,
+            Err(_) => {
+                self.get_all(
+                    &format!("/users/{owner}/repos?per_page=100"),
+                    CachePolicy::None,
+                )
+                .await?
+            }
+        };
+
+        Ok(repos
+            .into_iter()
+            .filter_map(|r| {
+                let clone_url = r.clone_url.as_ref()?.to_string();
+                Some(RemoteRepo {
+                    full_name: r
+                        .full_name
+                        .clone()
+                        .unwrap_or_else(|| format!("{owner}/{}", r.name)),
+                    name: r.name,
+                    clone_url,
+                    description: r.description,
+                    archived: r.archived.unwrap_or(false),
+                })
+            })
+            .collect())
+    }
+}
+
+/// Look for `gh` CLI and git credential tokens for `hosts` on a blocking
+/// thread, then report back so requests can use them.
+pub fn spawn_token_discovery(hosts: Vec<String>, tx: mpsc::UnboundedSender<Message>) {
+    tokio::task::spawn_blocking(move || {
+        for host in &hosts {
+            crate::auth::discovered_token(host);
+        }
+        let _ = tx.send(Message::TokensDiscovered);
+    });
+}
+
+/// List an owner's repositories in the background.
+pub fn spawn_list_owner_repos(
+    owner: String,
+    target: HostTarget,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    tokio::spawn(async move {
+        let result = match GitHubClient::ready(&target).await {
+            Ok(client) => client.list_owner_repos(&owner).await,
+            Err(e) => Err(e),
+        };
+        let _ = tx.send(Message::OwnerReposListed {
+            owner,
+            result: result.map_err(|e| describe_error(&e)),
+        });
+    });
+}
+
+/// Fetch the inbox for one host's repositories in the background, tagged
+/// with the inbox load it answers.
+pub fn spawn_inbox_fetch(
+    repos: Vec<GitHubRepo>,
+    target: HostTarget,
+    generation: u64,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    tokio::spawn(async move {
+        let result = if target.token.is_none() {
+            Err(format!("no GitHub token configured for {}", target.host))
+        } else {
+            match GitHubClient::ready(&target).await {
+                Ok(client) => client.fetch_inbox(&repos).await,
+                Err(e) => Err(e),
+            }
+            .map_err(|e| describe_error(&e))
+        };
+        let _ = tx.send(Message::InboxLoaded {
+            generation,
+            host: target.host,
+            result,
+        });
+    });
+}
+
+/// Fetch one host's unread notifications in the background.
+pub fn spawn_notifications_fetch(
+    repos: Vec<GitHubRepo>,
+    target: HostTarget,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    tokio::spawn(async move {
+        let result = match GitHubClient::ready(&target).await {
+            Ok(client) => client.fetch_notifications(&repos).await,
+            Err(e) => Err(e),
+        };
+        let _ = tx.send(Message::NotificationsLoaded {
+            host: target.host,
+            repos,
+            result: result.map_err(|e| describe_error(&e)),
+        });
+    });
+}
+
+/// Mark a notification thread read (and optionally unsubscribe) in the
+/// background.
+pub fn spawn_dismiss_notification(
+    id: u64,
+    unsubscribe: bool,
+    target: HostTarget,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    tokio::spawn(async move {
+        let result = match GitHubClient::ready(&target).await {
+            Ok(client) => client.dismiss_notification(id, unsubscribe).await,
+            Err(e) => Err(e),
+        };
+        let _ = tx.send(Message::NotificationDismissed {
+            id,
+            unsubscribe,
+            result: result.map_err(|e| describe_error(&e)),
+        });
+    });
+}
+
+/// Look up the open PR for a repo's current branch in the background.
+pub fn spawn_branch_pr_fetch(
+    path: PathBuf,
+    repo: GitHubRepo,
+    head_owner: String,
+    branch: String,
+    target: HostTarget,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    tokio::spawn(async move {
+        let result = match GitHubClient::ready(&target).await {
+            Ok(client) => client.fetch_branch_pr(&repo, &head_owner, &branch).await,
+            Err(e) => Err(e),
+        };
+        let _ = tx.send(Message::BranchPrLoaded {
+            path,
+            branch,
+            result: result.map_err(|e| describe_error(&e)),
+        });
+    });
+}
+
+/// Load a repository's releases and next-release preview in the background.
+pub fn spawn_releases_fetch(
+    path: PathBuf,
+    repo: GitHubRepo,
+    target: HostTarget,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    tokio::spawn(async move {
+        let result = match GitHubClient::ready(&target).await {
+            Ok(client) => client.fetch_releases(&repo.owner, &repo.name).await,
+            Err(e) => Err(e),
+        };
+        let _ = tx.send(Message::ReleasesLoaded {
+            path,
+            result: result.map_err(|e| describe_error(&e)).map(Box::new),
+        });
+    });
+}
+
+/// Create a draft release in the background.
+pub fn spawn_create_draft_release(
+    path: PathBuf,
+    repo: GitHubRepo,
+    tag: String,
+    branch: String,
+    notes: String,
+    target: HostTarget,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    tokio::spawn(async move {
+        let result = match GitHubClient::ready(&target).await {
+            Ok(client) => {
+                client
+                    .create_draft_release(&repo.owner, &repo.name, &tag, &branch, &notes)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        let _ = tx.send(Message::ReleaseCreated {
+            path,
+            result: result.map_err(|e| describe_error(&e)),
+        });
+    });
+}
+
+/// List a repository's recent workflow runs in the background.
+pub fn spawn_workflow_runs_fetch(
+    path: PathBuf,
+    repo: GitHubRepo,
+    target: HostTarget,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    tokio::spawn(async move {
+        let result = match GitHubClient::ready(&target).await {
+            Ok(client) => client.fetch_workflow_runs(&repo.owner, &repo.name).await,
+            Err(e) => Err(e),
+        };
+        let _ = tx.send(Message::WorkflowRunsLoaded {
+            path,
+            result: result.map_err(|e| describe_error(&e)),
+        });
+    });
+}
+
+/// Fetch the failed job logs of a run in the background.
+pub fn spawn_run_logs_fetch(
+    path: PathBuf,
+    repo: GitHubRepo,
+    run_id: u64,
+    target: HostTarget,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    tokio::spawn(async move {
+        let result = match GitHubClient::ready(&target).await {
+            Ok(client) => {
+                client
+                    .fetch_failed_job_logs(&repo.owner, &repo.name, run_id)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        let _ = tx.send(Message::RunLogsLoaded {
+            path,
+            run_id,
+            result: result.map_err(|e| describe_error(&e)),
+        });
+    });
+}
+
+/// Re-run a run's failed jobs in the background.
+pub fn spawn_rerun_failed_jobs(
+    path: PathBuf,
+    repo: GitHubRepo,
+    run_id: u64,
+    target: HostTarget,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    tokio::spawn(async move {
+        let result = match GitHubClient::ready(&target).await {
+            Ok(client) => {
+                client
+                    .rerun_failed_jobs(&repo.owner, &repo.name, run_id)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        let _ = tx.send(Message::RunRerunRequested {
+            path,
+            run_id,
+            result: result.map_err(|e| describe_error(&e)),
+        });
+    });
+}
+
+/// Fetch an issue's or PR's details in the background for the item view.
+pub fn spawn_item_detail_fetch(
+    path: PathBuf,
+    repo: GitHubRepo,
+    number: u64,
+    is_pr: bool,
+    target: HostTarget,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    tokio::spawn(async move {
+        let result = match GitHubClient::ready(&target).await {
+            Ok(client) if is_pr => client
+                .fetch_pr_detail(&repo.owner, &repo.name, number)
+                .await
+                .map(|d| ItemDetail::Pr(Box::new(d))),
+            Ok(client) => client
+                .fetch_issue_detail(&repo.owner, &repo.name, number)
+                .await
+                .map(|d| ItemDetail::Issue(Box::new(d))),
+            Err(e) => Err(e),
+        };
+        let _ = tx.send(Message::ItemDetailLoaded {
+            path,
+            number,
+            result: result.map_err(|e| describe_error(&e)),
+        });
+    });
+}
+
+/// Fetch a repo's labels and assignable users for the new issue form.
+pub fn spawn_issue_options_fetch(
+    path: PathBuf,
+    repo: GitHubRepo,
+    target: HostTarget,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    tokio::spawn(async move {
+        let result = match GitHubClient::ready(&target).await {
+            Ok(client) => client.fetch_issue_options(&repo.owner, &repo.name).await,
+            Err(e) => Err(e),
+        };
+        let _ = tx.send(Message::IssueOptionsLoaded {
+            path,
+            result: result.map_err(|e| describe_error(&e)),
+        });
+    });
+}
+
+pub fn spawn_create_issue(
+    path: PathBuf,
+    repo: GitHubRepo,
+    issue: NewIssue,
+    target: HostTarget,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    tokio::spawn(async move {
+        let result = match GitHubClient::ready(&target).await {
+            Ok(client) => client.create_issue(&repo.owner, &repo.name, &issue).await,
+            Err(e) => Err(e),
+        };
+        let _ = tx.send(Message::IssueCreated {
+            path,
+            result: result.map_err(|e| describe_error(&e)),
+        });
+    });
+}
+
+/// Apply an [`ItemAction`] in the background.
+pub fn spawn_item_action(
+    path: PathBuf,
+    repo: GitHubRepo,
+    number: u64,
+    action: ItemAction,
+    target: HostTarget,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    tokio::spawn(async move {
+        let result = match GitHubClient::ready(&target).await {
+            Ok(client) => {
+                client
+                    .apply_item_action(&repo.owner, &repo.name, number, &action)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        let _ = tx.send(Message::ItemActionDone {
+            path,
+            number,
+            result: result.map_err(|e| describe_error(&e)),
+        });
+    });
+}
+
+pub fn spawn_merge_settings_fetch(
+    path: PathBuf,
+    repo: GitHubRepo,
+    number: u64,
+    target: HostTarget,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    tokio::spawn(async move {
+        let result = match GitHubClient::ready(&target).await {
+            Ok(client) => client.fetch_merge_settings(&repo).await,
+            Err(e) => Err(e),
+        };
+        let _ = tx.send(Message::MergeSettingsLoaded {
+            path,
+            number,
+            result: result.map_err(|e| describe_error(&e)),
+        });
+    });
+}
+
+/// Fetch the next page of an issue's comment thread in the background.
+pub fn spawn_issue_comments_fetch(
+    path: PathBuf,
+    repo: GitHubRepo,
+    number: u64,
+    page: u32,
+    target: HostTarget,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    tokio::spawn(async move {
+        let result = match GitHubClient::ready(&target).await {
+            Ok(client) => {
+                client
+                    .fetch_issue_comments(&repo.owner, &repo.name, number, page)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        let _ = tx.send(Message::IssueCommentsLoaded {
+            path,
+            number,
+            result: result.map_err(|e| describe_error(&e)),
+        });
+    });
+}
+
+/// Fetch one page of a filtered issue or PR list in the background.
+pub fn spawn_item_page_fetch(
+    path: PathBuf,
+    repo: GitHubRepo,
+    is_pr: bool,
+    filter: ItemFilter,
+    page: u32,
+    target: HostTarget,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    tokio::spawn(async move {
+        let result = match GitHubClient::ready(&target).await {
+            Ok(client) => client.fetch_item_page(&repo, is_pr, &filter, page).await,
+            Err(e) => Err(e),
+        };
+        let _ = tx.send(Message::ItemPageLoaded {
+            path,
+            is_pr,
+            filter,
+            page,
+            result: result.map_err(|e| describe_error(&e)),
+        });
+    });
+}
+
+pub fn spawn_pr_head_fetch(
+    path: PathBuf,
+    repo: GitHubRepo,
+    number: u64,
+    worktree: bool,
+    target: HostTarget,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    tokio::spawn(async move {
+        let result = match GitHubClient::ready(&target).await {
+            Ok(client) => client.fetch_pr_head(&repo, number).await,
+            Err(e) => Err(e),
+        };
+        let _ = tx.send(Message::PrHeadLoaded {
+            path,
+            number,
+            worktree,
+            result: result.map_err(|e| describe_error(&e)),
+        });
+    });
+}
+
+/// Spawn a single background task to fetch GitHub data for one repo.
+/// Result is sent back via the provided channel.
+pub fn spawn_github_fetch(
+    path: PathBuf,
+    repo: GitHubRepo,
+    target: HostTarget,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    tokio::spawn(fetch_and_send(vec![(path, repo)], target, false, tx));
+}
+
+/// Fetch GitHub data for repos of one host nobody is looking at yet: a
+/// GraphQL batch (or, without a token, a single repo) per permit of
+/// `semaphore`, without eating into the quota reserved for interactive use.
+pub fn spawn_github_prefetch(
+    repos: Vec<(PathBuf, GitHubRepo)>,
+    target: HostTarget,
+    semaphore: std::sync::Arc<tokio::sync::Semaphore>,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    let batch = if target.token.is_some() {
+        GRAPHQL_BATCH
+    } else {
+        1
+    };
+    for chunk in repos.chunks(batch) {
+        let chunk = chunk.to_vec();
+        let target = target.clone();
+        let semaphore = semaphore.clone();
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let paths = chunk.iter().map(|(path, _)| path.clone()).collect();
+            let _ = tx.send(Message::GitHubPrefetchStarted(paths));
+            fetch_and_send(chunk, target, true, tx).await;
+        });
+    }
+}
+
+async fn fetch_and_send(
+    repos: Vec<(PathBuf, GitHubRepo)>,
+    target: HostTarget,
+    background: bool,
+    tx: mpsc::UnboundedSender<Message>,
+) {
+    let client = match GitHubClient::ready(&target).await {
+        Ok(c) => c,
+        Err(e) => {
+            for (path, _) in repos {
+                let _ = tx.send(Message::GitHubError {
+                    path,
+                    error: describe_error(&e),
+                });
+            }
+            return;
+        }
+    };
+    if background {
+        client.wait_for_reserve().await;
+    }
+
+    let (paths, repos): (Vec<PathBuf>, Vec<GitHubRepo>) = repos.into_iter().unzip();
+    let (results, graphql_error) = client.fetch_repos_data(&repos).await;
+    if let Some(error) = graphql_error {
+        let _ = tx.send(Message::GraphQlFailed {
+            host: target.host.clone(),
+            error,
+        });
+    }
+    for (path, result) in paths.into_iter().zip(results) {
+        let _ = tx.send(match result {
+            Ok(data) => Message::GitHubDataReceived { path, data },
+            Err(error) => Message::GitHubError { path, error },
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn mock_target(server: &MockServer) -> HostTarget {
+        HostTarget {
+            host: GITHUB_HOST.to_string(),
+            api_url: Some(server.uri()),
+            token: None,
+            token_source: None,
+        }
+    }
+
+    fn user_json(server: &str) -> Value {
+        json!({
+            "login": "octo", "id": 1, "node_id": "U_1",
+            "avatar_url": format!("{server}/a"), "gravatar_id": "",
+            "url": format!("{server}/u"), "html_url": format!("{server}/u"),
+            "followers_url": format!("{server}/u"), "following_url": format!("{server}/u"),
+            "gists_url": format!("{server}/u"), "starred_url": format!("{server}/u"),
+            "subscriptions_url": format!("{server}/u"), "organizations_url": format!("{server}/u"),
+            "repos_url": format!("{server}/u"), "events_url": format!("{server}/u"),
+            "received_events_url": format!("{server}/u"), "type": "User", "site_admin": false
+        })
+    }
+
+    fn issue_json(server: &str, number: u64, is_pr: bool) -> Value {
+        let mut issue = json!({
+            "id": number, "node_id": format!("I_{number}"), "number": number,
+            "url": format!("{server}/i/{number}"), "repository_url": format!("{server}/r"),
+            "labels_url": format!("{server}/l"), "comments_url": format!("{server}/c"),
+            "events_url": format!("{server}/e"), "html_url": format!("{server}/h/{number}"),
+            "state": "open", "title": format!("Item {number}"), "user": user_json(server),
+            "labels": [], "assignees": [], "author_association": "OWNER", "locked": false,
+            "comments": 0, "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-02T00:00:00Z"
+        });
+        if is_pr {
+            issue["pull_request"] = json!({
+                "url": format!("{server}/p/{number}"), "html_url": format!("{server}/h/{number}"),
+                "diff_url": format!("{server}/d"), "patch_url": format!("{server}/p")
+            });
+        }
+        issue
+    }
+
+    fn pull_json(server: &str, number: u64) -> Value {
+        let branch = json!({ "ref": "main", "sha": "abc123" });
+        json!({
+            "url": format!("{server}/p/{number}"), "id": number, "number": number,
+            "title": format!("PR {number}"), "head": branch, "base": branch
+        })
+    }
+
+    /// Serve a repo with `open_prs` open PRs and `open_issues_count`
+    /// covering both, like GitHub does.
+    async fn mock_repo(server: &MockServer, repo: &str, open_issues: u64, open_prs: u64) {
+        let uri = server.uri();
+        Mock::given(method("GET"))
+            .and(path(format!("/repos/octo/{repo}")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 1, "name": repo, "url": format!("{uri}/repos/octo/{repo}"),
+                "open_issues_count": open_issues + open_prs
+            })))
+            .mount(server)
+            .await;
+
+        let pulls_path = format!("/repos/octo/{repo}/pulls");
+        let first: Vec<Value> = (1..=open_prs.min(1)).map(|n| pull_json(&uri, n)).collect();
+        let mut count_response = ResponseTemplate::new(200).set_body_json(first);
+        if open_prs > 1 {
+            count_response = count_response.insert_header(
+                "link",
+                format!(
+                    "<{uri}{pulls_path}?state=open&per_page=1&page=2>; rel=\"next\", \
+                     <{uri}{pulls_path}?state=open&per_page=1&page={open_prs}>; rel=\"last\""
+                ),
+            );
+        }
+        Mock::given(method("GET"))
+            .and(path(pulls_path.clone()))
+            .and(query_param("per_page", "1"))
+            .respond_with(count_response)
+            .mount(server)
+            .await;
+
+        let recent: Vec<Value> = (1..=open_prs.min(RECENT_ITEMS as u64))
+            .map(|n| pull_json(&uri, n))
+            .collect();
+        Mock::given(method("GET"))
+            .and(path(pulls_path))
+            .and(query_param("per_page", RECENT_ITEMS.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(recent))
+            .mount(server)
+            .await;
+
+        // Issues listing interleaves PRs with real issues
+        let mut items = Vec::new();
+        for n in 1..=open_issues.max(open_prs).min(ISSUE_SCAN_ITEMS as u64 / 2) {
+            if n <= open_prs {
+                items.push(issue_json(&uri, 100 + n, true));
+            }
+            if n <= open_issues {
+                items.push(issue_json(&uri, 200 + n, false));
+            }
+        }
+        Mock::given(method("GET"))
+            .and(path(format!("/repos/octo/{repo}/issues")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(items))
+            .mount(server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_counts_exceed_page_size() {
+        let server = MockServer::start().await;
+        mock_repo(&server, "counts-large", 42, 17).await;
+
+        let client = GitHubClient::new(&mock_target(&server)).unwrap();
+        let data = client
+            .fetch_repo_data("octo", "counts-large")
+            .await
+            .unwrap();
+
+        assert_eq!(data.open_prs, 17);
+        assert_eq!(data.open_issues, 42);
+        assert_eq!(data.recent_prs.len(), RECENT_ITEMS as usize);
+        assert_eq!(data.recent_issues.len(), RECENT_ITEMS as usize);
+        assert!(data.recent_issues.iter().all(|i| i.number > 200));
+    }
+
+    #[tokio::test]
+    async fn test_conditional_requests_and_rate_limit() {
+        let server = MockServer::start().await;
+        mock_repo(&server, "etag-repo", 1, 0).await;
+        let uri = server.uri();
+        let repo_json = json!({
+            "id": 1, "name": "etag-repo", "url": format!("{uri}/repos/octo/etag-repo"),
+            "open_issues_count": 1
+        });
+        Mock::given(method("GET"))
+            .and(path("/repos/octo/etag-repo"))
+            .and(header("if-none-match", "\"v1\""))
+            .respond_with(
+                ResponseTemplate::new(304)
+                    .insert_header("x-ratelimit-limit", "5000")
+                    .insert_header("x-ratelimit-remaining", "4998")
+                    .insert_header("x-ratelimit-reset", "4102444800"),
+            )
+            .with_priority(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/octo/etag-repo"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(repo_json)
+                    .insert_header("etag", "\"v1\"")
+                    .insert_header("x-ratelimit-limit", "5000")
+                    .insert_header("x-ratelimit-remaining", "4999")
+                    .insert_header("x-ratelimit-reset", "4102444800"),
+            )
+            .with_priority(2)
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // A host of its own keeps the shared quota and ETag state isolated
+        let target = HostTarget {
+            host: "etag.example.com".to_string(),
+            ..mock_target(&server)
+        };
+        let client = GitHubClient::new(&target).unwrap();
+        let first = client.fetch_repo_data("octo", "etag-repo").await.unwrap();
+        invalidate_cached(&GitHubRepo {
+            host: target.host.clone(),
+            owner: "octo".to_string(),
+            name: "etag-repo".to_string(),
+        });
+        let second = client.fetch_repo_data("octo", "etag-repo").await.unwrap();
+
+        assert_eq!(first.open_issues, 1);
+        assert_eq!(second.open_issues, 1);
+        let quota = rate_limit(&target_key(&target)).unwrap();
+        assert_eq!((quota.remaining, quota.limit), (4998, 5000));
+        assert!(quota.wait().is_none());
+        let exhausted = RateLimit {
+            remaining: 0,
+            ..quota
+        };
+        assert!(exhausted.wait().is_some());
+    }
+
+    #[test]
+    fn test_rate_limits_are_per_target() {
+        let target = |token: &str| HostTarget {
+            host: "quota.example.com".to_string(),
+            api_url: None,
+            token: Some(token.to_string()),
+            token_source: None,
+        };
+        let (first, second) = (target_key(&target("one")), target_key(&target("two")));
+        for (key, remaining) in [(&first, "10"), (&second, "4000")] {
+            let mut headers = http::HeaderMap::new();
+            headers.insert("x-ratelimit-limit", "5000".parse().unwrap());
+            headers.insert("x-ratelimit-remaining", remaining.parse().unwrap());
+            headers.insert("x-ratelimit-reset", "0".parse().unwrap());
+            record_rate_limit(key, "quota.example.com", &headers);
+        }
+
+        assert_eq!(rate_limit(&first).unwrap().remaining, 10);
+        assert_eq!(rate_limit(&second).unwrap().remaining, 4000);
+    }
+
+    #[tokio::test]
+    async fn test_writes_wait_out_secondary_rate_limits() {
+        let server = MockServer::start().await;
+        Mock::given(method("PATCH"))
+            .and(path("/notifications/threads/21"))
+            .respond_with(
+                ResponseTemplate::new(403)
+                    .insert_header("retry-after", "0")
+                    .set_body_json(
+                        json!({ "message": "You have exceeded a secondary rate limit" }),
+                    ),
+            )
+            .up_to_n_times(1)
+            .with_priority(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/notifications/threads/21"))
+            .respond_with(
+                ResponseTemplate::new(205)
+                    .insert_header("x-ratelimit-limit", "5000")
+                    .insert_header("x-ratelimit-remaining", "4321")
+                    .insert_header("x-ratelimit-reset", "4102444800"),
+            )
+            .with_priority(2)
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let target = HostTarget {
+            host: "secondary.example.com".to_string(),
+            ..mock_target(&server)
+        };
+        let client = GitHubClient::new(&target).unwrap();
+        client.dismiss_notification(21, false).await.unwrap();
+        let quota = rate_limit(&target_key(&target)).unwrap();
+        assert_eq!(quota.remaining, 4321);
+    }
+
+    #[tokio::test]
+    async fn test_graphql_batches_repos() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": {
+                    "r0": {
+                        "description": "Batched",
+                        "repositoryTopics": { "nodes": [{ "topic": { "name": "rust" } }] },
+                        "stargazerCount": 120, "forkCount": 8, "watchers": { "totalCount": 5 },
+                        "visibility": "PUBLIC", "isArchived": true, "isDisabled": false,
+                        "licenseInfo": { "name": "MIT License", "spdxId": "MIT" },
+                        "pushedAt": "2026-10-01T12:00:00Z",
+                        "defaultBranchRef": {
+                            "name": "trunk",
+                            "target": { "statusCheckRollup": { "state": "FAILURE" } }
+                        },
+                        "issues": { "totalCount": 12 },
+                        "pullRequests": { "totalCount": 3 },
+                        "recentIssues": { "nodes": [{ "number": 40, "title": "Crash" }] },
+                        "recentPrs": { "nodes": [{ "number": 41, "title": "Fix crash" }] }
+                    },
+                    "r1": null,
+                    "rateLimit": { "limit": 5000, "remaining": 4990, "resetAt": "2026-10-01T13:00:00Z" }
+                },
+                "errors": [{
+                    "message": "Could not resolve to a Repository with the name 'octo/gone'.",
+                    "path": ["r1"]
+                }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let target = HostTarget {
+            host: "graphql.example.com".to_string(),
+            token: Some("t".to_string()),
+            ..mock_target(&server)
+        };
+        let repo = |name: &str| GitHubRepo {
+            host: target.host.clone(),
+            owner: "octo".to_string(),
+            name: name.to_string(),
+        };
+        let client = GitHubClient::new(&target).unwrap();
+        let (results, graphql_error) = client
+            .fetch_repos_data(&[repo("batched"), repo("gone")])
+            .await;
+        assert!(graphql_error.is_none());
+
+        let data = results[0].as_ref().unwrap();
+        assert_eq!((data.open_issues, data.open_prs), (12, 3));
+        assert_eq!(data.recent_issues[0].title, "Crash");
+        assert_eq!(data.recent_prs[0].number, 41);
+        assert_eq!(data.default_branch.as_deref(), Some("trunk"));
+        assert_eq!(data.ci, Some(CheckState::Failure));
+        assert_eq!(data.meta.topics, vec!["rust".to_string()]);
+        assert_eq!(
+            (data.meta.stars, data.meta.forks, data.meta.watchers),
+            (120, 8, 5)
+        );
+        assert_eq!(data.meta.visibility.as_deref(), Some("public"));
+        assert!(data.meta.archived);
+        assert_eq!(data.meta.license.as_deref(), Some("MIT"));
+        assert!(results[1].as_ref().unwrap_err().contains("octo/gone"));
+        let budget = rate_limit(&format!("{}|graphql", target_key(&target))).unwrap();
+        assert_eq!((budget.limit, budget.remaining), (5000, 4990));
+    }
+
+    #[tokio::test]
+    async fn test_graphql_failure_falls_back_to_rest() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/graphql"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": null,
+                "errors": [{ "message": "Something went wrong" }]
+            })))
+            .mount(&server)
+            .await;
+        mock_repo(&server, "fallback", 2, 1).await;
+
+        let target = HostTarget {
+            host: "fallback.example.com".to_string(),
+            token: Some("t".to_string()),
+            ..mock_target(&server)
+        };
+        let client = GitHubClient::new(&target).unwrap();
+        let (results, graphql_error) = client
+            .fetch_repos_data(&[GitHubRepo {
+                host: target.host.clone(),
+                owner: "octo".to_string(),
+                name: "fallback".to_string(),
+            }])
+            .await;
+
+        assert!(graphql_error.unwrap().contains("Something went wrong"));
+        assert_eq!(results[0].as_ref().unwrap().open_issues, 2);
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_limits_repos_in_flight() {
+        let server = MockServer::start().await;
+        let names: Vec<String> = (0..4).map(|i| format!("prefetch-{i}")).collect();
+        for name in &names {
+            // Slow enough that every permit is taken before one is released
+            Mock::given(method("GET"))
+                .and(path(format!("/repos/octo/{name}")))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(json!({
+                            "id": 1, "name": name, "open_issues_count": 0,
+                            "url": format!("{}/repos/octo/{name}", server.uri())
+                        }))
+                        .set_delay(Duration::from_millis(200)),
+                )
+                .mount(&server)
+                .await;
+            mock_repo(&server, name, 0, 0).await;
+        }
+
+        let target = HostTarget {
+            host: "prefetch.example.com".to_string(),
+            ..mock_target(&server)
+        };
+        let repos = names
+            .iter()
+            .map(|name| {
+                let repo = GitHubRepo {
+                    host: target.host.clone(),
+                    owner: "octo".to_string(),
+                    name: name.clone(),
+                };
+                (PathBuf::from(name), repo)
+            })
+            .collect();
+        let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(2));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        spawn_github_prefetch(repos, target, semaphore, tx);
+
+        let (mut in_flight, mut most, mut done) = (0, 0, 0);
+        while done < names.len() {
+            match rx.recv().await.unwrap() {
+                Message::GitHubPrefetchStarted(paths) => in_flight += paths.len(),
+                Message::GitHubDataReceived { .. } => {
+                    in_flight -= 1;
+                    done += 1;
+                }
+                Message::GitHubError { error, .. } => panic!("prefetch failed: {error}"),
+                _ => {}
+            }
+            most = most.max(in_flight);
+        }
+        assert_eq!(most, 2);
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_waits_below_the_reserve() {
+        let server = MockServer::start().await;
+        mock_repo(&server, "reserve-background", 0, 0).await;
+        mock_repo(&server, "reserve-selected", 0, 0).await;
+
+        let target = HostTarget {
+            host: "reserve.example.com".to_string(),
+            ..mock_target(&server)
+        };
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        set_rate_limit(
+            &target_key(&target),
+            &target.host,
+            RateLimit {
+                limit: 5000,
+                remaining: 100,
+                reset: now + 1,
+                checked: Instant::now(),
+            },
+        );
+        let job = |name: &str| {
+            let repo = GitHubRepo {
+                host: target.host.clone(),
+                owner: "octo".to_string(),
+                name: name.to_string(),
+            };
+            (PathBuf::from(name), repo)
+        };
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(4));
+        spawn_github_prefetch(
+            vec![job("reserve-background")],
+            target.clone(),
+            semaphore,
+            tx.clone(),
+        );
+        let (path, repo) = job("reserve-selected");
+        spawn_github_fetch(path, repo, target, tx);
+
+        // Browsing may use the reserve; the prefetch waits for the reset
+        let started = Instant::now();
+        let mut received = Vec::new();
+        while received.len() < 2 {
+            if let Message::GitHubDataReceived { path, .. } = rx.recv().await.unwrap() {
+                received.push((path, started.elapsed()));
+            }
+        }
+        assert_eq!(received[0].0, PathBuf::from("reserve-selected"));
+        assert_eq!(received[1].0, PathBuf::from("reserve-background"));
+        assert!(received[0].1 < Duration::from_millis(500));
+        assert!(received[1].1 >= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_disk_cache_is_shown_but_revalidated() {
+        let dir = std::env::temp_dir().join(format!("project-dash-cache-{}", std::process::id()));
+        let file = dir.join("github.json");
+        let repo = GitHubRepo {
+            host: "disk.example.com".to_string(),
+            owner: "octo".to_string(),
+            name: "cached".to_string(),
+        };
+        let data = GitHubData {
+            open_issues: 3,
+            open_prs: 1,
+            recent_issues: vec![GitHubItem {
+                number: 7,
+                title: "Bug".to_string(),
+            }],
+            recent_prs: Vec::new(),
+            fetched_at: chrono::Utc::now() - chrono::Duration::hours(3),
+            default_branch: Some("main".to_string()),
+            ci: None,
+            meta: RepoMeta::default(),
+        };
+        let mut disk = DiskCache::default();
+        disk.repos
+            .insert(cache_key(&repo.host, &repo.owner, &repo.name), data);
+        for (route, disk_entry) in [("/repos/octo/cached", true), ("/user", false)] {
+            disk.etags.insert(
+                format!("disk.example.com{route}"),
+                EtagEntry {
+                    etag: "\"v1\"".to_string(),
+                    body: "{}".to_string(),
+                    pages: Pages::default(),
+                    disk: disk_entry,
+                    stored: Instant::now(),
+                },
+            );
+        }
+        write_disk_cache(&file, &disk);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&file).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        fill_caches(read_disk_cache(&file));
+        let cached = cached_data(&repo).unwrap();
+        assert_eq!(cached.open_issues, 3);
+        assert_eq!(cached.recent_issues[0].title, "Bug");
+        assert!(get_cached(&repo.host, &repo.owner, &repo.name).is_none());
+        let etags = ETAGS.lock().unwrap();
+        assert!(etags.contains_key("disk.example.com/repos/octo/cached"));
+        assert!(!etags.contains_key("disk.example.com/user"));
+        drop(etags);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_evict_etags() {
+        let entry = |disk: bool, age: u64| EtagEntry {
+            etag: String::new(),
+            body: String::new(),
+            pages: Pages::default(),
+            disk,
+            stored: Instant::now() - Duration::from_secs(age),
+        };
+        let mut etags = HashMap::new();
+        etags.insert("old".to_string(), entry(false, 3600));
+        etags.insert("old-disk".to_string(), entry(true, 3600));
+        for i in 0..ETAG_MAX_ENTRIES + 5 {
+            etags.insert(format!("recent-{i}"), entry(false, i as u64));
+        }
+
+        evict_etags(&mut etags);
+        assert!(!etags.contains_key("old"));
+        assert!(etags.contains_key("old-disk"));
+        assert_eq!(etags.len(), ETAG_MAX_ENTRIES + 1);
+        assert!(etags.contains_key("recent-0"));
+        assert!(!etags.contains_key(&format!("recent-{}", ETAG_MAX_ENTRIES + 4)));
+    }
+
+    #[tokio::test]
+    async fn test_counts_single_page() {
+        let server = MockServer::start().await;
+        mock_repo(&server, "counts-small", 2, 1).await;
+
+        let client = GitHubClient::new(&mock_target(&server)).unwrap();
+        let data = client
+            .fetch_repo_data("octo", "counts-small")
+            .await
+            .unwrap();
+
+        assert_eq!(data.open_prs, 1);
+        assert_eq!(data.open_issues, 2);
+        assert_eq!(data.recent_issues.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_counts_empty_repo() {
+        let server = MockServer::start().await;
+        mock_repo(&server, "counts-empty", 0, 0).await;
+
+        let client = GitHubClient::new(&mock_target(&server)).unwrap();
+        let data = client
+            .fetch_repo_data("octo", "counts-empty")
+            .await
+            .unwrap();
+
+        assert_eq!(data.open_prs, 0);
+        assert_eq!(data.open_issues, 0);
+        assert!(data.recent_prs.is_empty());
+    }
+
+    #[test]
+    fn test_host_target_resolution() {
+        let config = crate::config::Config::parse(
+            r#"
+[github.hosts."github.mycorp.com"]
+token = "corp"
+owner_tokens = { platform = "platform" }
+"#,
+        )
+        .unwrap();
+
+        let env = Some(&("env".to_string(), TokenSource::Env));
+        let dotcom = host_target(&config.github, "github.com", Some("octo"), env);
+        assert_eq!(dotcom.api_url, None);
+        assert_eq!(dotcom.token.as_deref(), Some("env"));
+        assert_eq!(dotcom.token_source, Some(TokenSource::Env));
+
+        let corp = host_target(&config.github, "github.mycorp.com", None, env);
+        assert_eq!(
+            corp.api_url.as_deref(),
+            Some("https://github.mycorp.com/api/v3")
+        );
+        assert_eq!(corp.token.as_deref(), Some("corp"));
+        assert_eq!(corp.token_source, Some(TokenSource::Config));
+
+        // An owner's own token wins over the host's
+        let team = host_target(&config.github, "github.mycorp.com", Some("Platform"), env);
+        assert_eq!(team.token.as_deref(), Some("platform"));
+        assert_eq!(
+            team.token_source,
+            Some(TokenSource::ConfigOwner("platform".to_string()))
+        );
+
+        // The github.com token never leaks to other hosts
+        let other = host_target(&config.github, "ghe.example.com", None, env);
+        assert_eq!(other.token, None);
+    }
+
+    #[tokio::test]
+    async fn test_configured_api_url_targets_mock_server() {
+        let server = MockServer::start().await;
+        mock_repo(&server, "configured-host", 3, 2).await;
+
+        let config = crate::config::Config::parse(&format!(
+            "[github.hosts.\"github.com\"]\napi_url = \"{}\"\n",
+            server.uri()
+        ))
+        .unwrap();
+        let target = host_target(&config.github, GITHUB_HOST, None, None);
+        let client = GitHubClient::new(&target).unwrap();
+        let data = client
+            .fetch_repo_data("octo", "configured-host")
+            .await
+            .unwrap();
+
+        assert_eq!((data.open_issues, data.open_prs), (3, 2));
+    }
+
+    #[tokio::test]
+    async fn test_pr_detail_reviews_and_checks() {
+        let server = MockServer::start().await;
+        let uri = server.uri();
+        let base = "/repos/octo/pr-detail";
+
+        let mut pr = pull_json(&uri, 7);
+        pr["user"] = user_json(&uri);
+        pr["draft"] = json!(false);
+        pr["head"] = json!({ "label": "fork:feature", "ref": "feature", "sha": "deadbeef" });
+        pr["labels"] = json!([{
+            "id": 1, "node_id": "L", "url": uri, "name": "bug", "color": "f00", "default": false
+        }]);
+        pr["mergeable"] = json!(true);
+        pr["mergeable_state"] = json!("unstable");
+        pr["additions"] = json!(10);
+        pr["deletions"] = json!(2);
+        pr["body"] = json!("## Why\nBecause.");
+        Mock::given(method("GET"))
+            .and(path(format!("{base}/pulls/7")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pr))
+            .mount(&server)
+            .await;
+
+        let review = |id: u64, state: &str| {
+            json!({
+                "id": id, "node_id": "R", "html_url": format!("{uri}/r"),
+                "user": user_json(&uri), "state": state
+            })
+        };
+        Mock::given(method("GET"))
+            .and(path(format!("{base}/pulls/7/reviews")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                review(1, "CHANGES_REQUESTED"),
+                review(2, "APPROVED"),
+                review(3, "COMMENTED"),
+            ])))
+            .mount(&server)
+            .await;
+
+        Mock::given(method("GET"))
+            .and(path(format!("{base}/commits/deadbeef/check-runs")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "total_count": 2,
+                "check_runs": [
+                    { "name": "build", "status": "completed", "conclusion": "success", "html_url": null },
+                    { "name": "lint", "status": "completed", "conclusion": "failure", "html_url": null }
+                ]
+            })))
+            .mount(&server)
+            .await;
+        // No commit status endpoint: that half is skipped, not an error
+
+        let client = GitHubClient::new(&mock_target(&server)).unwrap();
+        let detail = client
+            .fetch_pr_detail("octo", "pr-detail", 7)
+            .await
+            .unwrap();
+
+        assert_eq!(detail.author, "octo");
+        assert_eq!(detail.head, "fork:feature");
+        assert_eq!(detail.labels, vec!["bug".to_string()]);
+        assert_eq!(
+            detail.reviews,
+            vec![("octo".to_string(), ReviewVerdict::Approved)]
+        );
+        assert_eq!(detail.checks.len(), 2);
+        assert_eq!(detail.check_state(), Some(CheckState::Failure));
+        assert_eq!(detail.mergeability, Mergeability::Unstable);
+        assert_eq!((detail.additions, detail.deletions), (10, 2));
+    }
+
+    #[tokio::test]
+    async fn test_issue_detail_pages_comments() {
+        let server = MockServer::start().await;
+        let uri = server.uri();
+        let base = "/repos/octo/issue-detail";
+
+        let mut issue = issue_json(&uri, 9, false);
+        issue["comments"] = json!(31);
+        issue["body"] = json!("Steps to reproduce");
+        issue["assignees"] = json!([user_json(&uri)]);
+        Mock::given(method("GET"))
+            .and(path(format!("{base}/issues/9")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(issue))
+            .mount(&server)
+            .await;
+
+        let comment = |id: u64| {
+            json!({
+                "id": id, "node_id": "C", "url": format!("{uri}/c/{id}"),
+                "html_url": format!("{uri}/c/{id}"), "body": format!("comment {id}"),
+                "author_association": "NONE", "user": user_json(&uri),
+                "created_at": "2024-01-03T10:00:00Z"
+            })
+        };
+        let comments_path = format!("{base}/issues/9/comments");
+        Mock::given(method("GET"))
+            .and(path(comments_path.clone()))
+            .and(query_param("page", "1"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json((1..=30).map(comment).collect::<Vec<_>>())
+                    .insert_header(
+                        "link",
+                        format!("<{uri}{comments_path}?per_page=30&page=2>; rel=\"next\""),
+                    ),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(comments_path))
+            .and(query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(vec![comment(31)]))
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::new(&mock_target(&server)).unwrap();
+        let detail = client
+            .fetch_issue_detail("octo", "issue-detail", 9)
+            .await
+            .unwrap();
+        assert!(detail.open);
+        assert_eq!(detail.assignees, vec!["octo".to_string()]);
+        assert_eq!(detail.comments.len(), 30);
+        assert_eq!(detail.next_comment_page, Some(2));
+
+        let (more, next) = client
+            .fetch_issue_comments("octo", "issue-detail", 9, 2)
+            .await
+            .unwrap();
+        assert_eq!(more[0].body, "comment 31");
+        assert_eq!(more[0].created_at, "2024-01-03 10:00");
+        assert_eq!(next, None);
+    }
+
+    #[test]
+    fn test_parse_issue_template() {
+        let template = parse_issue_template(
+            "bug",
+            "---\nname: Bug report\nabout: Something broke\ntitle: \"[bug] \"\nlabels: bug, triage\nassignees:\n  - octo\n---\n\n- Steps:\n",
+        );
+        assert_eq!(template.name, "Bug report");
+        assert_eq!(template.title, "[bug] ");
+        assert_eq!(template.labels, vec!["bug", "triage"]);
+        assert_eq!(template.assignees, vec!["octo"]);
+        assert_eq!(template.body, "\n- Steps:\n");
+
+        let plain = parse_issue_template("Default", "Describe the issue");
+        assert_eq!(plain.body, "Describe the issue");
+        assert!(plain.labels.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_create_issue_sends_fields() {
+        let server = MockServer::start().await;
+        let uri = server.uri();
+        let mut created = issue_json(&uri, 77, false);
+        created["title"] = json!("Crash on start");
+        Mock::given(method("POST"))
+            .and(path("/repos/octo/create-issue/issues"))
+            .and(wiremock::matchers::body_partial_json(json!({
+                "title": "Crash on start",
+                "labels": ["bug"],
+                "assignees": ["octo"]
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(created))
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::new(&mock_target(&server)).unwrap();
+        let item = client
+            .create_issue(
+                "octo",
+                "create-issue",
+                &NewIssue {
+                    title: "Crash on start".to_string(),
+                    body: String::new(),
+                    labels: vec!["bug".to_string()],
+                    assignees: vec!["octo".to_string()],
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!((item.number, item.title.as_str()), (77, "Crash on start"));
+    }
+
+    #[tokio::test]
+    async fn test_item_actions_and_error_messages() {
+        let server = MockServer::start().await;
+        let uri = server.uri();
+        let mut closed = issue_json(&uri, 5, false);
+        closed["state"] = json!("closed");
+        Mock::given(method("PATCH"))
+            .and(path("/repos/octo/item-actions/issues/5"))
+            .and(wiremock::matchers::body_partial_json(
+                json!({ "state": "closed" }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(closed))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/repos/octo/item-actions/issues/5/comments"))
+            .respond_with(ResponseTemplate::new(403).set_body_json(json!({
+                "message": "Unable to create comment because issue is locked."
+            })))
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::new(&mock_target(&server)).unwrap();
+        let outcome = client
+            .apply_item_action("octo", "item-actions", 5, &ItemAction::SetOpen(false))
+            .await
+            .unwrap();
+        assert!(matches!(
+            outcome,
+            ActionOutcome::StateChanged { open: false }
+        ));
+
+        let err = client
+            .apply_item_action(
+                "octo",
+                "item-actions",
+                5,
+                &ItemAction::Comment("hi".to_string()),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(
+            describe_error(&err),
+            "Conversation is locked: Unable to create comment because issue is locked"
+        );
+    }
+
+    fn pr_detail() -> PrDetail {
+        PrDetail {
+            number: 12,
+            title: "Add widgets".to_string(),
+            author: "octo".to_string(),
+            open: true,
+            draft: false,
+            base: "main".to_string(),
+            head: "widgets".to_string(),
+            head_sha: "abc123".to_string(),
+            labels: Vec::new(),
+            assignees: Vec::new(),
+            requested_reviewers: Vec::new(),
+            reviews: Vec::new(),
+            checks: Vec::new(),
+            mergeability: Mergeability::Clean,
+            additions: 0,
+            deletions: 0,
+            changed_files: 0,
+            body: "Adds widgets.".to_string(),
+            html_url: None,
+        }
+    }
+
+    #[test]
+    fn test_merge_blockers_and_defaults() {
+        let mut pr = pr_detail();
+        assert!(pr.merge_blockers().is_empty());
+
+        let check = |name: &str, state| Check {
+            name: name.to_string(),
+            state,
+            url: None,
+        };
+        pr.checks = vec![
+            check("ci", CheckState::Failure),
+            check("lint", CheckState::Pending),
+        ];
+        pr.reviews = vec![("hubot".to_string(), ReviewVerdict::ChangesRequested)];
+        pr.mergeability = Mergeability::Blocked;
+        assert_eq!(
+            pr.merge_blockers(),
+            [
+                "failing checks: ci",
+                "checks still running: lint",
+                "changes requested by hubot",
+                "required approving review missing",
+            ]
+        );
+        pr.mergeability = Mergeability::Conflicting;
+        pr.checks.clear();
+        pr.reviews = vec![("hubot".to_string(), ReviewVerdict::Approved)];
+        assert_eq!(pr.merge_blockers(), ["conflicts with main"]);
+
+        let pr = pr_detail();
+        assert_eq!(
+            MergeMethod::Merge.default_commit(&pr, "octo"),
+            (
+                "Merge pull request #12 from octo/widgets".to_string(),
+                "Add widgets".to_string()
+            )
+        );
+        assert_eq!(
+            MergeMethod::Squash.default_commit(&pr, "octo"),
+            ("Add widgets (#12)".to_string(), "Adds widgets.".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_merge_pr() {
+        let server = MockServer::start().await;
+        let uri = server.uri();
+        let repo = GitHubRepo {
+            host: GITHUB_HOST.to_string(),
+            owner: "octo".to_string(),
+            name: "merging".to_string(),
+        };
+        Mock::given(method("GET"))
+            .and(path("/repos/octo/merging"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 1, "name": "merging", "url": format!("{uri}/repos/octo/merging"),
+                "allow_merge_commit": false, "allow_squash_merge": true, "allow_rebase_merge": true,
+                "delete_branch_on_merge": true
+            })))
+            .mount(&server)
+            .await;
+        let client = GitHubClient::new(&mock_target(&server)).unwrap();
+        let settings = client.fetch_merge_settings(&repo).await.unwrap();
+        assert_eq!(settings.methods, [MergeMethod::Squash, MergeMethod::Rebase]);
+        assert!(settings.delete_branch_on_merge);
+
+        let merge = |status: u16, body: Value| {
+            Mock::given(method("PUT"))
+                .and(path("/repos/octo/merging/pulls/12/merge"))
+                .respond_with(ResponseTemplate::new(status).set_body_json(body))
+        };
+        let request = MergeRequest {
+            method: MergeMethod::Squash,
+            title: "Add widgets (#12)".to_string(),
+            message: String::new(),
+            sha: "abc123".to_string(),
+            delete_branch: Some("widgets".to_string()),
+        };
+        let action = ItemAction::Merge(request);
+
+        let refusal = "At least 1 approving review is required by reviewers with write access.";
+        merge(405, json!({ "message": refusal }))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        let err = client
+            .apply_item_action("octo", "merging", 12, &action)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            describe_error(&err),
+            "Merge blocked: At least 1 approving review is required by reviewers with write access"
+        );
+
+        Mock::given(method("PUT"))
+            .and(path("/repos/octo/merging/pulls/12/merge"))
+            .and(wiremock::matchers::body_partial_json(json!({
+                "merge_method": "squash", "sha": "abc123", "commit_title": "Add widgets (#12)"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "sha": "def456", "merged": true, "message": "Pull Request successfully merged"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/repos/octo/merging/git/refs/heads/widgets"))
+            .respond_with(
+                ResponseTemplate::new(403)
+                    .set_body_json(json!({ "message": "Resource not accessible" })),
+            )
+            .mount(&server)
+            .await;
+        match client
+            .apply_item_action("octo", "merging", 12, &action)
+            .await
+            .unwrap()
+        {
+            ActionOutcome::Merged(summary) => assert_eq!(
+                summary,
+                "Merged #12; could not delete widgets: Permission denied: Resource not accessible"
+            ),
+            other => panic!("expected a merge, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_inbox_filters_to_local_repos_and_sorts() {
+        let server = MockServer::start().await;
+        let uri = server.uri();
+        let item = |number: u64, is_pr: bool, repo: &str, updated: &str| {
+            let mut item = issue_json(&uri, number, is_pr);
+            item["repository_url"] = json!(format!("{uri}/repos/{repo}"));
+            item["updated_at"] = json!(updated);
+            item
+        };
+        let search = |query: &str, items: Vec<Value>| {
+            Mock::given(method("GET"))
+                .and(path("/search/issues"))
+                .and(query_param("q", query))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "total_count": items.len(), "incomplete_results": false, "items": items
+                })))
+        };
+        let scoped =
+            |reason: InboxReason| format!("{} repo:octo/app repo:octo/lib", reason.query());
+        search(
+            &scoped(InboxReason::ReviewRequested),
+            vec![
+                item(1, true, "octo/app", "2024-03-01T00:00:00Z"),
+                item(2, true, "other/x", "2024-03-05T00:00:00Z"),
+            ],
+        )
+        .mount(&server)
+        .await;
+        search(
+            &scoped(InboxReason::Authored),
+            vec![item(3, true, "Octo/App", "2024-03-03T00:00:00Z")],
+        )
+        .mount(&server)
+        .await;
+        search(
+            &scoped(InboxReason::Assigned),
+            vec![item(4, false, "octo/lib", "2024-02-01T00:00:00Z")],
+        )
+        .mount(&server)
+        .await;
+
+        Mock::given(method("GET"))
+            .and(path("/repos/octo/app/pulls/3"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pull_json(&uri, 3)))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/octo/app/commits/abc123/check-runs"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "total_count": 1,
+                "check_runs": [{ "name": "ci", "status": "in_progress", "conclusion": null, "html_url": null }]
+            })))
+            .mount(&server)
+            .await;
+
+        let local = |name: &str| GitHubRepo {
+            host: GITHUB_HOST.to_string(),
+            owner: "octo".to_string(),
+            name: name.to_string(),
+        };
+        let client = GitHubClient::new(&mock_target(&server)).unwrap();
+        let items = client
+            .fetch_inbox(&[local("app"), local("lib")])
+            .await
+            .unwrap();
+
+        let summary: Vec<(u64, InboxReason, Option<CheckState>)> = items
+            .iter()
+            .map(|i| (i.number, i.reason, i.checks))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (3, InboxReason::Authored, Some(CheckState::Pending)),
+                (1, InboxReason::ReviewRequested, None),
+                (4, InboxReason::Assigned, None),
+            ]
+        );
+        assert_eq!(items[0].repo, local("app"));
+        assert!(!items[2].is_pr);
+    }
+
+    #[test]
+    fn test_repo_scoped_queries() {
+        let repos: Vec<GitHubRepo> = (0..40)
+            .map(|i| GitHubRepo {
+                host: GITHUB_HOST.to_string(),
+                owner: "octo".to_string(),
+                name: format!("repo-{i:02}"),
+            })
+            .collect();
+        let base = InboxReason::Authored.query();
+        let queries = repo_scoped_queries(base, &repos);
+
+        assert!(queries.len() > 1);
+        assert!(queries.iter().all(|q| q.len() <= SEARCH_QUERY_LIMIT));
+        assert!(queries.iter().all(|q| q.starts_with(base)));
+        let scoped = queries
+            .iter()
+            .flat_map(|q| q.split(' '))
+            .filter(|t| t.starts_with("repo:"));
+        assert_eq!(scoped.count(), repos.len());
+        assert!(repo_scoped_queries(base, &[]).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_notifications_list_and_dismiss() {
+        let server = MockServer::start().await;
+        let uri = server.uri();
+        let thread = |id: &str, repo: &str, kind: &str, subject: &str, unread: bool| {
+            json!({
+                "id": id, "unread": unread, "reason": "review_requested",
+                "updated_at": "2024-03-01T12:00:00Z", "last_read_at": null,
+                "url": format!("{uri}/notifications/threads/{id}"),
+                "subject": {
+                    "title": format!("Subject {id}"), "type": kind,
+                    "url": format!("{uri}/repos/octo/{repo}/{subject}"), "latest_comment_url": null
+                },
+                "repository": {
+                    "id": 1, "name": repo, "url": format!("{uri}/repos/octo/{repo}"),
+                    "owner": user_json(&uri)
+                }
+            })
+        };
+        Mock::given(method("GET"))
+            .and(path("/notifications"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                thread("11", "app", "PullRequest", "pulls/9", true),
+                thread("12", "elsewhere", "Issue", "issues/1", true),
+                thread("13", "app", "Release", "releases/5", true),
+                thread("14", "app", "Issue", "issues/2", false),
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/notifications/threads/11/subscription"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/notifications/threads/11"))
+            .respond_with(ResponseTemplate::new(205))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let app = GitHubRepo {
+            host: GITHUB_HOST.to_string(),
+            owner: "octo".to_string(),
+            name: "app".to_string(),
+        };
+        let client = GitHubClient::new(&mock_target(&server)).unwrap();
+        let threads = client
+            .fetch_notifications(std::slice::from_ref(&app))
+            .await
+            .unwrap();
+
+        let summary: Vec<(u64, Option<u64>, &str)> = threads
+            .iter()
+            .map(|t| (t.id, t.number, t.web_url.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (11, Some(9), "https://github.com/octo/app/pull/9"),
+                (13, None, "https://github.com/octo/app/releases"),
+            ]
+        );
+        assert!(threads[0].is_pr());
+
+        client.dismiss_notification(11, true).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_workflow_runs_logs_and_rerun() {
+        let server = MockServer::start().await;
+        let uri = server.uri();
+        let base = "/repos/octo/ci";
+
+        Mock::given(method("GET"))
+            .and(path(format!("{base}/actions/runs")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "total_count": 2,
+                "workflow_runs": [
+                    {
+                        "id": 30, "name": "CI", "run_number": 12, "head_branch": "main",
+                        "head_sha": "abc1234def", "event": "push", "status": "completed",
+                        "conclusion": "failure", "run_started_at": "2024-03-01T10:00:00Z",
+                        "updated_at": "2024-03-01T10:03:05Z", "html_url": format!("{uri}/runs/30")
+                    },
+                    {
+                        "id": 31, "name": "Docs", "run_number": 4, "head_branch": "main",
+                        "head_sha": "abc1234def", "event": "push", "status": "in_progress",
+                        "conclusion": null, "run_started_at": "2024-03-01T10:00:00Z",
+                        "updated_at": "2024-03-01T10:01:00Z", "html_url": format!("{uri}/runs/31")
+                    }
+                ]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("{base}/actions/runs/30/jobs")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "total_count": 2,
+                "jobs": [
+                    { "id": 1, "name": "build", "conclusion": "success", "steps": [] },
+                    { "id": 2, "name": "test", "conclusion": "failure", "steps": [
+                        { "name": "Checkout", "conclusion": "success" },
+                        { "name": "Run tests", "conclusion": "failure" }
+                    ] }
+                ]
+            })))
+            .mount(&server)
+            .await;
+        // Job logs redirect to storage elsewhere
+        Mock::given(method("GET"))
+            .and(path(format!("{base}/actions/jobs/2/logs")))
+            .respond_with(
+                ResponseTemplate::new(302).insert_header("location", format!("{uri}/blob/2")),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/blob/2"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "2024-03-01T10:02:00.1234567Z running 3 tests\n2024-03-01T10:03:00.0000000Z \u{1b}[31mtest foo ... FAILED\u{1b}[0m\n",
+            ))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(format!("{base}/actions/runs/30/rerun-failed-jobs")))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::new(&mock_target(&server)).unwrap();
+        let runs = client.fetch_workflow_runs("octo", "ci").await.unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(
+            (runs[0].state, runs[0].outcome.as_str(), runs[0].duration),
+            (CheckState::Failure, "failure", Some(185))
+        );
+        assert_eq!(
+            (runs[1].state, runs[1].outcome.as_str(), runs[1].duration),
+            (CheckState::Pending, "in_progress", None)
+        );
+
+        let logs = client
+            .fetch_failed_job_logs("octo", "ci", 30)
+            .await
+            .unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].name, "test");
+        assert_eq!(logs[0].failed_step.as_deref(), Some("Run tests"));
+        assert_eq!(
+            logs[0].lines,
+            vec!["running 3 tests", "test foo ... FAILED"]
+        );
+        assert!(!logs[0].truncated);
+
+        client.rerun_failed_jobs("octo", "ci", 30).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_branch_pr_searches_fork_parent() {
+        let server = MockServer::start().await;
+        let uri = server.uri();
+        Mock::given(method("GET"))
+            .and(path("/repos/me/app"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 2, "name": "app", "url": format!("{uri}/repos/me/app"),
+                "default_branch": "main", "fork": true,
+                "parent": {
+                    "id": 1, "name": "app", "url": format!("{uri}/repos/octo/app"),
+                    "owner": user_json(&uri), "default_branch": "trunk"
+                }
+            })))
+            .mount(&server)
+            .await;
+        let list = |repo: &str, items: Vec<Value>| {
+            Mock::given(method("GET"))
+                .and(path(format!("/repos/{repo}/pulls")))
+                .and(query_param("head", "me:feature"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(items))
+        };
+        list("octo/app", vec![]).mount(&server).await;
+        list("me/app", vec![]).mount(&server).await;
+
+        let fork = GitHubRepo {
+            host: GITHUB_HOST.to_string(),
+            owner: "me".to_string(),
+            name: "app".to_string(),
+        };
+        let client = GitHubClient::new(&mock_target(&server)).unwrap();
+
+        match client.fetch_branch_pr(&fork, "me", "main").await.unwrap() {
+            BranchPr::DefaultBranch => {}
+            other => panic!("expected default branch, got {other:?}"),
+        }
+        match client
+            .fetch_branch_pr(&fork, "me", "feature")
+            .await
+            .unwrap()
+        {
+            BranchPr::Missing { create_url } => assert_eq!(
+                create_url,
+                "https://github.com/octo/app/compare/trunk...me:feature?expand=1"
+            ),
+            other => panic!("expected no PR, got {other:?}"),
+        }
+
+        // Once opened against the parent, it is found there
+        server.reset().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/me/app"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 2, "name": "app", "url": format!("{uri}/repos/me/app"), "default_branch": "main",
+                "parent": { "id": 1, "name": "app", "url": format!("{uri}/repos/octo/app"), "owner": user_json(&uri) }
+            })))
+            .mount(&server)
+            .await;
+        let mut pr = pull_json(&uri, 21);
+        pr["state"] = json!("open");
+        list("octo/app", vec![pr.clone()]).mount(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/repos/octo/app/pulls/21"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(pr))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/octo/app/pulls/21/reviews"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .mount(&server)
+            .await;
+
+        match client
+            .fetch_branch_pr(&fork, "me", "feature")
+            .await
+            .unwrap()
+        {
+            BranchPr::Open(detail) => assert_eq!((detail.number, detail.open), (21, true)),
+            other => panic!("expected open PR, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_item_filter_round_trip() {
+        let filter = ItemFilter::parse(
+            r#"is:closed label:bug label:"good first issue" author:octo crash on start"#,
+        );
+        assert_eq!(filter.state, ItemState::Closed);
+        assert_eq!(filter.labels, ["bug", "good first issue"]);
+        assert_eq!(filter.author.as_deref(), Some("octo"));
+        assert_eq!(filter.text.as_deref(), Some("crash on start"));
+        assert_eq!(ItemFilter::parse(&filter.query()), filter);
+
+        let repo = GitHubRepo {
+            host: GITHUB_HOST.to_string(),
+            owner: "octo".to_string(),
+            name: "app".to_string(),
+        };
+        assert_eq!(
+            ItemFilter::parse("").search_query(&repo, false),
+            "repo:octo/app is:issue is:open"
+        );
+        assert_eq!(
+            ItemFilter::parse("state:all milestone:v2").search_query(&repo, true),
+            "repo:octo/app is:pr milestone:v2"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_item_pages() {
+        let server = MockServer::start().await;
+        let uri = server.uri();
+        let repo = GitHubRepo {
+            host: GITHUB_HOST.to_string(),
+            owner: "octo".to_string(),
+            name: "app".to_string(),
+        };
+        let filter = ItemFilter::parse("label:bug");
+        let page = |page: u32, numbers: std::ops::Range<u64>| {
+            let items: Vec<Value> = numbers
+                .map(|n| {
+                    let mut item = issue_json(&uri, n, false);
+                    item["labels"] = json!([{
+                        "id": 1, "node_id": "L_1", "url": format!("{uri}/l/bug"), "name": "bug",
+                        "color": "d73a4a", "default": true
+                    }]);
+                    item
+                })
+                .collect();
+            Mock::given(method("GET"))
+                .and(path("/search/issues"))
+                .and(query_param("q", "repo:octo/app is:issue is:open label:bug"))
+                .and(query_param("page", page.to_string()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "total_count": 35, "incomplete_results": false, "items": items
+                })))
+        };
+        page(1, 1..31).mount(&server).await;
+        page(2, 31..36).mount(&server).await;
+        let client = GitHubClient::new(&mock_target(&server)).unwrap();
+
+        let first = client
+            .fetch_item_page(&repo, false, &filter, 1)
+            .await
+            .unwrap();
+        assert_eq!((first.items.len(), first.total, first.more), (30, 35, true));
+        assert_eq!(first.items[0].labels, ["bug"]);
+        assert_eq!(first.items[0].updated_at, "2024-01-02");
+        let second = client
+            .fetch_item_page(&repo, false, &filter, 2)
+            .await
+            .unwrap();
+        assert_eq!((second.items.len(), second.more), (5, false));
+    }
+
+    #[test]
+    fn test_next_version_tag() {
+        assert_eq!(next_version_tag(Some("v1.2.3")), "v1.2.4");
+        assert_eq!(next_version_tag(Some("release-9")), "release-10");
+        assert_eq!(next_version_tag(Some("stable")), "");
+        assert_eq!(next_version_tag(None), "v0.1.0");
+    }
+
+    #[test]
+    fn test_ref_escape() {
+        assert_eq!(ref_escape("release/1.0"), "release/1.0");
+        assert_eq!(ref_escape("v1.0+build"), "v1.0%2Bbuild");
+        assert_eq!(ref_escape("fix#12?%"), "fix%2312%3F%25");
+        assert_eq!(query_escape("me:fix/a b"), "me%3Afix%2Fa%20b");
+    }
+
+    #[tokio::test]
+    async fn test_releases_preview_and_draft() {
+        let server = MockServer::start().await;
+        let uri = server.uri();
+        let base = "/repos/octo/rel";
+        let release = |id: u64, tag: &str, draft: bool, assets: usize| {
+            let asset = json!({
+                "url": format!("{uri}/a"), "browser_download_url": format!("{uri}/a"), "id": 1,
+                "node_id": "A", "name": "bin.tar.gz", "label": null, "state": "uploaded",
+                "content_type": "application/gzip", "size": 1, "download_count": 0,
+                "created_at": "2024-01-01T00:00:00Z", "updated_at": "2024-01-01T00:00:00Z", "uploader": null
+            });
+            json!({
+                "url": format!("{uri}/r/{id}"), "html_url": format!("{uri}/h/{id}"),
+                "assets_url": format!("{uri}/r/{id}/assets"), "upload_url": format!("{uri}/u"),
+                "id": id, "node_id": format!("RE_{id}"), "tag_name": tag, "target_commitish": "main",
+                "name": tag, "body": "", "draft": draft, "prerelease": false,
+                "created_at": "2024-02-01T00:00:00Z",
+                "published_at": if draft { Value::Null } else { json!("2024-02-01T00:00:00Z") },
+                "author": null, "assets": vec![asset; assets]
+            })
+        };
+
+        Mock::given(method("GET"))
+            .and(path(base))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 1, "name": "rel", "url": format!("{uri}{base}"), "default_branch": "main"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("{base}/releases")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                release(3, "v1.3.0", true, 0),
+                release(2, "v1.2.0", false, 2),
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("{base}/compare/v1.2.0...main")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "html_url": "https://github.com/octo/rel/compare/v1.2.0...main",
+                "commits": [
+                    { "sha": "1111111aaaa", "commit": { "message": "Add feature\n\nDetails" } },
+                    { "sha": "2222222bbbb", "commit": { "message": "Merge pull request #8" } }
+                ]
+            })))
+            .mount(&server)
+            .await;
+        let mut merged = issue_json(&uri, 8, true);
+        merged["title"] = json!("Add feature");
+        Mock::given(method("GET"))
+            .and(path("/search/issues"))
+            .and(query_param(
+                "q",
+                "repo:octo/rel is:pr is:merged base:main merged:>2024-02-01T00:00:00Z",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "total_count": 1, "incomplete_results": false, "items": [merged]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(format!("{base}/releases")))
+            .and(wiremock::matchers::body_partial_json(json!({
+                "tag_name": "v1.2.1", "target_commitish": "main", "draft": true
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(release(4, "v1.2.1", true, 0)))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::new(&mock_target(&server)).unwrap();
+        let info = client.fetch_releases("octo", "rel").await.unwrap();
+
+        assert_eq!(info.releases.len(), 2);
+        assert!(info.releases[0].draft);
+        assert_eq!(
+            (info.releases[1].assets, info.releases[1].date.as_str()),
+            (2, "2024-02-01")
+        );
+
+        let preview = &info.preview;
+        assert_eq!(preview.since_tag.as_deref(), Some("v1.2.0"));
+        assert_eq!(preview.next_tag, "v1.2.1");
+        assert_eq!(
+            preview.commits[0],
+            ("2222222".to_string(), "Merge pull request #8".to_string())
+        );
+        assert_eq!(
+            preview.notes(),
+            "## What's Changed\n* Add feature by @octo in #8\n\n\
+             **Full Changelog**: https://github.com/octo/rel/compare/v1.2.0...main\n"
+        );
+
+        let created = client
+            .create_draft_release("octo", "rel", "v1.2.1", "main", &preview.notes())
+            .await
+            .unwrap();
+        assert_eq!(created.tag, "v1.2.1");
+    }
 }
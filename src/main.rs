@@ -1,16 +1,24 @@
+mod actions;
 mod app;
+mod auth;
+mod config;
+mod git;
 mod github;
+mod markdown;
 mod repo;
 mod ui;
 
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use clap::Parser;
-use crossterm::event::{self, Event, KeyCode, KeyEventKind, MouseButton, MouseEventKind};
+use crossterm::event::{self, Event, KeyEventKind, MouseButton, MouseEventKind};
 use tokio::sync::mpsc;
 
+use actions::ExternalCommand;
 use app::{App, Message};
+use config::Config;
 
 #[derive(Parser)]
 #[command(name = "project-dash", about = "Git repository dashboard")]
@@ -22,6 +30,14 @@ struct Cli {
     /// GitHub personal access token (or set GITHUB_TOKEN env var)
     #[arg(long = "github-token", env = "GITHUB_TOKEN")]
     github_token: Option<String>,
+
+    /// Config file (defaults to ~/.config/project-dash/config.toml)
+    #[arg(long)]
+    config: Option<PathBuf>,
+
+    /// Show only GitHub data cached on disk, without calling the API
+    #[arg(long)]
+    offline: bool,
 }
 
 #[tokio::main]
@@ -29,20 +45,35 @@ async fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
 
     let cli = Cli::parse();
+    let mut config = Config::load(cli.config.as_deref())?;
+    config.github.offline |= cli.offline;
+    github::set_offline(config.github.offline);
+    if let Some(path) = config::default_cache_path() {
+        github::load_disk_cache(path);
+    }
     let scan_path = cli.path.canonicalize().unwrap_or(cli.path);
 
     let (tx, mut rx) = mpsc::unbounded_channel::<Message>();
 
-    let mut app = App::new(scan_path, cli.github_token, tx.clone());
+    // clap fills the flag from the environment too; tell them apart for the UI
+    let github_token = cli.github_token.map(|token| {
+        let source = if std::env::var("GITHUB_TOKEN").is_ok_and(|env| env == token) {
+            auth::TokenSource::Env
+        } else {
+            auth::TokenSource::Flag
+        };
+        (token, source)
+    });
+    let mut app = App::new(scan_path, github_token, config, tx.clone());
 
     // Initial scan in a blocking task
     let scan_path = app.scan_path.clone();
+    let hosts = app.config.github.extra_hosts();
     let scan_tx = tx.clone();
     tokio::spawn(async move {
-        let repos =
-            tokio::task::spawn_blocking(move || repo::scan_directory(&scan_path))
-                .await
-                .unwrap_or_default();
+        let repos = tokio::task::spawn_blocking(move || repo::scan_directory(&scan_path, &hosts))
+            .await
+            .unwrap_or_default();
         let _ = scan_tx.send(Message::ReposScanned(repos));
     });
 
@@ -50,51 +81,48 @@ async fn main() -> color_eyre::Result<()> {
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic_info| {
         let _ = crossterm::execute!(std::io::stdout(), crossterm::event::DisableMouseCapture);
-        let _ = ratatui::restore();
+        ratatui::restore();
         original_hook(panic_info);
     }));
 
     let mut terminal = ratatui::init();
     crossterm::execute!(std::io::stdout(), crossterm::event::EnableMouseCapture)?;
 
+    // Held by the event reader while it polls, and by the main loop while an
+    // external program owns the terminal, so no input is stolen from it.
+    let input_lock = Arc::new(Mutex::new(()));
+
     // Spawn keyboard event reader
     let key_tx = tx.clone();
+    let reader_lock = input_lock.clone();
     tokio::spawn(async move {
         loop {
             // Poll for events with a timeout to allow the task to be cooperative
-            let has_event = tokio::task::spawn_blocking(|| {
-                event::poll(Duration::from_millis(100)).unwrap_or(false)
+            let lock = reader_lock.clone();
+            let polled = tokio::task::spawn_blocking(move || {
+                let _guard = lock.lock();
+                if event::poll(Duration::from_millis(100)).unwrap_or(false) {
+                    Some(event::read())
+                } else {
+                    None
+                }
             })
             .await
-            .unwrap_or(false);
-
-            if has_event {
-                let ev = tokio::task::spawn_blocking(event::read)
-                    .await
-                    .unwrap_or(Ok(Event::FocusLost));
+            .unwrap_or(None);
 
+            if let Some(ev) = polled {
                 let msg = match ev {
                     Ok(Event::Key(key)) => {
                         if key.kind != KeyEventKind::Press {
                             continue;
                         }
-                        match key.code {
-                            KeyCode::Char('q') => Some(Message::Quit),
-                            KeyCode::Up | KeyCode::Char('k') => Some(Message::MoveUp),
-                            KeyCode::Down | KeyCode::Char('j') => Some(Message::MoveDown),
-                            KeyCode::Char('r') => Some(Message::Refresh),
-                            KeyCode::Char('R') => Some(Message::ForceRefresh),
-                            KeyCode::Tab | KeyCode::Enter => Some(Message::SwitchPane),
-                            KeyCode::Esc => Some(Message::FocusList),
-                            KeyCode::Char(']') => Some(Message::NextTab),
-                            KeyCode::Char('[') => Some(Message::PrevTab),
-                            _ => None,
-                        }
+                        Some(Message::Key(key))
                     }
                     Ok(Event::Mouse(mouse)) => match mouse.kind {
-                        MouseEventKind::Down(MouseButton::Left) => {
-                            Some(Message::Click { column: mouse.column, row: mouse.row })
-                        }
+                        MouseEventKind::Down(MouseButton::Left) => Some(Message::Click {
+                            column: mouse.column,
+                            row: mouse.row,
+                        }),
                         _ => None,
                     },
                     _ => None,
@@ -126,6 +154,11 @@ async fn main() -> color_eyre::Result<()> {
             }
         }
 
+        if let Some(cmd) = app.pending_external.take() {
+            let result = run_external(&mut terminal, &input_lock, &cmd);
+            app.update(Message::ExternalFinished(result));
+        }
+
         if app.should_quit {
             break;
         }
@@ -133,5 +166,50 @@ async fn main() -> color_eyre::Result<()> {
 
     crossterm::execute!(std::io::stdout(), crossterm::event::DisableMouseCapture)?;
     ratatui::restore();
+    github::flush_disk_cache();
     Ok(())
 }
+
+/// Run an external program. Interactive ones get the terminal: the TUI is
+/// torn down, the program runs to completion, and the TUI is restored.
+fn run_external(
+    terminal: &mut ratatui::DefaultTerminal,
+    input_lock: &Mutex<()>,
+    cmd: &ExternalCommand,
+) -> Result<(), String> {
+    let mut command = std::process::Command::new(&cmd.program);
+    command.args(&cmd.args).current_dir(&cmd.cwd);
+
+    if !cmd.suspend {
+        command
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null());
+        let mut child = command
+            .spawn()
+            .map_err(|e| format!("{}: {e}", cmd.program))?;
+        // Reap it once it exits so it doesn't linger as a zombie
+        tokio::task::spawn_blocking(move || child.wait());
+        return Ok(());
+    }
+
+    let _guard = input_lock.lock();
+    let _ = crossterm::execute!(std::io::stdout(), crossterm::event::DisableMouseCapture);
+    ratatui::restore();
+
+    let status = tokio::task::block_in_place(|| command.status());
+
+    let _ = crossterm::terminal::enable_raw_mode();
+    let _ = crossterm::execute!(
+        std::io::stdout(),
+        crossterm::terminal::EnterAlternateScreen,
+        crossterm::event::EnableMouseCapture
+    );
+    let _ = terminal.clear();
+
+    match status {
+        Ok(s) if s.success() => Ok(()),
+        Ok(s) => Err(format!("{} exited with {s}", cmd.program)),
+        Err(e) => Err(format!("{}: {e}", cmd.program)),
+    }
+}
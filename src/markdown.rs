@@ -0,0 +1,306 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+/// Render GitHub-flavored markdown as styled lines for a `Paragraph`.
+/// Covers what issue and PR bodies commonly use: headings, lists, task
+/// lists, quotes, fenced code, rules and inline emphasis, code and links.
+/// HTML comments (as left behind by templates) are dropped.
+pub fn render(text: &str) -> Vec<Line<'static>> {
+    let text = strip_html_comments(text);
+    let dim = Style::default().fg(Color::DarkGray);
+    let code = Style::default().fg(Color::Yellow);
+
+    let mut lines = Vec::new();
+    let mut in_fence = false;
+    let mut blank_run = false;
+
+    for raw in text.lines() {
+        let trimmed = raw.trim_start();
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            lines.push(Line::from(vec![
+                Span::styled(" │ ", dim),
+                Span::styled(raw.to_string(), code),
+            ]));
+            continue;
+        }
+
+        // Collapse runs of blank lines
+        if trimmed.is_empty() {
+            if !blank_run && !lines.is_empty() {
+                lines.push(Line::from(""));
+            }
+            blank_run = true;
+            continue;
+        }
+        blank_run = false;
+
+        let indent = " ".repeat(1 + (raw.len() - trimmed.len()).min(8));
+
+        if let Some(heading) = heading_text(trimmed) {
+            let style = Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD);
+            lines.push(Line::from(Span::styled(format!(" {heading}"), style)));
+        } else if is_rule(trimmed) {
+            lines.push(Line::from(Span::styled(" ────────────────", dim)));
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            let mut spans = vec![Span::styled(format!("{indent}▎ "), dim)];
+            spans.extend(inline(
+                quote.trim_start(),
+                dim.add_modifier(Modifier::ITALIC),
+            ));
+            lines.push(Line::from(spans));
+        } else if let Some((marker, rest)) = list_item(trimmed) {
+            let mut spans = vec![Span::raw(indent)];
+            match task_state(rest) {
+                Some((done, rest)) => {
+                    let (mark, color) = if done {
+                        ("☑ ", Color::Green)
+                    } else {
+                        ("☐ ", Color::DarkGray)
+                    };
+                    spans.push(Span::styled(mark, Style::default().fg(color)));
+                    spans.extend(inline(rest, Style::default().fg(Color::White)));
+                }
+                None => {
+                    spans.push(Span::styled(
+                        format!("{marker} "),
+                        Style::default().fg(Color::Yellow),
+                    ));
+                    spans.extend(inline(rest, Style::default().fg(Color::White)));
+                }
+            }
+            lines.push(Line::from(spans));
+        } else {
+            let mut spans = vec![Span::raw(indent)];
+            spans.extend(inline(trimmed, Style::default().fg(Color::White)));
+            lines.push(Line::from(spans));
+        }
+    }
+
+    while lines.last().is_some_and(|l| l.spans.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+fn strip_html_comments(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("<!--") {
+        out.push_str(&rest[..start]);
+        match rest[start..].find("-->") {
+            Some(end) => rest = &rest[start + end + 3..],
+            None => return out,
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn heading_text(line: &str) -> Option<&str> {
+    let hashes = line.chars().take_while(|c| *c == '#').count();
+    if hashes == 0 || hashes > 6 {
+        return None;
+    }
+    let rest = &line[hashes..];
+    (rest.is_empty() || rest.starts_with(' ')).then(|| rest.trim())
+}
+
+fn is_rule(line: &str) -> bool {
+    let compact: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    compact.len() >= 3
+        && ["-", "*", "_"]
+            .iter()
+            .any(|m| compact.chars().all(|c| c.to_string() == *m))
+}
+
+/// Split a list item into its display marker and content.
+fn list_item(line: &str) -> Option<(String, &str)> {
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(rest) = line.strip_prefix(bullet) {
+            return Some(("•".to_string(), rest));
+        }
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        let rest = &line[digits..];
+        if let Some(rest) = rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") ")) {
+            return Some((format!("{}.", &line[..digits]), rest));
+        }
+    }
+    None
+}
+
+fn task_state(item: &str) -> Option<(bool, &str)> {
+    if let Some(rest) = item.strip_prefix("[ ] ") {
+        Some((false, rest))
+    } else {
+        item.strip_prefix("[x] ")
+            .or_else(|| item.strip_prefix("[X] "))
+            .map(|rest| (true, rest))
+    }
+}
+
+/// Style inline `code`, **bold**, *italic* / _italic_ and [links](url).
+fn inline(text: &str, base: Style) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    let flush = |plain: &mut String, spans: &mut Vec<Span<'static>>| {
+        if !plain.is_empty() {
+            spans.push(Span::styled(std::mem::take(plain), base));
+        }
+    };
+
+    while let Some(c) = rest.chars().next() {
+        let styled = match c {
+            '`' => delimited(rest, "`").map(|(inner, len)| {
+                (
+                    Span::styled(inner.to_string(), Style::default().fg(Color::Yellow)),
+                    len,
+                )
+            }),
+            // Underscores inside words (snake_case) are not emphasis
+            '_' if plain.chars().last().is_some_and(char::is_alphanumeric) => None,
+            '*' | '_' => {
+                let double = if c == '*' { "**" } else { "__" };
+                let single = if c == '*' { "*" } else { "_" };
+                delimited(rest, double)
+                    .map(|(inner, len)| {
+                        (
+                            Span::styled(inner.to_string(), base.add_modifier(Modifier::BOLD)),
+                            len,
+                        )
+                    })
+                    .or_else(|| {
+                        delimited(rest, single).map(|(inner, len)| {
+                            (
+                                Span::styled(
+                                    inner.to_string(),
+                                    base.add_modifier(Modifier::ITALIC),
+                                ),
+                                len,
+                            )
+                        })
+                    })
+            }
+            '[' => link(rest).map(|(label, len)| {
+                (
+                    Span::styled(
+                        label.to_string(),
+                        Style::default()
+                            .fg(Color::Blue)
+                            .add_modifier(Modifier::UNDERLINED),
+                    ),
+                    len,
+                )
+            }),
+            _ => None,
+        };
+
+        match styled {
+            Some((span, len)) => {
+                flush(&mut plain, &mut spans);
+                spans.push(span);
+                rest = &rest[len..];
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+    flush(&mut plain, &mut spans);
+    spans
+}
+
+/// `text` starting with `delim`, closed by another `delim` on the same
+/// line: the inner text and the total length consumed.
+fn delimited<'a>(text: &'a str, delim: &str) -> Option<(&'a str, usize)> {
+    let body = text.strip_prefix(delim)?;
+    let end = body.find(delim)?;
+    let inner = &body[..end];
+    if inner.is_empty() || inner.starts_with(' ') {
+        return None;
+    }
+    Some((inner, delim.len() * 2 + end))
+}
+
+/// `[label](url)`: the label and the total length consumed.
+fn link(text: &str) -> Option<(&str, usize)> {
+    let close = text.find("](")?;
+    let label = &text[1..close];
+    if label.contains('[') {
+        return None;
+    }
+    let url_end = text[close + 2..].find(')')?;
+    Some((label, close + 2 + url_end + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_render_blocks() {
+        let lines = render(
+            "## Summary\n<!-- template hint -->\n\n\n- [x] tests\n1. step\n```\nlet x = 1;\n```\n> quoted",
+        );
+        let text: Vec<String> = lines.iter().map(plain).collect();
+        assert_eq!(
+            text,
+            vec![
+                " Summary",
+                "",
+                " ☑ tests",
+                " 1. step",
+                " │ let x = 1;",
+                " ▎ quoted"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_inline_styles() {
+        let lines = render("Use `cargo` and **bold** or *it* — see [docs](https://x.y/z).");
+        let spans = &lines[0].spans;
+        let contents: Vec<&str> = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(
+            contents,
+            vec![
+                " ",
+                "Use ",
+                "cargo",
+                " and ",
+                "bold",
+                " or ",
+                "it",
+                " — see ",
+                "docs",
+                "."
+            ]
+        );
+        assert!(spans[4].style.add_modifier.contains(Modifier::BOLD));
+        assert!(spans[6].style.add_modifier.contains(Modifier::ITALIC));
+        assert!(spans[8].style.add_modifier.contains(Modifier::UNDERLINED));
+    }
+
+    #[test]
+    fn test_unclosed_markers_stay_literal() {
+        let lines = render("a * b and snake_case_name");
+        assert_eq!(plain(&lines[0]), " a * b and snake_case_name");
+    }
+}
@@ -4,6 +4,7 @@ use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
 use git2::Repository;
+use serde::{Deserialize, Serialize};
 
 const REPO_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
 
@@ -25,18 +26,72 @@ pub enum RepoStatus {
     },
 }
 
-#[derive(Debug, Clone)]
+pub const GITHUB_HOST: &str = "github.com";
+
+/// A repository on github.com or a GitHub Enterprise Server host.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GitHubRepo {
+    pub host: String,
+    pub owner: String,
+    pub name: String,
+}
+
+impl GitHubRepo {
+    pub fn full_name(&self) -> String {
+        format!("{}/{}", self.owner, self.name)
+    }
+
+    /// Same repository, ignoring case: GitHub names are case-insensitive.
+    pub fn eq_ignore_case(&self, other: &GitHubRepo) -> bool {
+        self.host.eq_ignore_ascii_case(&other.host)
+            && self.owner.eq_ignore_ascii_case(&other.owner)
+            && self.name.eq_ignore_ascii_case(&other.name)
+    }
+
+    /// Browser URL of the repository.
+    pub fn web_url(&self) -> String {
+        format!("https://{}/{}/{}", self.host, self.owner, self.name)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitHubItem {
     pub number: u64,
     pub title: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitHubData {
     pub open_issues: usize,
     pub open_prs: usize,
     pub recent_issues: Vec<GitHubItem>,
     pub recent_prs: Vec<GitHubItem>,
+    pub fetched_at: chrono::DateTime<chrono::Utc>,
+    #[serde(default)]
+    pub default_branch: Option<String>,
+    /// Checks on the default branch's head; only known when fetched
+    /// through GraphQL.
+    #[serde(default)]
+    pub ci: Option<crate::github::CheckState>,
+    #[serde(default)]
+    pub meta: RepoMeta,
+}
+
+/// Descriptive details of a GitHub repository.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RepoMeta {
+    pub description: Option<String>,
+    pub topics: Vec<String>,
+    pub stars: u32,
+    pub forks: u32,
+    pub watchers: u32,
+    /// `public`, `private` or `internal`.
+    pub visibility: Option<String>,
+    pub archived: bool,
+    pub disabled: bool,
+    /// SPDX identifier, or the license name when it has none.
+    pub license: Option<String>,
+    pub pushed_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Clone)]
@@ -55,8 +110,11 @@ pub struct RepoInfo {
     pub status: RepoStatus,
     pub current_branch: String,
     pub branches: Vec<String>,
+    /// The default branch as the local clone knows it, to compare with
+    /// GitHub's.
+    pub local_default_branch: Option<String>,
     pub remote_url: Option<String>,
-    pub github_repo: Option<(String, String)>,
+    pub github_repo: Option<GitHubRepo>,
     pub github_data: Option<GitHubData>,
     pub github_error: Option<String>,
     pub recent_commits: Vec<CommitInfo>,
@@ -65,19 +123,20 @@ pub struct RepoInfo {
 
 /// Recursively scan a directory for git repositories.
 /// Stops recursing into directories that are themselves git repos.
-pub fn scan_directory(path: &Path) -> Vec<RepoInfo> {
+/// `github_hosts` lists Enterprise hosts recognized besides github.com.
+pub fn scan_directory(path: &Path, github_hosts: &[String]) -> Vec<RepoInfo> {
     let mut repos = Vec::new();
 
     // Check if the starting directory itself is a repo
     if is_git_repo(path) {
-        if let Some(info) = analyze_repo(path) {
+        if let Some(info) = analyze_repo(path, github_hosts) {
             repos.push(info);
         }
         return repos;
     }
 
-    scan_recursive(path, &mut repos);
-    repos.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    scan_recursive(path, github_hosts, &mut repos);
+    repos.sort_by_key(|r| r.name.to_lowercase());
     repos
 }
 
@@ -110,7 +169,7 @@ fn is_git_repo(path: &Path) -> bool {
     git_path.is_dir() || git_path.is_file()
 }
 
-fn scan_recursive(path: &Path, repos: &mut Vec<RepoInfo>) {
+fn scan_recursive(path: &Path, github_hosts: &[String], repos: &mut Vec<RepoInfo>) {
     let entries = match std::fs::read_dir(path) {
         Ok(entries) => entries,
         Err(_) => return,
@@ -133,18 +192,18 @@ fn scan_recursive(path: &Path, repos: &mut Vec<RepoInfo>) {
         }
 
         if is_git_repo(&entry_path) {
-            if let Some(info) = analyze_repo(&entry_path) {
+            if let Some(info) = analyze_repo(&entry_path, github_hosts) {
                 repos.push(info);
             }
         }
         // Always recurse - there may be nested repos inside
-        scan_recursive(&entry_path, repos);
+        scan_recursive(&entry_path, github_hosts, repos);
     }
 }
 
 /// Analyze a single git repository and extract information.
 /// Results are cached for 1 hour per repo path.
-fn analyze_repo(path: &Path) -> Option<RepoInfo> {
+fn analyze_repo(path: &Path, github_hosts: &[String]) -> Option<RepoInfo> {
     // Check cache
     if let Ok(cache) = REPO_CACHE.lock() {
         if let Some(entry) = cache.get(path) {
@@ -154,7 +213,7 @@ fn analyze_repo(path: &Path) -> Option<RepoInfo> {
         }
     }
 
-    let info = analyze_repo_uncached(path)?;
+    let info = analyze_repo_uncached(path, github_hosts)?;
 
     // Store in cache
     if let Ok(mut cache) = REPO_CACHE.lock() {
@@ -170,7 +229,7 @@ fn analyze_repo(path: &Path) -> Option<RepoInfo> {
     Some(info)
 }
 
-fn analyze_repo_uncached(path: &Path) -> Option<RepoInfo> {
+fn analyze_repo_uncached(path: &Path, github_hosts: &[String]) -> Option<RepoInfo> {
     let repo = Repository::open(path).ok()?;
 
     let name = path
@@ -181,9 +240,12 @@ fn analyze_repo_uncached(path: &Path) -> Option<RepoInfo> {
 
     let current_branch = get_current_branch(&repo);
     let branches = list_branches(&repo);
+    let local_default_branch = crate::git::default_branch(&repo);
     let (status, changed_files) = get_repo_status(&repo);
     let remote_url = get_remote_url(&repo);
-    let github_repo = remote_url.as_deref().and_then(parse_github_url);
+    let github_repo = remote_url
+        .as_deref()
+        .and_then(|url| parse_github_url(url, github_hosts));
     let recent_commits = get_recent_commits(&repo, 20);
 
     Some(RepoInfo {
@@ -192,6 +254,7 @@ fn analyze_repo_uncached(path: &Path) -> Option<RepoInfo> {
         status,
         current_branch,
         branches,
+        local_default_branch,
         remote_url,
         github_repo,
         github_data: None,
@@ -311,7 +374,7 @@ fn get_recent_commits(repo: &Repository, count: usize) -> Vec<CommitInfo> {
     commits
 }
 
-fn format_timestamp(secs: i64) -> String {
+pub fn format_timestamp(secs: i64) -> String {
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .map(|d| d.as_secs() as i64)
@@ -331,6 +394,14 @@ fn format_timestamp(secs: i64) -> String {
     }
 }
 
+/// Re-analyze a single repository, replacing its cache entry.
+pub fn rescan_repo(path: &Path, github_hosts: &[String]) -> Option<RepoInfo> {
+    if let Ok(mut cache) = REPO_CACHE.lock() {
+        cache.remove(path);
+    }
+    analyze_repo(path, github_hosts)
+}
+
 /// Invalidate all repo scan caches.
 pub fn invalidate_all_repo_caches() {
     if let Ok(mut cache) = REPO_CACHE.lock() {
@@ -338,27 +409,38 @@ pub fn invalidate_all_repo_caches() {
     }
 }
 
-/// Parse a GitHub URL (HTTPS or SSH) into (owner, repo).
-pub fn parse_github_url(url: &str) -> Option<(String, String)> {
-    // SSH: git@github.com:owner/repo.git
-    if let Some(rest) = url.strip_prefix("git@github.com:") {
-        let rest = rest.strip_suffix(".git").unwrap_or(rest);
-        let parts: Vec<&str> = rest.splitn(2, '/').collect();
-        if parts.len() == 2 {
-            return Some((parts[0].to_string(), parts[1].to_string()));
-        }
-    }
+/// Parse a GitHub remote URL (HTTPS or SSH) into host, owner and repo.
+/// `github.com` is always recognized; `extra_hosts` adds Enterprise hosts.
+pub fn parse_github_url(url: &str, extra_hosts: &[String]) -> Option<GitHubRepo> {
+    let url = url.trim().trim_end_matches('/');
+    let url = url.strip_suffix(".git").unwrap_or(url);
+
+    let (host, path) = if let Some((_, rest)) = url.split_once("://") {
+        // HTTPS or SSH URL: scheme://[user@]host[:port]/owner/repo
+        let (authority, path) = rest.split_once('/')?;
+        let host = authority.rsplit('@').next()?;
+        (host.split(':').next()?, path)
+    } else {
+        // scp-style SSH: git@host:owner/repo
+        let (user_host, path) = url.split_once(':')?;
+        (user_host.rsplit('@').next()?, path)
+    };
 
-    // HTTPS: https://github.com/owner/repo.git
-    if url.contains("github.com") {
-        let url = url.strip_suffix(".git").unwrap_or(url);
-        let parts: Vec<&str> = url.rsplitn(3, '/').collect();
-        if parts.len() >= 2 {
-            return Some((parts[1].to_string(), parts[0].to_string()));
-        }
+    let known = host.eq_ignore_ascii_case(GITHUB_HOST)
+        || extra_hosts.iter().any(|h| h.eq_ignore_ascii_case(host));
+    if !known {
+        return None;
     }
 
-    None
+    let (owner, name) = path.split_once('/')?;
+    if owner.is_empty() || name.is_empty() || name.contains('/') {
+        return None;
+    }
+    Some(GitHubRepo {
+        host: host.to_lowercase(),
+        owner: owner.to_string(),
+        name: name.to_string(),
+    })
 }
 
 #[cfg(test)]
@@ -367,28 +449,49 @@ mod tests {
 
     #[test]
     fn test_parse_github_ssh_url() {
-        let result = parse_github_url("git@github.com:user/repo.git");
+        let result =
+            parse_github_url("git@github.com:user/repo.git", &[]).map(|r| (r.owner, r.name));
         assert_eq!(result, Some(("user".to_string(), "repo".to_string())));
     }
 
     #[test]
     fn test_parse_github_https_url() {
-        let result = parse_github_url("https://github.com/user/repo.git");
+        let result =
+            parse_github_url("https://github.com/user/repo.git", &[]).map(|r| (r.owner, r.name));
         assert_eq!(result, Some(("user".to_string(), "repo".to_string())));
     }
 
     #[test]
     fn test_parse_github_https_no_git_suffix() {
-        let result = parse_github_url("https://github.com/user/repo");
+        let result =
+            parse_github_url("https://github.com/user/repo", &[]).map(|r| (r.owner, r.name));
         assert_eq!(result, Some(("user".to_string(), "repo".to_string())));
     }
 
     #[test]
     fn test_parse_non_github_url() {
-        let result = parse_github_url("https://gitlab.com/user/repo.git");
+        let result = parse_github_url("https://gitlab.com/user/repo.git", &[]);
         assert_eq!(result, None);
     }
 
+    #[test]
+    fn test_parse_enterprise_host() {
+        let hosts = vec!["github.mycorp.com".to_string()];
+        let result = parse_github_url("ssh://git@github.mycorp.com:2222/team/tool.git", &hosts);
+        assert_eq!(
+            result,
+            Some(GitHubRepo {
+                host: "github.mycorp.com".to_string(),
+                owner: "team".to_string(),
+                name: "tool".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_github_url("git@github.mycorp.com:team/tool.git", &[]),
+            None
+        );
+    }
+
     #[test]
     fn test_scan_finds_nested_repos() {
         let tmp = std::env::temp_dir().join("project-dash-test-nested");
@@ -403,7 +506,7 @@ mod tests {
         git2::Repository::init(&repo_a).unwrap();
         git2::Repository::init(&repo_b).unwrap();
 
-        let repos = scan_directory(&tmp);
+        let repos = scan_directory(&tmp, &[]);
         let mut names: Vec<&str> = repos.iter().map(|r| r.name.as_str()).collect();
         names.sort();
 
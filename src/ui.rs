@@ -2,11 +2,21 @@ use ratatui::{
     layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, Cell, Paragraph, Row, Table, Wrap},
+    widgets::{Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Wrap},
     Frame,
 };
 
-use crate::app::{ActivePane, App, AppState, DetailTab};
+use crate::actions::BulkAction;
+use crate::app::{
+    ActionsView, ActivePane, App, AppState, BranchCleanup, BulkProgress, BulkState, Choice,
+    ClickTarget, CommandRun, DetailTab, Inbox, IssueField, IssueForm, ItemList, ItemView,
+    LabelPicker, MergeField, MergeForm, Overlay, Prompt, ReleasesView, RemoteRepoList, RunStatus,
+};
+use crate::github::{
+    BranchPr, CheckState, InboxReason, IssueDetail, ItemDetail, Mergeability, PrDetail,
+    ReviewVerdict,
+};
+use crate::markdown;
 use crate::repo::RepoStatus;
 
 fn block(title: &str, focused: bool) -> Block<'_> {
@@ -54,6 +64,22 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
             },
             Style::default().fg(Color::Yellow),
         ),
+        Span::styled(
+            if app.marked.is_empty() {
+                String::new()
+            } else {
+                format!(" {} marked", app.marked.len())
+            },
+            Style::default().fg(Color::Magenta),
+        ),
+        Span::styled(
+            if app.notifications.is_empty() {
+                String::new()
+            } else {
+                format!(" {} unread", app.notifications.len())
+            },
+            Style::default().fg(Color::Yellow),
+        ),
     ]));
 
     frame.render_widget(title, title_area);
@@ -62,40 +88,94 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
     let mut max_name: u16 = 4;
     let mut max_status: u16 = 6;
     for repo in &app.repos {
-        max_name = max_name.max(repo.name.len() as u16);
+        let run_width = if app.command_runs.contains_key(&repo.path) {
+            2
+        } else {
+            0
+        };
+        max_name = max_name.max(repo.name.len() as u16 + run_width);
         max_status = max_status.max(status_width(repo));
     }
-    let list_width = 2 + 2 + max_name + PAD + max_status + PAD;
+    let count_width = count_width(&app.repos);
+    let list_width =
+        2 + 2 + MARK_WIDTH + max_name + PAD + max_status + PAD + 2 * (count_width + PAD);
 
     // Main area: repo list (left) + right side (info panel + detail tabs)
-    let [list_area, right_area] = Layout::horizontal([
-        Constraint::Length(list_width),
-        Constraint::Fill(1),
-    ])
-    .areas(main_area);
+    let [list_area, right_area] =
+        Layout::horizontal([Constraint::Length(list_width), Constraint::Fill(1)]).areas(main_area);
 
-    app.list_area = list_area;
     app.click_zones.clear();
-    draw_repo_list(frame, app, list_area);
-
-    // Right side: info panel (fixed height) + tabbed detail pane (fill)
-    let info_height = if app.selected_repo().is_some() { 5 } else { 0 };
-    let [info_area, detail_area] = Layout::vertical([
-        Constraint::Length(info_height),
-        Constraint::Fill(1),
-    ])
-    .areas(right_area);
+    if let Some(inbox) = &app.inbox {
+        app.list_area = Rect::default();
+        draw_inbox(frame, inbox, main_area);
+    } else if app.notifications_open {
+        app.list_area = Rect::default();
+        draw_notifications(frame, app, main_area);
+    } else {
+        app.list_area = list_area;
+        draw_repo_list(frame, app, list_area);
+
+        // Right side: info panel (fixed height) + tabbed detail pane (fill)
+        // GitHub repos get two more rows for the repository's metadata
+        let info_height = match app.selected_repo() {
+            Some(repo) if repo.github_repo.is_some() => 8,
+            Some(_) => 6,
+            None => 0,
+        };
+        let [info_area, detail_area] =
+            Layout::vertical([Constraint::Length(info_height), Constraint::Fill(1)])
+                .areas(right_area);
 
-    if app.selected_repo().is_some() {
-        draw_info_panel(frame, app, info_area);
+        if app.selected_repo().is_some() {
+            draw_info_panel(frame, app, info_area);
+        }
+        draw_detail_pane(frame, app, detail_area);
     }
-    draw_detail_pane(frame, app, detail_area);
 
     // Status bar
     let key = Style::default().fg(Color::DarkGray);
     let desc = Style::default().fg(Color::Rgb(100, 100, 100));
 
+    let pr_view = app
+        .item_view
+        .as_ref()
+        .is_some_and(|v| matches!(v.detail, Some(ItemDetail::Pr(_))));
+    let pr_list = app.item_list.as_ref().is_some_and(|l| l.is_pr);
     let keybinds = match app.active_pane {
+        _ if app.notifications_open => vec![
+            Span::styled(" [↑/k] ", key),
+            Span::styled("Up  ", desc),
+            Span::styled("[↓/j] ", key),
+            Span::styled("Down  ", desc),
+            Span::styled("[Enter] ", key),
+            Span::styled("Go to Item  ", desc),
+            Span::styled("[o] ", key),
+            Span::styled("Open in Browser  ", desc),
+            Span::styled("[d] ", key),
+            Span::styled("Mark Read  ", desc),
+            Span::styled("[u] ", key),
+            Span::styled("Unsubscribe  ", desc),
+            Span::styled("[r] ", key),
+            Span::styled("Reload  ", desc),
+            Span::styled("[N/Esc] ", key),
+            Span::styled("Close", desc),
+        ],
+        _ if app.inbox.is_some() => vec![
+            Span::styled(" [↑/k] ", key),
+            Span::styled("Up  ", desc),
+            Span::styled("[↓/j] ", key),
+            Span::styled("Down  ", desc),
+            Span::styled("[Enter] ", key),
+            Span::styled("Go to Repo  ", desc),
+            Span::styled("[o] ", key),
+            Span::styled("Open in Browser  ", desc),
+            Span::styled("[r] ", key),
+            Span::styled("Reload  ", desc),
+            Span::styled("[w/Esc] ", key),
+            Span::styled("Close  ", desc),
+            Span::styled("[q] ", key),
+            Span::styled("Quit", desc),
+        ],
         ActivePane::RepoList => vec![
             Span::styled(" [↑/k] ", key),
             Span::styled("Up  ", desc),
@@ -107,9 +187,100 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
             Span::styled("Refresh  ", desc),
             Span::styled("[R] ", key),
             Span::styled("Hard Refresh  ", desc),
+            Span::styled("[Space] ", key),
+            Span::styled("Mark  ", desc),
+            Span::styled("[x] ", key),
+            Span::styled("Bulk  ", desc),
+            Span::styled("[!] ", key),
+            Span::styled("Run  ", desc),
+            Span::styled("[e/s/t] ", key),
+            Span::styled("Editor/Shell/tmux  ", desc),
+            Span::styled("[b/B] ", key),
+            Span::styled("Clean Branches  ", desc),
+            Span::styled("[c/C] ", key),
+            Span::styled("Clone  ", desc),
+            Span::styled("[P] ", key),
+            Span::styled("Create PR  ", desc),
+            Span::styled("[w] ", key),
+            Span::styled("My Work  ", desc),
+            Span::styled("[N] ", key),
+            Span::styled("Notifications  ", desc),
             Span::styled("[q] ", key),
             Span::styled("Quit", desc),
         ],
+        ActivePane::Detail if app.item_view.is_some() => vec![
+            Span::styled(" [↑/↓] ", key),
+            Span::styled("Scroll  ", desc),
+            Span::styled("[c] ", key),
+            Span::styled("Comment  ", desc),
+            Span::styled("[x] ", key),
+            Span::styled("Close/Reopen  ", desc),
+            Span::styled("[l] ", key),
+            Span::styled("Labels  ", desc),
+            Span::styled("[m/M] ", key),
+            Span::styled("Assign/Unassign Me  ", desc),
+            Span::styled(if pr_view { "[g] " } else { "" }, key),
+            Span::styled(if pr_view { "Merge  " } else { "" }, desc),
+            Span::styled(if pr_view { "[C/W] " } else { "" }, key),
+            Span::styled(if pr_view { "Check Out/Worktree  " } else { "" }, desc),
+            Span::styled("[r] ", key),
+            Span::styled("Reload  ", desc),
+            Span::styled("[Esc] ", key),
+            Span::styled("Back", desc),
+        ],
+        ActivePane::Detail if app.detail_tab == DetailTab::Actions => vec![
+            Span::styled(" [↑/↓] ", key),
+            Span::styled("Select/Scroll  ", desc),
+            Span::styled("[Enter] ", key),
+            Span::styled("Failed Logs  ", desc),
+            Span::styled("[f] ", key),
+            Span::styled("Re-run Failed  ", desc),
+            Span::styled("[o] ", key),
+            Span::styled("Open Run  ", desc),
+            Span::styled("[r] ", key),
+            Span::styled("Reload  ", desc),
+            Span::styled("[[/]] ", key),
+            Span::styled("Tabs  ", desc),
+            Span::styled("[Esc] ", key),
+            Span::styled("Back", desc),
+        ],
+        ActivePane::Detail
+            if app.item_list.is_some()
+                && matches!(app.detail_tab, DetailTab::Issues | DetailTab::Prs) =>
+        {
+            vec![
+                Span::styled(" [↑/↓] ", key),
+                Span::styled("Select  ", desc),
+                Span::styled("[Enter] ", key),
+                Span::styled("Open  ", desc),
+                Span::styled("[/] ", key),
+                Span::styled("Filter  ", desc),
+                Span::styled("[v] ", key),
+                Span::styled("Open/Closed/All  ", desc),
+                Span::styled("[F] ", key),
+                Span::styled("Save Filter  ", desc),
+                Span::styled(if pr_list { "[C/W] " } else { "" }, key),
+                Span::styled(if pr_list { "Check Out/Worktree  " } else { "" }, desc),
+                Span::styled("[r] ", key),
+                Span::styled("Reload  ", desc),
+                Span::styled("[[/]] ", key),
+                Span::styled("Tabs  ", desc),
+                Span::styled("[Esc] ", key),
+                Span::styled("Back", desc),
+            ]
+        }
+        ActivePane::Detail if app.detail_tab == DetailTab::Releases => vec![
+            Span::styled(" [↑/↓] ", key),
+            Span::styled("Scroll  ", desc),
+            Span::styled("[D] ", key),
+            Span::styled("Create Draft Release  ", desc),
+            Span::styled("[r] ", key),
+            Span::styled("Reload  ", desc),
+            Span::styled("[[/]] ", key),
+            Span::styled("Tabs  ", desc),
+            Span::styled("[Esc] ", key),
+            Span::styled("Back", desc),
+        ],
         ActivePane::Detail => vec![
             Span::styled(" [↑/k] ", key),
             Span::styled("Scroll Up  ", desc),
@@ -119,8 +290,12 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
             Span::styled("Prev Tab  ", desc),
             Span::styled("[]] ", key),
             Span::styled("Next Tab  ", desc),
+            Span::styled("[Enter] ", key),
+            Span::styled("Open  ", desc),
             Span::styled("[r] ", key),
             Span::styled("Retry  ", desc),
+            Span::styled("[e] ", key),
+            Span::styled("Edit  ", desc),
             Span::styled("[Tab/Esc] ", key),
             Span::styled("Back  ", desc),
             Span::styled("[q] ", key),
@@ -128,14 +303,966 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
         ],
     };
 
-    let status = Paragraph::new(Line::from(keybinds));
-    frame.render_widget(status, status_area);
+    let status = match &app.flash {
+        Some(flash) => Paragraph::new(Line::from(Span::styled(
+            format!(" {}", flash.text),
+            Style::default().fg(if flash.error {
+                Color::Red
+            } else {
+                Color::Green
+            }),
+        ))),
+        None => Paragraph::new(Line::from(keybinds)),
+    };
+    match rate_limit_span() {
+        Some(quota) => {
+            let [keys_area, quota_area] = Layout::horizontal([
+                Constraint::Min(0),
+                Constraint::Length(quota.width() as u16 + 1),
+            ])
+            .areas(status_area);
+            frame.render_widget(status, keys_area);
+            frame.render_widget(Paragraph::new(Line::from(quota)), quota_area);
+        }
+        None => frame.render_widget(status, status_area),
+    }
+
+    if let Some(overlay) = &app.overlay {
+        draw_overlay(frame, overlay, main_area);
+    }
+}
+
+/// A rect of at most `width` x `height` centered in `area`.
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+fn draw_overlay(frame: &mut Frame, overlay: &Overlay, area: Rect) {
+    match overlay {
+        Overlay::BranchCleanup(cleanup) => draw_branch_cleanup(frame, cleanup, area),
+        Overlay::BulkMenu { cursor } => draw_bulk_menu(frame, *cursor, area),
+        Overlay::Prompt(prompt) => draw_prompt(frame, prompt, area),
+        Overlay::BulkProgress(progress) => draw_bulk_progress(frame, progress, area),
+        Overlay::RemoteRepos(list) => draw_remote_repos(frame, list, area),
+        Overlay::IssueForm(form) => draw_issue_form(frame, form, area),
+        Overlay::MergeForm(form) => draw_merge_form(frame, form, area),
+        Overlay::LabelPicker(picker) => draw_label_picker(frame, picker, area),
+    }
+}
+
+fn draw_label_picker(frame: &mut Frame, picker: &LabelPicker, area: Rect) {
+    let key = Style::default().fg(Color::DarkGray);
+    let desc = Style::default().fg(Color::Rgb(100, 100, 100));
+
+    let popup = centered(area, 50, (picker.choices.len() as u16).clamp(1, 14) + 3);
+    frame.render_widget(Clear, popup);
+    let title = format!("Labels on #{}", picker.number);
+    let outer = block(&title, true);
+    let inner = outer.inner(popup);
+    frame.render_widget(outer, popup);
+
+    let [list_area, keys_area] =
+        Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(inner);
+    let lines = if picker.loading && picker.choices.is_empty() {
+        vec![Line::from(Span::styled(
+            " Loading labels...",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        choice_lines(&picker.choices, picker.cursor, true, picker.loading)
+    };
+    let height = list_area.height as usize;
+    let scroll = picker.cursor.saturating_sub(height.saturating_sub(1));
+    frame.render_widget(Paragraph::new(lines).scroll((scroll as u16, 0)), list_area);
+
+    let keybinds = vec![
+        Span::styled(" [Space] ", key),
+        Span::styled("Toggle  ", desc),
+        Span::styled("[Enter] ", key),
+        Span::styled("Apply  ", desc),
+        Span::styled("[Esc] ", key),
+        Span::styled("Cancel", desc),
+    ];
+    frame.render_widget(Paragraph::new(Line::from(keybinds)), keys_area);
+}
+
+/// Rows of a choice list in the issue form: the full list around the cursor
+/// while focused, otherwise just the selected names.
+fn choice_lines(
+    choices: &[Choice],
+    cursor: usize,
+    focused: bool,
+    loading: bool,
+) -> Vec<Line<'static>> {
+    const VISIBLE: usize = 6;
+    let dim = Style::default().fg(Color::DarkGray);
+    let value = Style::default().fg(Color::White);
+
+    if !focused {
+        let selected: Vec<&str> = choices
+            .iter()
+            .filter(|c| c.selected)
+            .map(|c| c.name.as_str())
+            .collect();
+        let text = if selected.is_empty() {
+            Span::styled("   none", dim)
+        } else {
+            Span::styled(format!("   {}", selected.join(", ")), value)
+        };
+        return vec![Line::from(text)];
+    }
+    if choices.is_empty() {
+        let text = if loading {
+            "   Loading..."
+        } else {
+            "   none available"
+        };
+        return vec![Line::from(Span::styled(text, dim))];
+    }
+
+    let start = cursor.saturating_sub(VISIBLE - 1);
+    choices
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(VISIBLE)
+        .map(|(i, choice)| {
+            let marker = if i == cursor { " ▶ " } else { "   " };
+            let check = if choice.selected { "[x] " } else { "[ ] " };
+            Line::from(vec![
+                Span::styled(marker, Style::default().fg(Color::Cyan)),
+                Span::styled(check, value),
+                Span::styled(choice.name.clone(), value),
+            ])
+        })
+        .collect()
+}
+
+fn draw_issue_form(frame: &mut Frame, form: &IssueForm, area: Rect) {
+    let dim = Style::default().fg(Color::DarkGray);
+    let value = Style::default().fg(Color::White);
+    let key = Style::default().fg(Color::DarkGray);
+    let desc = Style::default().fg(Color::Rgb(100, 100, 100));
+    let heading = |field: IssueField, text: &str| {
+        let style = if form.field == field {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Yellow)
+        };
+        Line::from(Span::styled(format!(" {text}"), style))
+    };
+
+    let mut lines: Vec<Line> = Vec::new();
+
+    if !form.templates.is_empty() {
+        lines.push(heading(IssueField::Template, "Template"));
+        if form.field == IssueField::Template {
+            for (i, template) in form.templates.iter().enumerate() {
+                let marker = if i == form.template_cursor {
+                    " ▶ "
+                } else {
+                    "   "
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(marker, Style::default().fg(Color::Cyan)),
+                    Span::styled(template.name.clone(), value),
+                ]));
+            }
+        } else {
+            let name = form
+                .templates
+                .get(form.template_cursor)
+                .map_or("", |t| t.name.as_str());
+            lines.push(Line::from(Span::styled(format!("   {name}"), dim)));
+        }
+    }
+
+    lines.push(heading(IssueField::Title, "Title"));
+    let mut title = vec![Span::raw("   "), Span::styled(form.title.clone(), value)];
+    if form.field == IssueField::Title {
+        title.push(Span::styled("█", Style::default().fg(Color::Cyan)));
+    }
+    lines.push(Line::from(title));
+
+    lines.push(heading(IssueField::Body, "Body"));
+    if form.body.trim().is_empty() {
+        lines.push(Line::from(Span::styled(
+            "   (empty) — Enter to write in $EDITOR",
+            dim,
+        )));
+    } else {
+        let body_lines: Vec<&str> = form.body.lines().collect();
+        for line in body_lines.iter().take(4) {
+            lines.push(Line::from(Span::styled(format!("   {line}"), value)));
+        }
+        if body_lines.len() > 4 {
+            lines.push(Line::from(Span::styled(
+                format!("   … {} more lines", body_lines.len() - 4),
+                dim,
+            )));
+        }
+    }
+
+    lines.push(heading(IssueField::Labels, "Labels"));
+    lines.extend(choice_lines(
+        &form.labels,
+        form.label_cursor,
+        form.field == IssueField::Labels,
+        form.loading_options,
+    ));
+    lines.push(heading(IssueField::Assignees, "Assignees"));
+    lines.extend(choice_lines(
+        &form.assignees,
+        form.assignee_cursor,
+        form.field == IssueField::Assignees,
+        form.loading_options,
+    ));
+
+    lines.push(Line::from(""));
+    let submit_style = if form.field == IssueField::Submit {
+        Style::default()
+            .fg(Color::Black)
+            .bg(Color::Green)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::Green)
+    };
+    let submit_text = if form.submitting {
+        " Creating... "
+    } else {
+        " Create issue "
+    };
+    lines.push(Line::from(vec![
+        Span::raw(" "),
+        Span::styled(submit_text, submit_style),
+    ]));
+    if let Some(err) = &form.error {
+        lines.push(Line::from(Span::styled(
+            format!(" {err}"),
+            Style::default().fg(Color::Red),
+        )));
+    }
+
+    let popup = centered(area, 80, lines.len() as u16 + 3);
+    frame.render_widget(Clear, popup);
+    let title = format!("New issue in {}", form.repo_name);
+    let outer = block(&title, true);
+    let inner = outer.inner(popup);
+    frame.render_widget(outer, popup);
+
+    let [body_area, keys_area] =
+        Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(inner);
+    frame.render_widget(Paragraph::new(lines), body_area);
+
+    let keybinds = vec![
+        Span::styled(" [Tab] ", key),
+        Span::styled("Next field  ", desc),
+        Span::styled("[↑/↓] ", key),
+        Span::styled("Move  ", desc),
+        Span::styled("[Space] ", key),
+        Span::styled("Toggle  ", desc),
+        Span::styled("[Enter] ", key),
+        Span::styled("Select/Edit/Create  ", desc),
+        Span::styled("[Esc] ", key),
+        Span::styled("Cancel", desc),
+    ];
+    frame.render_widget(Paragraph::new(Line::from(keybinds)), keys_area);
+}
+
+fn draw_merge_form(frame: &mut Frame, form: &MergeForm, area: Rect) {
+    let dim = Style::default().fg(Color::DarkGray);
+    let value = Style::default().fg(Color::White);
+    let key = Style::default().fg(Color::DarkGray);
+    let desc = Style::default().fg(Color::Rgb(100, 100, 100));
+    let heading = |field: MergeField, text: &str| {
+        let style = if form.field == field {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Yellow)
+        };
+        Line::from(Span::styled(format!(" {text}"), style))
+    };
+
+    let mut lines: Vec<Line> = Vec::new();
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!(" #{} ", form.number),
+            Style::default().fg(Color::Magenta),
+        ),
+        Span::styled(form.pr.title.clone(), value.add_modifier(Modifier::BOLD)),
+    ]));
+    lines.push(Line::from(Span::styled(
+        format!(" {} → {}", form.pr.head, form.pr.base),
+        dim,
+    )));
+    if !form.blockers.is_empty() {
+        // Conflicts and branch protection stop the merge; failing checks
+        // alone only warn
+        let blocked = matches!(
+            form.pr.mergeability,
+            Mergeability::Blocked
+                | Mergeability::Behind
+                | Mergeability::Conflicting
+                | Mergeability::Draft
+        ) || !form.pr.open;
+        let (title, color) = if blocked {
+            ("Blocked", Color::Red)
+        } else {
+            ("Heads up", Color::Yellow)
+        };
+        lines.push(Line::from(Span::styled(
+            format!(" {title}:"),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        )));
+        for blocker in &form.blockers {
+            lines.push(Line::from(Span::styled(
+                format!("   • {blocker}"),
+                Style::default().fg(color),
+            )));
+        }
+    }
+    lines.push(Line::from(""));
+
+    lines.push(heading(MergeField::Method, "Method"));
+    let mut methods = vec![Span::raw("  ")];
+    for method in &form.methods {
+        let style = if *method == form.method {
+            Style::default().fg(Color::Black).bg(Color::Cyan)
+        } else {
+            dim
+        };
+        methods.push(Span::raw(" "));
+        methods.push(Span::styled(format!(" {} ", method.label()), style));
+    }
+    if form.loading_settings {
+        methods.push(Span::styled("  loading allowed methods...", dim));
+    }
+    lines.push(Line::from(methods));
+
+    if form.method.takes_message() {
+        lines.push(heading(MergeField::Title, "Commit title"));
+        let mut title = vec![Span::raw("   "), Span::styled(form.title.clone(), value)];
+        if form.field == MergeField::Title {
+            title.push(Span::styled("█", Style::default().fg(Color::Cyan)));
+        }
+        lines.push(Line::from(title));
+
+        lines.push(heading(MergeField::Message, "Commit message"));
+        if form.message.trim().is_empty() {
+            lines.push(Line::from(Span::styled(
+                "   (empty) — Enter to write in $EDITOR",
+                dim,
+            )));
+        } else {
+            let message_lines: Vec<&str> = form.message.lines().collect();
+            for line in message_lines.iter().take(4) {
+                lines.push(Line::from(Span::styled(format!("   {line}"), value)));
+            }
+            if message_lines.len() > 4 {
+                lines.push(Line::from(Span::styled(
+                    format!("   … {} more lines", message_lines.len() - 4),
+                    dim,
+                )));
+            }
+        }
+    }
+
+    match form.delete_branch {
+        Some(delete) => {
+            lines.push(heading(MergeField::DeleteBranch, "Head branch"));
+            let mark = if delete { "[x]" } else { "[ ]" };
+            lines.push(Line::from(vec![
+                Span::styled(format!("   {mark} "), Style::default().fg(Color::Cyan)),
+                Span::styled(format!("Delete {} after merging", form.pr.head), value),
+            ]));
+        }
+        None => lines.push(Line::from(Span::styled(
+            format!("   {} is in a fork and is left alone", form.pr.head),
+            dim,
+        ))),
+    }
+
+    lines.push(Line::from(""));
+    let submit_style = if form.field == MergeField::Submit {
+        Style::default()
+            .fg(Color::Black)
+            .bg(Color::Green)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::Green)
+    };
+    let submit_text = if form.submitting {
+        " Merging... ".to_string()
+    } else {
+        format!(" {} ", form.method.label())
+    };
+    lines.push(Line::from(vec![
+        Span::raw(" "),
+        Span::styled(submit_text, submit_style),
+    ]));
+    if let Some(err) = &form.error {
+        lines.push(Line::from(Span::styled(
+            format!(" {err}"),
+            Style::default().fg(Color::Red),
+        )));
+    }
+
+    let popup = centered(area, 80, lines.len() as u16 + 3);
+    frame.render_widget(Clear, popup);
+    let outer = block("Merge pull request", true);
+    let inner = outer.inner(popup);
+    frame.render_widget(outer, popup);
+
+    let [body_area, keys_area] =
+        Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(inner);
+    frame.render_widget(Paragraph::new(lines), body_area);
+
+    let keybinds = vec![
+        Span::styled(" [Tab] ", key),
+        Span::styled("Next field  ", desc),
+        Span::styled("[←/→] ", key),
+        Span::styled("Method  ", desc),
+        Span::styled("[Space] ", key),
+        Span::styled("Toggle  ", desc),
+        Span::styled("[Enter] ", key),
+        Span::styled("Edit/Merge  ", desc),
+        Span::styled("[Esc] ", key),
+        Span::styled("Cancel", desc),
+    ];
+    frame.render_widget(Paragraph::new(Line::from(keybinds)), keys_area);
+}
+
+fn draw_remote_repos(frame: &mut Frame, list: &RemoteRepoList, area: Rect) {
+    let dim = Style::default().fg(Color::DarkGray);
+    let value = Style::default().fg(Color::White);
+    let key = Style::default().fg(Color::DarkGray);
+    let desc = Style::default().fg(Color::Rgb(100, 100, 100));
+
+    let popup = centered(area, 90, area.height.saturating_sub(4));
+    frame.render_widget(Clear, popup);
+
+    let title = format!("Not cloned from {}", list.owner);
+    let outer = block(&title, true);
+    let inner = outer.inner(popup);
+    frame.render_widget(outer, popup);
+
+    let [list_area, status_area, keys_area] = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .areas(inner);
+
+    let mut lines: Vec<Line> = Vec::new();
+    if list.loading {
+        lines.push(Line::from(Span::styled(" Loading repositories...", dim)));
+    } else if let Some(err) = &list.error {
+        lines.push(Line::from(Span::styled(
+            format!(" {err}"),
+            Style::default().fg(Color::Red),
+        )));
+    } else if list.entries.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " Everything is already cloned",
+            dim,
+        )));
+    }
+
+    let name_width = list
+        .entries
+        .iter()
+        .map(|e| e.repo.name.len())
+        .max()
+        .unwrap_or(0);
+
+    for (i, entry) in list.entries.iter().enumerate() {
+        let cursor = if i == list.cursor { "▶ " } else { "  " };
+        let check = if entry.selected { "[x] " } else { "[ ] " };
+        let mut spans = vec![
+            Span::styled(cursor, Style::default().fg(Color::Cyan)),
+            Span::styled(check, value),
+            Span::styled(format!("{:<name_width$}  ", entry.repo.name), value),
+        ];
+        if entry.repo.archived {
+            spans.push(Span::styled(
+                "archived  ",
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        if let Some(description) = &entry.repo.description {
+            spans.push(Span::styled(description.clone(), dim));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let height = list_area.height as usize;
+    let scroll = list.cursor.saturating_sub(height.saturating_sub(1));
+    frame.render_widget(Paragraph::new(lines).scroll((scroll as u16, 0)), list_area);
+
+    let selected = list.entries.iter().filter(|e| e.selected).count();
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            format!(" {selected} of {} selected", list.entries.len()),
+            dim,
+        ))),
+        status_area,
+    );
+
+    let keybinds = vec![
+        Span::styled(" [Space] ", key),
+        Span::styled("Toggle  ", desc),
+        Span::styled("[a] ", key),
+        Span::styled("All  ", desc),
+        Span::styled("[Enter] ", key),
+        Span::styled("Clone  ", desc),
+        Span::styled("[Esc] ", key),
+        Span::styled("Close", desc),
+    ];
+    frame.render_widget(Paragraph::new(Line::from(keybinds)), keys_area);
+}
+
+fn draw_bulk_menu(frame: &mut Frame, cursor: usize, area: Rect) {
+    let menu = BulkAction::menu();
+    let popup = centered(area, 36, menu.len() as u16 + 2);
+    frame.render_widget(Clear, popup);
+
+    let lines: Vec<Line> = menu
+        .iter()
+        .enumerate()
+        .map(|(i, action)| {
+            if i == cursor {
+                Line::from(vec![
+                    Span::styled("▶ ", Style::default().fg(Color::Cyan)),
+                    Span::styled(
+                        action.label(),
+                        Style::default()
+                            .fg(Color::Cyan)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                ])
+            } else {
+                Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled(action.label(), Style::default().fg(Color::White)),
+                ])
+            }
+        })
+        .collect();
+
+    frame.render_widget(
+        Paragraph::new(lines).block(block("Bulk Action", true)),
+        popup,
+    );
+}
+
+fn draw_prompt(frame: &mut Frame, prompt: &Prompt, area: Rect) {
+    let popup = centered(area, 60, 3);
+    frame.render_widget(Clear, popup);
+
+    let line = Line::from(vec![
+        Span::raw(" "),
+        Span::styled(prompt.input.clone(), Style::default().fg(Color::White)),
+        Span::styled("█", Style::default().fg(Color::Cyan)),
+    ]);
+    frame.render_widget(
+        Paragraph::new(line).block(block(&prompt.title, true)),
+        popup,
+    );
+}
+
+fn draw_bulk_progress(frame: &mut Frame, progress: &BulkProgress, area: Rect) {
+    let dim = Style::default().fg(Color::DarkGray);
+    let key = Style::default().fg(Color::DarkGray);
+    let desc = Style::default().fg(Color::Rgb(100, 100, 100));
+
+    let popup = centered(area, 90, progress.entries.len() as u16 + 4);
+    frame.render_widget(Clear, popup);
+
+    let outer = block(&progress.label, true);
+    let inner = outer.inner(popup);
+    frame.render_widget(outer, popup);
+
+    let [list_area, status_area] =
+        Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(inner);
+
+    let name_width = progress
+        .entries
+        .iter()
+        .map(|e| e.name.len())
+        .max()
+        .unwrap_or(0);
+
+    let lines: Vec<Line> = progress
+        .entries
+        .iter()
+        .map(|entry| {
+            let (icon, color, detail, detail_style) = match &entry.state {
+                BulkState::Running => {
+                    let progress = entry.progress.clone().unwrap_or_default();
+                    ("⋯", Color::Yellow, progress, dim)
+                }
+                BulkState::Done(msg) => ("✓", Color::Green, msg.clone(), dim),
+                BulkState::Failed(err) => (
+                    "✗",
+                    Color::Red,
+                    err.clone(),
+                    Style::default().fg(Color::Red),
+                ),
+            };
+            Line::from(vec![
+                Span::raw(" "),
+                Span::styled(icon, Style::default().fg(color)),
+                Span::raw(" "),
+                Span::styled(
+                    format!("{:<name_width$}  ", entry.name),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(detail, detail_style),
+            ])
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(lines).scroll((progress.scroll as u16, 0)),
+        list_area,
+    );
+
+    let done = progress.finished();
+    let total = progress.entries.len();
+    let failed = progress
+        .entries
+        .iter()
+        .filter(|e| matches!(e.state, BulkState::Failed(_)))
+        .count();
+    let summary = if done < total {
+        Span::styled(
+            format!(" {done}/{total} done  "),
+            Style::default().fg(Color::Yellow),
+        )
+    } else if failed > 0 {
+        Span::styled(
+            format!(" {} succeeded, {failed} failed  ", total - failed),
+            Style::default().fg(Color::Red),
+        )
+    } else {
+        Span::styled(
+            format!(" All {total} succeeded  "),
+            Style::default().fg(Color::Green),
+        )
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(vec![
+            summary,
+            Span::styled("[Esc] ", key),
+            Span::styled("Close", desc),
+        ])),
+        status_area,
+    );
+}
+
+fn draw_branch_cleanup(frame: &mut Frame, cleanup: &BranchCleanup, area: Rect) {
+    let dim = Style::default().fg(Color::DarkGray);
+    let value = Style::default().fg(Color::White);
+    let key = Style::default().fg(Color::DarkGray);
+    let desc = Style::default().fg(Color::Rgb(100, 100, 100));
+
+    let popup = centered(area, 80, area.height.saturating_sub(4));
+    frame.render_widget(Clear, popup);
+
+    let title = if cleanup.all_repos {
+        "Clean Up Branches (all repos)"
+    } else {
+        "Clean Up Branches"
+    };
+    let outer = block(title, true);
+    let inner = outer.inner(popup);
+    frame.render_widget(outer, popup);
+
+    let [list_area, status_area, keys_area] = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(1),
+        Constraint::Length(1),
+    ])
+    .areas(inner);
+
+    let mut lines: Vec<Line> = Vec::new();
+    if cleanup.loading && cleanup.entries.is_empty() {
+        lines.push(Line::from(Span::styled(" Scanning branches...", dim)));
+    } else if cleanup.entries.is_empty() {
+        lines.push(Line::from(Span::styled(" No merged or gone branches", dim)));
+    }
+
+    let repo_width = cleanup
+        .entries
+        .iter()
+        .map(|e| e.branch.repo_name.len())
+        .max()
+        .unwrap_or(0);
+
+    for (i, entry) in cleanup.entries.iter().enumerate() {
+        let cursor = if i == cleanup.cursor { "▶ " } else { "  " };
+        let check = if entry.selected { "[x] " } else { "[ ] " };
+        let reason_color = match entry.branch.reason {
+            crate::git::StaleReason::Merged => Color::Green,
+            crate::git::StaleReason::UpstreamGone => Color::Yellow,
+        };
+        let mut spans = vec![
+            Span::styled(cursor, Style::default().fg(Color::Cyan)),
+            Span::styled(check, value),
+        ];
+        if cleanup.all_repos {
+            spans.push(Span::styled(
+                format!("{:<repo_width$}  ", entry.branch.repo_name),
+                dim,
+            ));
+        }
+        spans.push(Span::styled(entry.branch.branch.clone(), value));
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(
+            entry.branch.reason.label(),
+            Style::default().fg(reason_color),
+        ));
+        if !entry.branch.merged {
+            spans.push(Span::styled(", unmerged", Style::default().fg(Color::Red)));
+        }
+        if let Some(err) = &entry.error {
+            spans.push(Span::raw("  "));
+            spans.push(Span::styled(err.clone(), Style::default().fg(Color::Red)));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    // Keep the cursor row visible
+    let height = list_area.height as usize;
+    let scroll = cleanup.cursor.saturating_sub(height.saturating_sub(1));
+    frame.render_widget(Paragraph::new(lines).scroll((scroll as u16, 0)), list_area);
+
+    let status = match (
+        &cleanup.status,
+        cleanup.loading && !cleanup.entries.is_empty(),
+    ) {
+        (_, true) => Span::styled(" Deleting...", Style::default().fg(Color::Yellow)),
+        _ if cleanup.confirm_unmerged => {
+            let unmerged = cleanup
+                .entries
+                .iter()
+                .filter(|e| e.selected && !e.branch.merged)
+                .count();
+            Span::styled(
+                format!(
+                    " {unmerged} selected branch(es) are unmerged and their commits will be lost. \
+                     Press y to delete, any other key to cancel"
+                ),
+                Style::default().fg(Color::Red),
+            )
+        }
+        (Some(s), false) => Span::styled(format!(" {s}"), Style::default().fg(Color::Green)),
+        (None, false) => {
+            let selected = cleanup.entries.iter().filter(|e| e.selected).count();
+            Span::styled(
+                format!(" {selected} of {} selected", cleanup.entries.len()),
+                dim,
+            )
+        }
+    };
+    frame.render_widget(Paragraph::new(Line::from(status)), status_area);
+
+    let keybinds = vec![
+        Span::styled(" [Space] ", key),
+        Span::styled("Toggle  ", desc),
+        Span::styled("[a] ", key),
+        Span::styled("All  ", desc),
+        Span::styled("[Enter] ", key),
+        Span::styled("Delete  ", desc),
+        Span::styled("[Esc] ", key),
+        Span::styled("Close", desc),
+    ];
+    frame.render_widget(Paragraph::new(Line::from(keybinds)), keys_area);
+}
+
+fn draw_inbox(frame: &mut Frame, inbox: &Inbox, area: Rect) {
+    let dim = Style::default().fg(Color::DarkGray);
+    let header_style = Style::default().add_modifier(Modifier::BOLD);
+    let header = Row::new(vec![
+        Cell::from("Why").style(header_style),
+        Cell::from("Repository").style(header_style),
+        Cell::from("").style(header_style),
+        Cell::from("Title").style(header_style),
+        Cell::from("CI").style(header_style),
+        Cell::from("Updated").style(header_style),
+    ])
+    .style(Style::default().fg(Color::White));
+
+    let rows: Vec<Row> = inbox
+        .items
+        .iter()
+        .map(|item| {
+            let reason_color = match item.reason {
+                InboxReason::ReviewRequested => Color::Magenta,
+                InboxReason::Authored => Color::Cyan,
+                InboxReason::Assigned => Color::Yellow,
+            };
+            let checks = match item.checks {
+                Some(state) => {
+                    let (symbol, style) = check_style(state);
+                    Cell::from(Span::styled(symbol, style))
+                }
+                None => Cell::from(""),
+            };
+            let number = if item.is_pr {
+                format!("!{}", item.number)
+            } else {
+                format!("#{}", item.number)
+            };
+            Row::new(vec![
+                Cell::from(Span::styled(
+                    item.reason.label(),
+                    Style::default().fg(reason_color),
+                )),
+                Cell::from(item.repo.full_name()),
+                Cell::from(Span::styled(number, dim)),
+                Cell::from(item.title.clone()),
+                checks,
+                Cell::from(Span::styled(item.updated_at.clone(), dim)),
+            ])
+        })
+        .collect();
+
+    let repo_width = inbox
+        .items
+        .iter()
+        .map(|i| i.repo.full_name().chars().count() as u16)
+        .max()
+        .unwrap_or(10)
+        .max(10);
+    let widths = [
+        Constraint::Length(8),
+        Constraint::Length(repo_width),
+        Constraint::Length(6),
+        Constraint::Fill(1),
+        Constraint::Length(2),
+        Constraint::Length(16),
+    ];
+
+    let summary = if inbox.pending > 0 {
+        " loading... ".to_string()
+    } else {
+        format!(" {} items ", inbox.items.len())
+    };
+    let mut block = block("My Work", true).title_bottom(Line::from(summary).style(dim));
+    if let Some(error) = inbox.errors.first() {
+        block = block
+            .title_bottom(Line::from(format!(" {error} ")).style(Style::default().fg(Color::Red)));
+    }
+
+    if inbox.items.is_empty() {
+        let text = if inbox.pending > 0 {
+            "  Loading..."
+        } else {
+            "  Nothing needs you right now"
+        };
+        frame.render_widget(Paragraph::new(Span::styled(text, dim)).block(block), area);
+        return;
+    }
+
+    let mut state = TableState::default().with_selected(Some(inbox.cursor));
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(block)
+        .highlight_symbol("▶ ");
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+fn draw_notifications(frame: &mut Frame, app: &App, area: Rect) {
+    let dim = Style::default().fg(Color::DarkGray);
+    let header_style = Style::default().add_modifier(Modifier::BOLD);
+    let header = Row::new(vec![
+        Cell::from("Reason").style(header_style),
+        Cell::from("Repository").style(header_style),
+        Cell::from("Subject").style(header_style),
+        Cell::from("Updated").style(header_style),
+    ])
+    .style(Style::default().fg(Color::White));
+
+    let rows: Vec<Row> = app
+        .notifications
+        .iter()
+        .map(|n| {
+            let kind = match (n.kind.as_str(), n.number) {
+                ("PullRequest", Some(number)) => format!("!{number} "),
+                (_, Some(number)) => format!("#{number} "),
+                (kind, None) => format!("{kind} "),
+            };
+            Row::new(vec![
+                Cell::from(Span::styled(
+                    n.reason.replace('_', " "),
+                    Style::default().fg(Color::Magenta),
+                )),
+                Cell::from(n.repo.full_name()),
+                Cell::from(Line::from(vec![
+                    Span::styled(kind, dim),
+                    Span::raw(n.title.clone()),
+                ])),
+                Cell::from(Span::styled(n.updated_at.clone(), dim)),
+            ])
+        })
+        .collect();
+
+    let repo_width = app
+        .notifications
+        .iter()
+        .map(|n| n.repo.full_name().chars().count() as u16)
+        .max()
+        .unwrap_or(10)
+        .max(10);
+    let widths = [
+        Constraint::Length(16),
+        Constraint::Length(repo_width),
+        Constraint::Fill(1),
+        Constraint::Length(16),
+    ];
+
+    let mut block = block("Notifications", true)
+        .title_bottom(Line::from(format!(" {} unread ", app.notifications.len())).style(dim));
+    if let Some(error) = &app.notification_error {
+        block = block
+            .title_bottom(Line::from(format!(" {error} ")).style(Style::default().fg(Color::Red)));
+    }
+
+    if app.notifications.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Span::styled("  No unread notifications", dim)).block(block),
+            area,
+        );
+        return;
+    }
+
+    let mut state = TableState::default().with_selected(Some(app.notification_cursor));
+    let table = Table::new(rows, widths)
+        .header(header)
+        .block(block)
+        .highlight_symbol("▶ ");
+    frame.render_stateful_widget(table, area, &mut state);
 }
 
 fn status_width(repo: &crate::repo::RepoInfo) -> u16 {
     match &repo.status {
         RepoStatus::Clean => 1,
-        RepoStatus::Dirty { modified, added, deleted } => {
+        RepoStatus::Dirty {
+            modified,
+            added,
+            deleted,
+        } => {
             let w = format!("+{added} ~{modified} -{deleted}");
             w.len() as u16
         }
@@ -143,6 +1270,52 @@ fn status_width(repo: &crate::repo::RepoInfo) -> u16 {
 }
 
 const PAD: u16 = 2;
+/// Width of the bulk-selection marker in front of each repo name.
+const MARK_WIDTH: u16 = 2;
+
+/// Width of the open issue/PR count columns: the widest count, but at
+/// least the header.
+fn count_width(repos: &[crate::repo::RepoInfo]) -> u16 {
+    repos
+        .iter()
+        .filter_map(|r| r.github_data.as_ref())
+        .map(|d| d.open_issues.max(d.open_prs).to_string().len() as u16)
+        .max()
+        .unwrap_or(0)
+        .max(3)
+}
+
+/// Open issue and PR counts for the repo list: blank without a GitHub
+/// remote, `…` while the first fetch is under way and `?` if it failed.
+fn count_cells(repo: &crate::repo::RepoInfo, refreshing: bool) -> (Cell<'static>, Cell<'static>) {
+    let dim = Style::default().fg(Color::DarkGray);
+    let cell =
+        |text: String, style: Style| Cell::from(Line::from(text).right_aligned()).style(style);
+    match (&repo.github_repo, &repo.github_data) {
+        (None, _) => (Cell::from(""), Cell::from("")),
+        (Some(_), Some(data)) => {
+            let count_style = |n: usize| {
+                if n == 0 {
+                    dim
+                } else {
+                    Style::default().fg(Color::White)
+                }
+            };
+            (
+                cell(data.open_issues.to_string(), count_style(data.open_issues)),
+                cell(data.open_prs.to_string(), count_style(data.open_prs)),
+            )
+        }
+        (Some(_), None) => {
+            let marker = if refreshing || repo.github_error.is_none() {
+                "…"
+            } else {
+                "?"
+            };
+            (cell(marker.to_string(), dim), cell(marker.to_string(), dim))
+        }
+    }
+}
 
 fn draw_repo_list(frame: &mut Frame, app: &mut App, area: Rect) {
     let focused = app.active_pane == ActivePane::RepoList;
@@ -153,9 +1326,12 @@ fn draw_repo_list(frame: &mut Frame, app: &mut App, area: Rect) {
     }
 
     let header_style = Style::default().add_modifier(Modifier::BOLD);
+    let count_width = count_width(&app.repos);
     let header = Row::new(vec![
         Cell::from("Name").style(header_style),
         Cell::from("Status").style(header_style),
+        Cell::from(Line::from("Iss").right_aligned()).style(header_style),
+        Cell::from(Line::from("PRs").right_aligned()).style(header_style),
     ])
     .style(Style::default().fg(Color::White));
 
@@ -178,16 +1354,39 @@ fn draw_repo_list(frame: &mut Frame, app: &mut App, area: Rect) {
                 ])),
             };
 
+            let mark = if app.marked.contains(&repo.path) {
+                Span::styled("● ", Style::default().fg(Color::Magenta))
+            } else {
+                Span::raw("  ")
+            };
+            let run = match app.command_runs.get(&repo.path).map(|r| &r.status) {
+                Some(RunStatus::Running) => Span::styled(" ⋯", Style::default().fg(Color::Yellow)),
+                Some(RunStatus::Exited(0)) => Span::styled(" ✓", Style::default().fg(Color::Green)),
+                Some(_) => Span::styled(" ✗", Style::default().fg(Color::Red)),
+                None => Span::raw(""),
+            };
+
+            let (issues, prs) = count_cells(repo, app.github_refreshing(&repo.path));
+            let archived = repo.github_data.as_ref().is_some_and(|d| d.meta.archived);
             Row::new(vec![
-                Cell::from(repo.name.clone()),
+                Cell::from(Line::from(vec![mark, Span::raw(repo.name.clone()), run])),
                 status_cell,
+                issues,
+                prs,
             ])
+            .style(if archived {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default()
+            })
         })
         .collect();
 
     let widths = [
         Constraint::Fill(1),
         Constraint::Length(max_status + PAD),
+        Constraint::Length(count_width + PAD),
+        Constraint::Length(count_width + PAD),
     ];
 
     let repo_count = format!(" {} repos ", app.repos.len());
@@ -195,7 +1394,7 @@ fn draw_repo_list(frame: &mut Frame, app: &mut App, area: Rect) {
         .header(header)
         .block(
             block("Repositories", focused)
-                .title_bottom(Line::from(repo_count).style(Style::default().fg(Color::DarkGray)))
+                .title_bottom(Line::from(repo_count).style(Style::default().fg(Color::DarkGray))),
         )
         .highlight_symbol("▶ ");
 
@@ -215,6 +1414,10 @@ fn draw_info_panel(frame: &mut Frame, app: &mut App, area: Rect) {
     let path_str = repo.path.display().to_string();
     let branches = repo.branches.join(", ");
     let github_repo = repo.github_repo.clone();
+    let repo_path = repo.path.clone();
+    let current_branch = repo.current_branch.clone();
+    let github_data = repo.github_data.clone();
+    let local_default_branch = repo.local_default_branch.clone();
 
     let label = Style::default()
         .fg(Color::Yellow)
@@ -230,7 +1433,12 @@ fn draw_info_panel(frame: &mut Frame, app: &mut App, area: Rect) {
     // Row 1: name + branch + status
     let mut row1 = vec![
         Span::styled(" ", label),
-        Span::styled(repo_name.clone(), Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+        Span::styled(
+            repo_name.clone(),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
         Span::styled("  ", dim),
         Span::styled(branch, value),
         Span::styled("  ", dim),
@@ -239,12 +1447,25 @@ fn draw_info_panel(frame: &mut Frame, app: &mut App, area: Rect) {
         RepoStatus::Clean => {
             row1.push(Span::styled("✓", Style::default().fg(Color::Green)));
         }
-        RepoStatus::Dirty { modified, added, deleted } => {
-            row1.push(Span::styled(format!("+{added}"), Style::default().fg(Color::Green)));
+        RepoStatus::Dirty {
+            modified,
+            added,
+            deleted,
+        } => {
+            row1.push(Span::styled(
+                format!("+{added}"),
+                Style::default().fg(Color::Green),
+            ));
             row1.push(Span::raw(" "));
-            row1.push(Span::styled(format!("~{modified}"), Style::default().fg(Color::Yellow)));
+            row1.push(Span::styled(
+                format!("~{modified}"),
+                Style::default().fg(Color::Yellow),
+            ));
             row1.push(Span::raw(" "));
-            row1.push(Span::styled(format!("-{deleted}"), Style::default().fg(Color::Red)));
+            row1.push(Span::styled(
+                format!("-{deleted}"),
+                Style::default().fg(Color::Red),
+            ));
         }
     }
     lines.push(Line::from(row1));
@@ -260,33 +1481,168 @@ fn draw_info_panel(frame: &mut Frame, app: &mut App, area: Rect) {
         Span::styled(" branches: ", dim),
         Span::styled(branches.clone(), dim),
     ];
-    if let Some((owner, name)) = &github_repo {
+    if let Some(gh) = &github_repo {
         row3.push(Span::styled("  ", dim));
-        row3.push(Span::styled(
-            format!("↗ {owner}/{name}"),
-            link_style,
-        ));
+        row3.push(Span::styled(format!("↗ {}", gh.full_name()), link_style));
+    }
+    if let Some(gh) = &github_repo {
+        let source = app.github_target(&gh.host, Some(&gh.owner)).token_source;
+        let text = source.map_or_else(
+            || "no token".to_string(),
+            |s| format!("token: {}", s.label()),
+        );
+        row3.push(Span::styled(format!("  {text}"), dim));
+    }
+    if let Some(data) = &github_data {
+        if let Some(default_branch) = &data.default_branch {
+            row3.push(Span::styled(format!("  default: {default_branch}"), dim));
+            match &local_default_branch {
+                Some(local) if local != default_branch => {
+                    row3.push(Span::styled(
+                        format!(" ⚠ local: {local}"),
+                        Style::default().fg(Color::Yellow),
+                    ));
+                }
+                _ => {}
+            }
+        }
+        if let Some(ci) = data.ci {
+            let (symbol, style) = check_style(ci);
+            row3.push(Span::styled(format!(" {symbol}"), style));
+        }
     }
     lines.push(Line::from(row3));
 
     // Register click zone for the github link
-    if let Some((owner, name)) = &github_repo {
-        let github_text = format!("↗ {owner}/{name}");
+    if let Some(gh) = &github_repo {
+        let github_text = format!("↗ {}", gh.full_name());
         let branches_text = format!(" branches: {}  ", branches);
         let link_x = area.x + 1 + branches_text.len() as u16;
         let link_row = area.y + 3;
         app.click_zones.push((
             Rect::new(link_x, link_row, github_text.len() as u16, 1),
-            format!("https://github.com/{owner}/{name}"),
+            ClickTarget::Url(gh.web_url()),
         ));
     }
 
-    let info = Paragraph::new(lines)
-        .block(block(&repo_name, false));
+    // Rows 4-5: repository metadata
+    if github_repo.is_some() {
+        let (about, stats) = match &github_data {
+            Some(data) => meta_lines(&data.meta),
+            None => (Line::from(""), Line::from("")),
+        };
+        lines.push(about);
+        lines.push(stats);
+    }
+
+    // Row 6: the PR for the current branch
+    let branch_pr = app
+        .branch_prs
+        .get(&repo_path)
+        .filter(|(b, _)| *b == current_branch)
+        .map(|(_, result)| result);
+    let pr_row = area.y + 1 + lines.len() as u16;
+    match branch_pr {
+        Some(Ok(BranchPr::Open(pr))) => {
+            let pr_text = format!("↗ PR #{}", pr.number);
+            let mut row = vec![
+                Span::styled(" ", dim),
+                Span::styled(pr_text.clone(), link_style),
+            ];
+            if pr.draft {
+                row.push(Span::styled(" draft", dim));
+            }
+            row.push(Span::styled(format!(" {}", pr.title), value));
+            if let Some(state) = pr.check_state() {
+                let (symbol, style) = check_style(state);
+                row.push(Span::styled(format!("  {symbol} checks"), style));
+            }
+            let (review, review_style) = review_summary(pr);
+            row.push(Span::styled(format!("  {review}"), review_style));
+            lines.push(Line::from(row));
+            if let Some(url) = &pr.html_url {
+                app.click_zones.push((
+                    Rect::new(area.x + 2, pr_row, pr_text.chars().count() as u16, 1),
+                    ClickTarget::Url(url.clone()),
+                ));
+            }
+        }
+        Some(Ok(BranchPr::Missing { create_url })) => {
+            let create_text = "↗ create PR [P]";
+            lines.push(Line::from(vec![
+                Span::styled(" no open PR for this branch  ", dim),
+                Span::styled(create_text, link_style),
+            ]));
+            let create_x = area.x + 1 + " no open PR for this branch  ".len() as u16;
+            app.click_zones.push((
+                Rect::new(create_x, pr_row, create_text.chars().count() as u16, 1),
+                ClickTarget::Url(create_url.clone()),
+            ));
+        }
+        Some(Err(e)) => lines.push(Line::from(Span::styled(
+            format!(" PR lookup failed: {e}"),
+            dim,
+        ))),
+        Some(Ok(BranchPr::DefaultBranch)) | None => {}
+    }
+
+    let info = Paragraph::new(lines).block(block(&repo_name, false));
 
     frame.render_widget(info, area);
 }
 
+/// Description with topics, and the repository's counts and flags.
+fn meta_lines(meta: &crate::repo::RepoMeta) -> (Line<'static>, Line<'static>) {
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut about = vec![Span::raw(" ")];
+    if let Some(description) = &meta.description {
+        about.push(Span::styled(
+            description.clone(),
+            Style::default().fg(Color::White),
+        ));
+        about.push(Span::raw(" "));
+    }
+    for topic in &meta.topics {
+        about.push(Span::styled(
+            format!(" #{topic}"),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+
+    let mut stats = vec![Span::styled(
+        format!(
+            " ★ {}  ⑂ {}  ◉ {} watching",
+            meta.stars, meta.forks, meta.watchers
+        ),
+        dim,
+    )];
+    let mut facts: Vec<String> = meta
+        .visibility
+        .iter()
+        .chain(&meta.license)
+        .cloned()
+        .collect();
+    if let Some(pushed) = meta.pushed_at {
+        facts.push(format!(
+            "pushed {}",
+            crate::repo::format_timestamp(pushed.timestamp())
+        ));
+    }
+    for fact in facts {
+        stats.push(Span::styled(format!(" · {fact}"), dim));
+    }
+    let flag = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    if meta.archived {
+        stats.push(Span::styled("  ARCHIVED", flag));
+    }
+    if meta.disabled {
+        stats.push(Span::styled("  DISABLED", flag));
+    }
+    (Line::from(about), Line::from(stats))
+}
+
 fn draw_detail_pane(frame: &mut Frame, app: &mut App, area: Rect) {
     let focused = app.active_pane == ActivePane::Detail;
 
@@ -309,11 +1665,8 @@ fn draw_detail_pane(frame: &mut Frame, app: &mut App, area: Rect) {
         return;
     }
 
-    let [tab_area, content_area] = Layout::vertical([
-        Constraint::Length(1),
-        Constraint::Min(1),
-    ])
-    .areas(inner);
+    let [tab_area, content_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(1)]).areas(inner);
 
     app.tab_bar_area = tab_area;
     app.detail_content_area = content_area;
@@ -321,18 +1674,78 @@ fn draw_detail_pane(frame: &mut Frame, app: &mut App, area: Rect) {
     draw_tab_bar(frame, app.detail_tab, tab_area);
 
     let detail_tab = app.detail_tab;
-    let detail_scroll = app.detail_scroll;
+    // The run list scrolls to keep its cursor row in view
+    let detail_scroll = match app.actions.as_ref().filter(|a| a.log.is_none()) {
+        Some(actions) if detail_tab == DetailTab::Actions => {
+            (actions.cursor + 4).saturating_sub(content_area.height as usize) as u16
+        }
+        // So does an issue or PR list, below its header and filter bar
+        _ if matches!(detail_tab, DetailTab::Issues | DetailTab::Prs)
+            && app.item_view.is_none() =>
+        {
+            (app.item_cursor + ITEM_LIST_HEADER + 3).saturating_sub(content_area.height as usize)
+                as u16
+        }
+        _ => app.detail_scroll,
+    };
+
+    let open_item = app.item_view.as_ref().filter(|v| v.path == repo.path);
 
     // Build lines + collect click zones for the content
     let (lines, zones) = match detail_tab {
-        DetailTab::Changes => (tab_changes_lines(&repo), Vec::new()),
+        DetailTab::Changes => {
+            let cursor = focused.then_some(app.change_cursor);
+            (tab_changes_lines(&repo, cursor), Vec::new())
+        }
         DetailTab::Commits => tab_commits_content(&repo, content_area, detail_scroll),
-        DetailTab::Issues => tab_issues_content(&repo, content_area, detail_scroll),
-        DetailTab::Prs => tab_prs_content(&repo, content_area, detail_scroll),
+        DetailTab::Issues | DetailTab::Prs => match open_item {
+            Some(view) => item_view_content(view, content_area, detail_scroll),
+            None => {
+                let cursor = focused.then_some(app.item_cursor);
+                let refreshing = app.github_refreshing(&repo.path);
+                let list = app.item_list.as_ref().filter(|l| l.path == repo.path);
+                let view = ItemListView {
+                    list,
+                    area: content_area,
+                    scroll: detail_scroll,
+                    cursor,
+                    refreshing,
+                };
+                if detail_tab == DetailTab::Issues {
+                    tab_issues_content(&repo, &view)
+                } else {
+                    tab_prs_content(&repo, &view)
+                }
+            }
+        },
+        DetailTab::Actions => {
+            let cursor = focused.then(|| app.actions.as_ref().map_or(0, |a| a.cursor));
+            tab_actions_content(
+                &repo,
+                app.actions.as_ref(),
+                content_area,
+                detail_scroll,
+                cursor,
+            )
+        }
+        DetailTab::Releases => {
+            tab_releases_content(&repo, app.releases.as_ref(), content_area, detail_scroll)
+        }
+        DetailTab::Output => (
+            tab_output_lines(app.command_runs.get(&repo.path)),
+            Vec::new(),
+        ),
     };
 
     app.click_zones.extend(zones);
 
+    // The Changes tab scrolls to keep its cursor row in view
+    let detail_scroll = if detail_tab == DetailTab::Changes {
+        (app.change_cursor + 2).saturating_sub(content_area.height as usize) as u16
+    } else {
+        detail_scroll
+    };
+
     let content = Paragraph::new(lines)
         .wrap(Wrap { trim: false })
         .scroll((detail_scroll, 0));
@@ -341,13 +1754,6 @@ fn draw_detail_pane(frame: &mut Frame, app: &mut App, area: Rect) {
 }
 
 fn draw_tab_bar(frame: &mut Frame, active: DetailTab, area: Rect) {
-    let tabs = [
-        ("Changes", DetailTab::Changes),
-        ("Commits", DetailTab::Commits),
-        ("Issues", DetailTab::Issues),
-        ("PRs", DetailTab::Prs),
-    ];
-
     let active_style = Style::default()
         .fg(Color::Cyan)
         .add_modifier(Modifier::BOLD);
@@ -356,46 +1762,441 @@ fn draw_tab_bar(frame: &mut Frame, active: DetailTab, area: Rect) {
 
     let mut spans = Vec::new();
     spans.push(Span::raw(" "));
-    for (i, (name, tab)) in tabs.iter().enumerate() {
+    for (i, tab) in DetailTab::ALL.iter().enumerate() {
         if i > 0 {
             spans.push(Span::styled(" │ ", sep_style));
         }
         if *tab == active {
-            spans.push(Span::styled(*name, active_style));
+            spans.push(Span::styled(tab.title(), active_style));
+        } else {
+            spans.push(Span::styled(tab.title(), inactive_style));
+        }
+    }
+
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+fn tab_changes_lines(repo: &crate::repo::RepoInfo, cursor: Option<usize>) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(Color::DarkGray);
+
+    let mut lines = Vec::new();
+    lines.push(Line::from(""));
+
+    if repo.changed_files.is_empty() {
+        lines.push(Line::from(vec![
+            Span::raw(" "),
+            Span::styled("No changes", dim),
+        ]));
+        return lines;
+    }
+
+    for (i, f) in repo.changed_files.iter().enumerate() {
+        let (prefix, rest) = f.split_at(1);
+        let color = match prefix {
+            "M" => Color::Yellow,
+            "A" => Color::Green,
+            "D" => Color::Red,
+            _ => Color::White,
+        };
+        let (marker, rest_style) = if cursor == Some(i) {
+            (
+                Span::styled("▶ ", Style::default().fg(Color::Cyan)),
+                Style::default().fg(Color::White),
+            )
+        } else {
+            (Span::raw("  "), dim)
+        };
+        lines.push(Line::from(vec![
+            marker,
+            Span::styled(prefix.to_string(), Style::default().fg(color)),
+            Span::styled(rest.to_string(), rest_style),
+        ]));
+    }
+
+    lines
+}
+
+/// Remaining API quota of the target (host and token) closest to its
+/// limit. Requests are held back while it is exhausted, so say when they
+/// resume.
+fn rate_limit_span() -> Option<Span<'static>> {
+    let (target, limit) = crate::github::tightest_rate_limit()?;
+    if let Some(wait) = limit.wait() {
+        return Some(Span::styled(
+            format!(
+                "{target} rate limited · resumes in {}",
+                format_duration(wait.as_secs())
+            ),
+            Style::default().fg(Color::Red),
+        ));
+    }
+    let color = if limit.remaining * 10 < limit.limit {
+        Color::Yellow
+    } else {
+        Color::DarkGray
+    };
+    Some(Span::styled(
+        format!("{target} API {}/{}", limit.remaining, limit.limit),
+        Style::default().fg(color),
+    ))
+}
+
+/// `1h02m`, `3m05s` or `42s`.
+fn format_duration(secs: u64) -> String {
+    match secs {
+        s if s >= 3600 => format!("{}h{:02}m", s / 3600, s % 3600 / 60),
+        s if s >= 60 => format!("{}m{:02}s", s / 60, s % 60),
+        s => format!("{s}s"),
+    }
+}
+
+fn tab_actions_content(
+    repo: &crate::repo::RepoInfo,
+    actions: Option<&ActionsView>,
+    area: Rect,
+    scroll: u16,
+    cursor: Option<usize>,
+) -> (Vec<Line<'static>>, Vec<(Rect, ClickTarget)>) {
+    let label = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let dim = Style::default().fg(Color::DarkGray);
+    let error = Style::default().fg(Color::Red);
+
+    let mut lines = vec![Line::from("")];
+    let mut zones = Vec::new();
+
+    let Some(actions) = actions.filter(|_| repo.github_repo.is_some()) else {
+        lines.push(Line::from(vec![
+            Span::raw(" "),
+            Span::styled("No GitHub remote", dim),
+        ]));
+        return (lines, zones);
+    };
+
+    if let Some(log) = &actions.log {
+        lines.push(Line::from(vec![
+            Span::styled(format!(" Failed jobs — {}", log.title), label),
+            Span::styled("  [f] re-run failed  [Esc] back", dim),
+        ]));
+        match &log.jobs {
+            None => lines.push(Line::from(Span::styled(" Loading logs...", dim))),
+            Some(Err(e)) => lines.push(Line::from(Span::styled(format!(" {e}"), error))),
+            Some(Ok(jobs)) if jobs.is_empty() => {
+                lines.push(Line::from(Span::styled(
+                    " No failed jobs in the latest attempt",
+                    dim,
+                )));
+            }
+            Some(Ok(jobs)) => {
+                for job in jobs {
+                    lines.push(Line::from(""));
+                    let mut header = vec![
+                        Span::styled(" ✗ ", error),
+                        Span::styled(
+                            job.name.clone(),
+                            Style::default()
+                                .fg(Color::White)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                    ];
+                    if let Some(step) = &job.failed_step {
+                        header.push(Span::styled(format!("  step: {step}"), dim));
+                    }
+                    lines.push(Line::from(header));
+                    if job.truncated {
+                        lines.push(Line::from(Span::styled(" │ …", dim)));
+                    }
+                    for line in &job.lines {
+                        let style = if line.starts_with("##[error]") {
+                            error
+                        } else {
+                            Style::default().fg(Color::White)
+                        };
+                        lines.push(Line::from(vec![
+                            Span::styled(" │ ", dim),
+                            Span::styled(line.clone(), style),
+                        ]));
+                    }
+                }
+            }
+        }
+        return (lines, zones);
+    }
+
+    lines.push(Line::from(Span::styled(" Workflow runs", label)));
+    lines.push(Line::from(""));
+
+    let runs = match (&actions.runs, &actions.error) {
+        (_, Some(e)) => {
+            lines.push(Line::from(vec![
+                Span::raw(" "),
+                Span::styled(e.clone(), error),
+            ]));
+            return (lines, zones);
+        }
+        (None, None) => {
+            lines.push(Line::from(vec![
+                Span::raw(" "),
+                Span::styled("Loading...", dim),
+            ]));
+            return (lines, zones);
+        }
+        (Some(runs), None) => runs,
+    };
+    if runs.is_empty() {
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled("No workflow runs", dim),
+        ]));
+        return (lines, zones);
+    }
+
+    // The run for the checked-out commit stands out
+    let head = repo.recent_commits.first().map(|c| c.hash.as_str());
+    for (i, run) in runs.iter().enumerate() {
+        let is_head = head.is_some_and(|h| run.head_sha.starts_with(h));
+        let (symbol, state_style) = check_style(run.state);
+        let marker = if cursor == Some(i) {
+            Span::styled("▶ ", Style::default().fg(Color::Cyan))
+        } else {
+            Span::raw("  ")
+        };
+        let name_style = if is_head {
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let mut spans = vec![
+            marker,
+            Span::styled(format!("{symbol} "), state_style),
+            Span::styled(run.workflow.clone(), name_style),
+            Span::styled(format!(" #{}", run.run_number), dim),
+            Span::styled(
+                format!("  {}", run.branch),
+                Style::default().fg(Color::Green),
+            ),
+            Span::styled(format!("  {}", run.event), dim),
+            Span::styled(format!("  {}", run.outcome.replace('_', " ")), state_style),
+        ];
+        if let Some(secs) = run.duration {
+            spans.push(Span::styled(format!("  {}", format_duration(secs)), dim));
+        }
+        if is_head {
+            spans.push(Span::styled(
+                "  HEAD",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        push_zone(&mut zones, lines.len(), area, scroll, &run.html_url);
+        lines.push(Line::from(spans));
+    }
+
+    (lines, zones)
+}
+
+/// Commits of the next-release preview listed before eliding the rest.
+const PREVIEW_COMMITS: usize = 15;
+
+fn tab_releases_content(
+    repo: &crate::repo::RepoInfo,
+    releases: Option<&ReleasesView>,
+    area: Rect,
+    scroll: u16,
+) -> (Vec<Line<'static>>, Vec<(Rect, ClickTarget)>) {
+    let label = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let value = Style::default().fg(Color::White);
+    let dim = Style::default().fg(Color::DarkGray);
+    let clickable = Style::default().fg(Color::Magenta);
+
+    let mut lines = vec![Line::from("")];
+    let mut zones = Vec::new();
+
+    let (Some(gh), Some(view)) = (&repo.github_repo, releases) else {
+        lines.push(Line::from(vec![
+            Span::raw(" "),
+            Span::styled("No GitHub remote", dim),
+        ]));
+        return (lines, zones);
+    };
+    let info = match &view.info {
+        None => {
+            lines.push(Line::from(vec![
+                Span::raw(" "),
+                Span::styled("Loading...", dim),
+            ]));
+            return (lines, zones);
+        }
+        Some(Err(e)) => {
+            lines.push(Line::from(vec![
+                Span::raw(" "),
+                Span::styled(e.clone(), Style::default().fg(Color::Red)),
+            ]));
+            return (lines, zones);
+        }
+        Some(Ok(info)) => info,
+    };
+
+    // Next release preview
+    let preview = &info.preview;
+    let since = match &preview.since_tag {
+        Some(tag) => format!("since {tag}"),
+        None => "no releases yet".to_string(),
+    };
+    lines.push(Line::from(vec![
+        Span::styled(" Next release", label),
+        Span::styled(format!("  {since} on {}", preview.branch), dim),
+    ]));
+    if preview.commits.is_empty() {
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled("Nothing new to release", dim),
+        ]));
+    } else {
+        let action = if view.creating {
+            "creating draft...".to_string()
         } else {
-            spans.push(Span::styled(*name, inactive_style));
+            format!("[D] create draft {}", preview.next_tag)
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    "  {} merged PRs, {} commits  ",
+                    preview.prs.len(),
+                    preview.commits.len()
+                ),
+                value,
+            ),
+            Span::styled(action, Style::default().fg(Color::Cyan)),
+        ]));
+        lines.push(Line::from(""));
+        for pr in &preview.prs {
+            push_zone(
+                &mut zones,
+                lines.len(),
+                area,
+                scroll,
+                &format!("{}/pull/{}", gh.web_url(), pr.number),
+            );
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(format!("#{}", pr.number), clickable),
+                Span::raw(" "),
+                Span::styled(pr.title.clone(), value),
+                Span::styled(format!("  @{}", pr.author), dim),
+            ]));
+        }
+        if !preview.prs.is_empty() {
+            lines.push(Line::from(""));
+        }
+        for (hash, summary) in preview.commits.iter().take(PREVIEW_COMMITS) {
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(hash.clone(), Style::default().fg(Color::Yellow)),
+                Span::raw(" "),
+                Span::styled(summary.clone(), value),
+            ]));
+        }
+        if preview.commits.len() > PREVIEW_COMMITS {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "  … and {} more commits",
+                    preview.commits.len() - PREVIEW_COMMITS
+                ),
+                dim,
+            )));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(" Releases", label)));
+    lines.push(Line::from(""));
+    if info.releases.is_empty() {
+        lines.push(Line::from(vec![Span::raw("  "), Span::styled("None", dim)]));
+    }
+    for release in &info.releases {
+        let mut spans = vec![
+            Span::raw("  "),
+            Span::styled(release.tag.clone(), clickable.add_modifier(Modifier::BOLD)),
+        ];
+        if let Some(name) = release.name.as_ref().filter(|n| **n != release.tag) {
+            spans.push(Span::styled(format!(" {name}"), value));
+        }
+        spans.push(Span::styled(format!("  {}", release.date), dim));
+        if release.draft {
+            spans.push(Span::styled("  draft", Style::default().fg(Color::Yellow)));
+        }
+        if release.prerelease {
+            spans.push(Span::styled(
+                "  pre-release",
+                Style::default().fg(Color::Magenta),
+            ));
+        }
+        if release.assets > 0 {
+            let plural = if release.assets == 1 { "" } else { "s" };
+            spans.push(Span::styled(
+                format!("  {} asset{plural}", release.assets),
+                dim,
+            ));
         }
+        push_zone(&mut zones, lines.len(), area, scroll, &release.html_url);
+        lines.push(Line::from(spans));
     }
 
-    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    (lines, zones)
 }
 
-fn tab_changes_lines(repo: &crate::repo::RepoInfo) -> Vec<Line<'static>> {
+fn tab_output_lines(run: Option<&CommandRun>) -> Vec<Line<'static>> {
     let dim = Style::default().fg(Color::DarkGray);
 
     let mut lines = Vec::new();
     lines.push(Line::from(""));
 
-    if repo.changed_files.is_empty() {
-        lines.push(Line::from(vec![
-            Span::raw(" "),
-            Span::styled("No changes", dim),
-        ]));
-        return lines;
-    }
+    let run = match run {
+        Some(r) => r,
+        None => {
+            lines.push(Line::from(vec![
+                Span::raw(" "),
+                Span::styled("No command run yet — press ! to run one", dim),
+            ]));
+            return lines;
+        }
+    };
 
-    for f in &repo.changed_files {
-        let (prefix, rest) = f.split_at(1);
-        let color = match prefix {
-            "M" => Color::Yellow,
-            "A" => Color::Green,
-            "D" => Color::Red,
-            _ => Color::White,
+    let (status, color) = match &run.status {
+        RunStatus::Running => ("running".to_string(), Color::Yellow),
+        RunStatus::Exited(0) => ("exit 0".to_string(), Color::Green),
+        RunStatus::Exited(code) => (format!("exit {code}"), Color::Red),
+        RunStatus::Failed(err) => (err.clone(), Color::Red),
+    };
+    lines.push(Line::from(vec![
+        Span::styled(" $ ", dim),
+        Span::styled(
+            run.command.clone(),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw("  "),
+        Span::styled(status, Style::default().fg(color)),
+    ]));
+    lines.push(Line::from(""));
+
+    for line in &run.output {
+        let style = if line.stderr {
+            Style::default().fg(Color::Red)
+        } else {
+            Style::default().fg(Color::White)
         };
         lines.push(Line::from(vec![
-            Span::raw("  "),
-            Span::styled(prefix.to_string(), Style::default().fg(color)),
-            Span::styled(rest.to_string(), dim),
+            Span::raw(" "),
+            Span::styled(line.text.clone(), style),
         ]));
     }
 
@@ -406,7 +2207,7 @@ fn tab_commits_content(
     repo: &crate::repo::RepoInfo,
     _area: Rect,
     _scroll: u16,
-) -> (Vec<Line<'static>>, Vec<(Rect, String)>) {
+) -> (Vec<Line<'static>>, Vec<(Rect, ClickTarget)>) {
     let dim = Style::default().fg(Color::DarkGray);
     let value = Style::default().fg(Color::White);
 
@@ -440,15 +2241,181 @@ fn tab_commits_content(
     (lines, Vec::new())
 }
 
-fn tab_issues_content(
+/// How old the shown GitHub data is, and whether a refresh is under way
+/// or failed, since cached data is shown before it is revalidated.
+fn data_age_span(
     repo: &crate::repo::RepoInfo,
+    data: &crate::repo::GitHubData,
+    refreshing: bool,
+) -> Span<'static> {
+    let age = crate::repo::format_timestamp(data.fetched_at.timestamp());
+    let dim = Style::default().fg(Color::DarkGray);
+    if refreshing {
+        Span::styled(format!("  updated {age} · refreshing…"), dim)
+    } else if crate::github::is_offline() {
+        Span::styled(format!("  updated {age} · offline"), dim)
+    } else if let Some(err) = &repo.github_error {
+        Span::styled(
+            format!("  updated {age} · refresh failed: {err}"),
+            Style::default().fg(Color::Yellow),
+        )
+    } else {
+        Span::styled(format!("  updated {age}"), dim)
+    }
+}
+
+/// Rows above the first item of an issue or PR list: spacing, the count
+/// header and the filter bar.
+const ITEM_LIST_HEADER: usize = 5;
+
+/// How an Issues or PRs tab is being drawn.
+struct ItemListView<'a> {
+    list: Option<&'a ItemList>,
     area: Rect,
     scroll: u16,
-) -> (Vec<Line<'static>>, Vec<(Rect, String)>) {
+    cursor: Option<usize>,
+    refreshing: bool,
+}
+
+/// The filter in effect and how many items match it.
+fn filter_bar_line(list: &ItemList) -> Line<'static> {
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut spans = vec![
+        Span::styled(" Filter: ", dim),
+        Span::styled(list.filter.query(), Style::default().fg(Color::Cyan)),
+    ];
+    if let Some(total) = list.total {
+        spans.push(Span::styled(format!("  ({total} matching)"), dim));
+    }
+    spans.push(Span::styled("  [/] edit [v] state [F] save", dim));
+    Line::from(spans)
+}
+
+/// The rows of an issue or PR list: the filtered list once loaded, else
+/// the recent items while the default filter's first page loads.
+fn item_rows(
+    view: &ItemListView,
+    recent: &[crate::repo::GitHubItem],
+    clickable: Style,
+) -> Vec<(u64, Vec<Span<'static>>)> {
+    let value = Style::default().fg(Color::White);
+    let dim = Style::default().fg(Color::DarkGray);
+    let list = match view.list {
+        Some(list) if !list.items.is_empty() => list,
+        Some(list)
+            if list.total.is_some() || list.filter != crate::github::ItemFilter::default() =>
+        {
+            return Vec::new();
+        }
+        _ => {
+            return recent
+                .iter()
+                .map(|item| {
+                    let spans = vec![
+                        Span::styled(format!("#{}", item.number), clickable),
+                        Span::raw(" "),
+                        Span::styled(item.title.clone(), value),
+                    ];
+                    (item.number, spans)
+                })
+                .collect();
+        }
+    };
+    list.items
+        .iter()
+        .map(|item| {
+            let mut spans = vec![
+                Span::styled(format!("#{}", item.number), clickable),
+                Span::raw(" "),
+            ];
+            if !item.open {
+                spans.push(Span::styled("[closed] ", Style::default().fg(Color::Red)));
+            }
+            spans.push(Span::styled(item.title.clone(), value));
+            for label in &item.labels {
+                spans.push(Span::styled(
+                    format!(" [{label}]"),
+                    Style::default().fg(Color::Blue),
+                ));
+            }
+            let mut meta = format!("  {} · {}", item.author, item.updated_at);
+            if item.comments > 0 {
+                meta.push_str(&format!(" · {} comments", item.comments));
+            }
+            spans.push(Span::styled(meta, dim));
+            (item.number, spans)
+        })
+        .collect()
+}
+
+/// Push the filter bar and the rows of an issue or PR list, with a click
+/// zone per visible row opening `{url_base}/{number}`.
+fn push_item_list(
+    lines: &mut Vec<Line<'static>>,
+    zones: &mut Vec<(Rect, ClickTarget)>,
+    view: &ItemListView,
+    rows: Vec<(u64, Vec<Span<'static>>)>,
+    url_base: &str,
+) {
+    let dim = Style::default().fg(Color::DarkGray);
+    if let Some(list) = view.list {
+        lines.push(filter_bar_line(list));
+        lines.push(Line::from(""));
+    }
+    if rows.is_empty() && !view.list.is_some_and(|l| l.loading) {
+        lines.push(Line::from(vec![Span::raw("  "), Span::styled("None", dim)]));
+    }
+    for (i, (number, spans)) in rows.into_iter().enumerate() {
+        let line_idx = lines.len();
+        let marker = if view.cursor == Some(i) {
+            Span::styled("▶ ", Style::default().fg(Color::Cyan))
+        } else {
+            Span::raw("  ")
+        };
+        lines.push(Line::from([vec![marker], spans].concat()));
+        let visual_row = line_idx as i32 - view.scroll as i32;
+        if visual_row >= 0 && (visual_row as u16) < view.area.height {
+            zones.push((
+                Rect::new(
+                    view.area.x,
+                    view.area.y + visual_row as u16,
+                    view.area.width,
+                    1,
+                ),
+                ClickTarget::Url(format!("{url_base}/{number}")),
+            ));
+        }
+    }
+    let Some(list) = view.list else {
+        return;
+    };
+    if let Some(err) = &list.error {
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(err.clone(), Style::default().fg(Color::Red)),
+            Span::styled("  [r] retry", dim),
+        ]));
+    } else if list.loading {
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled("Loading...", dim),
+        ]));
+    } else if let (Some(total), Some(_)) = (list.total, list.next_page) {
+        let more = total.saturating_sub(list.items.len() as u64);
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(format!("{more} more, scroll down to load"), dim),
+        ]));
+    }
+}
+
+fn tab_issues_content(
+    repo: &crate::repo::RepoInfo,
+    view: &ItemListView,
+) -> (Vec<Line<'static>>, Vec<(Rect, ClickTarget)>) {
     let label = Style::default()
         .fg(Color::Yellow)
         .add_modifier(Modifier::BOLD);
-    let value = Style::default().fg(Color::White);
     let dim = Style::default().fg(Color::DarkGray);
     let clickable = Style::default().fg(Color::Green);
 
@@ -457,8 +2424,8 @@ fn tab_issues_content(
 
     lines.push(Line::from(""));
 
-    let (owner, name) = match &repo.github_repo {
-        Some(pair) => pair,
+    let gh = match &repo.github_repo {
+        Some(gh) => gh,
         None => {
             lines.push(Line::from(vec![
                 Span::raw(" "),
@@ -471,44 +2438,41 @@ fn tab_issues_content(
     if let Some(data) = &repo.github_data {
         lines.push(Line::from(vec![
             Span::styled(format!(" Open Issues ({})", data.open_issues), label),
+            data_age_span(repo, data, view.refreshing),
         ]));
         lines.push(Line::from(""));
 
-        if data.recent_issues.is_empty() {
-            lines.push(Line::from(vec![
-                Span::raw("  "),
-                Span::styled("None", dim),
-            ]));
-        } else {
-            for issue in &data.recent_issues {
-                let line_idx = lines.len();
-                lines.push(Line::from(vec![
-                    Span::styled(format!("  #{}", issue.number), clickable),
-                    Span::raw(" "),
-                    Span::styled(issue.title.clone(), value),
-                ]));
-                let visual_row = line_idx as i32 - scroll as i32;
-                if visual_row >= 0 && (visual_row as u16) < area.height {
-                    zones.push((
-                        Rect::new(area.x, area.y + visual_row as u16, area.width, 1),
-                        format!("https://github.com/{owner}/{name}/issues/{}", issue.number),
-                    ));
-                }
-            }
-        }
+        let rows = item_rows(view, &data.recent_issues, clickable);
+        push_item_list(
+            &mut lines,
+            &mut zones,
+            view,
+            rows,
+            &format!("{}/issues", gh.web_url()),
+        );
 
         lines.push(Line::from(""));
-        let new_issue_idx = lines.len();
-        lines.push(Line::from(vec![
-            Span::styled("  + New Issue", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)),
-        ]));
-        let visual_row = new_issue_idx as i32 - scroll as i32;
-        if visual_row >= 0 && (visual_row as u16) < area.height {
+        let visual_row = lines.len() as i32 - view.scroll as i32;
+        if visual_row >= 0 && (visual_row as u16) < view.area.height {
             zones.push((
-                Rect::new(area.x, area.y + visual_row as u16, area.width, 1),
-                format!("https://github.com/{owner}/{name}/issues/new"),
+                Rect::new(
+                    view.area.x,
+                    view.area.y + visual_row as u16,
+                    view.area.width,
+                    1,
+                ),
+                ClickTarget::NewIssue,
             ));
         }
+        lines.push(Line::from(vec![
+            Span::styled(
+                "  + New Issue",
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("  [i]", dim),
+        ]));
     } else if let Some(err) = &repo.github_error {
         lines.push(Line::from(vec![
             Span::raw(" "),
@@ -526,13 +2490,11 @@ fn tab_issues_content(
 
 fn tab_prs_content(
     repo: &crate::repo::RepoInfo,
-    area: Rect,
-    scroll: u16,
-) -> (Vec<Line<'static>>, Vec<(Rect, String)>) {
+    view: &ItemListView,
+) -> (Vec<Line<'static>>, Vec<(Rect, ClickTarget)>) {
     let label = Style::default()
         .fg(Color::Yellow)
         .add_modifier(Modifier::BOLD);
-    let value = Style::default().fg(Color::White);
     let dim = Style::default().fg(Color::DarkGray);
     let clickable = Style::default().fg(Color::Magenta);
 
@@ -541,8 +2503,8 @@ fn tab_prs_content(
 
     lines.push(Line::from(""));
 
-    let (owner, name) = match &repo.github_repo {
-        Some(pair) => pair,
+    let gh = match &repo.github_repo {
+        Some(gh) => gh,
         None => {
             lines.push(Line::from(vec![
                 Span::raw(" "),
@@ -555,31 +2517,18 @@ fn tab_prs_content(
     if let Some(data) = &repo.github_data {
         lines.push(Line::from(vec![
             Span::styled(format!(" Open PRs ({})", data.open_prs), label),
+            data_age_span(repo, data, view.refreshing),
         ]));
         lines.push(Line::from(""));
 
-        if data.recent_prs.is_empty() {
-            lines.push(Line::from(vec![
-                Span::raw("  "),
-                Span::styled("None", dim),
-            ]));
-        } else {
-            for pr in &data.recent_prs {
-                let line_idx = lines.len();
-                lines.push(Line::from(vec![
-                    Span::styled(format!("  #{}", pr.number), clickable),
-                    Span::raw(" "),
-                    Span::styled(pr.title.clone(), value),
-                ]));
-                let visual_row = line_idx as i32 - scroll as i32;
-                if visual_row >= 0 && (visual_row as u16) < area.height {
-                    zones.push((
-                        Rect::new(area.x, area.y + visual_row as u16, area.width, 1),
-                        format!("https://github.com/{owner}/{name}/pull/{}", pr.number),
-                    ));
-                }
-            }
-        }
+        let rows = item_rows(view, &data.recent_prs, clickable);
+        push_item_list(
+            &mut lines,
+            &mut zones,
+            view,
+            rows,
+            &format!("{}/pull", gh.web_url()),
+        );
     } else if let Some(err) = &repo.github_error {
         lines.push(Line::from(vec![
             Span::raw(" "),
@@ -594,3 +2543,352 @@ fn tab_prs_content(
 
     (lines, zones)
 }
+
+/// Where review of a PR stands, for one-line summaries.
+fn review_summary(pr: &PrDetail) -> (&'static str, Style) {
+    let verdicts = || pr.reviews.iter().map(|(_, v)| *v);
+    if verdicts().any(|v| v == ReviewVerdict::ChangesRequested) {
+        ("changes requested", Style::default().fg(Color::Red))
+    } else if verdicts().any(|v| v == ReviewVerdict::Approved) {
+        ("approved", Style::default().fg(Color::Green))
+    } else if !pr.requested_reviewers.is_empty() {
+        ("review requested", Style::default().fg(Color::Yellow))
+    } else {
+        ("no reviews", Style::default().fg(Color::DarkGray))
+    }
+}
+
+fn check_style(state: CheckState) -> (&'static str, Style) {
+    match state {
+        CheckState::Success => ("✓", Style::default().fg(Color::Green)),
+        CheckState::Failure => ("✗", Style::default().fg(Color::Red)),
+        CheckState::Pending => ("●", Style::default().fg(Color::Yellow)),
+        CheckState::Neutral => ("○", Style::default().fg(Color::DarkGray)),
+    }
+}
+
+/// Clickable zone for the line about to be pushed at `lines.len()`, if
+/// it is scrolled into view.
+fn push_zone(
+    zones: &mut Vec<(Rect, ClickTarget)>,
+    line_idx: usize,
+    area: Rect,
+    scroll: u16,
+    url: &str,
+) {
+    let visual_row = line_idx as i32 - scroll as i32;
+    if visual_row >= 0 && (visual_row as u16) < area.height {
+        zones.push((
+            Rect::new(area.x, area.y + visual_row as u16, area.width, 1),
+            ClickTarget::Url(url.to_string()),
+        ));
+    }
+}
+
+fn item_view_content(
+    view: &ItemView,
+    area: Rect,
+    scroll: u16,
+) -> (Vec<Line<'static>>, Vec<(Rect, ClickTarget)>) {
+    let dim = Style::default().fg(Color::DarkGray);
+    match (&view.detail, &view.error) {
+        (Some(ItemDetail::Pr(pr)), _) => pr_detail_content(pr, area, scroll),
+        (Some(ItemDetail::Issue(issue)), _) => {
+            issue_detail_content(issue, view.loading_more, area, scroll)
+        }
+        (None, Some(err)) => (
+            vec![
+                Line::from(""),
+                Line::from(vec![
+                    Span::raw(" "),
+                    Span::styled(
+                        format!("#{}: {err}", view.number),
+                        Style::default().fg(Color::Red),
+                    ),
+                ]),
+            ],
+            Vec::new(),
+        ),
+        (None, None) => (
+            vec![
+                Line::from(""),
+                Line::from(vec![
+                    Span::raw(" "),
+                    Span::styled(format!("Loading #{}...", view.number), dim),
+                ]),
+            ],
+            Vec::new(),
+        ),
+    }
+}
+
+fn labels_line(labels: &[String]) -> Line<'static> {
+    let mut spans = vec![Span::styled(
+        " Labels     ",
+        Style::default().fg(Color::Yellow),
+    )];
+    for l in labels {
+        spans.push(Span::styled(
+            format!("[{l}] "),
+            Style::default().fg(Color::Magenta),
+        ));
+    }
+    Line::from(spans)
+}
+
+fn assignees_line(assignees: &[String]) -> Line<'static> {
+    let names = if assignees.is_empty() {
+        Span::styled("none", Style::default().fg(Color::DarkGray))
+    } else {
+        Span::styled(
+            assignees
+                .iter()
+                .map(|a| format!("@{a}"))
+                .collect::<Vec<_>>()
+                .join(" "),
+            Style::default().fg(Color::White),
+        )
+    };
+    Line::from(vec![
+        Span::styled(" Assignees  ", Style::default().fg(Color::Yellow)),
+        names,
+    ])
+}
+
+fn issue_detail_content(
+    issue: &IssueDetail,
+    loading_more: bool,
+    area: Rect,
+    scroll: u16,
+) -> (Vec<Line<'static>>, Vec<(Rect, ClickTarget)>) {
+    let label = Style::default().fg(Color::Yellow);
+    let value = Style::default().fg(Color::White);
+    let dim = Style::default().fg(Color::DarkGray);
+
+    let mut lines = vec![Line::from("")];
+    let mut zones = Vec::new();
+
+    let (state, state_color) = if issue.open {
+        ("open", Color::Green)
+    } else {
+        ("closed", Color::Magenta)
+    };
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!(" #{} ", issue.number),
+            Style::default().fg(Color::Green),
+        ),
+        Span::styled(issue.title.clone(), value.add_modifier(Modifier::BOLD)),
+        Span::styled(format!("  [{state}]"), Style::default().fg(state_color)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!(" @{}", issue.author),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::styled(format!("  opened {}", issue.created_at), dim),
+    ]));
+    push_zone(&mut zones, lines.len(), area, scroll, &issue.html_url);
+    lines.push(Line::from(Span::styled(
+        format!(" ↗ {}", issue.html_url),
+        Style::default()
+            .fg(Color::Blue)
+            .add_modifier(Modifier::UNDERLINED),
+    )));
+    lines.push(Line::from(""));
+
+    if !issue.labels.is_empty() {
+        lines.push(labels_line(&issue.labels));
+    }
+    lines.push(assignees_line(&issue.assignees));
+    if let Some(milestone) = &issue.milestone {
+        lines.push(Line::from(vec![
+            Span::styled(" Milestone  ", label),
+            Span::styled(milestone.clone(), value),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    if issue.body.trim().is_empty() {
+        lines.push(Line::from(Span::styled(" No description provided.", dim)));
+    } else {
+        lines.extend(markdown::render(&issue.body));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!(" Comments ({})", issue.total_comments),
+        label.add_modifier(Modifier::BOLD),
+    )));
+    for comment in &issue.comments {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" ─ @{}", comment.author),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::styled(format!("  {}", comment.created_at), dim),
+        ]));
+        lines.extend(markdown::render(&comment.body));
+    }
+
+    if issue.next_comment_page.is_some() {
+        lines.push(Line::from(""));
+        let remaining = (issue.total_comments as usize).saturating_sub(issue.comments.len());
+        let text = if loading_more {
+            " Loading more comments...".to_string()
+        } else {
+            format!(" [n] Load more comments ({remaining} more)")
+        };
+        lines.push(Line::from(Span::styled(text, dim)));
+    }
+
+    (lines, zones)
+}
+
+fn pr_detail_content(
+    pr: &PrDetail,
+    area: Rect,
+    scroll: u16,
+) -> (Vec<Line<'static>>, Vec<(Rect, ClickTarget)>) {
+    let label = Style::default().fg(Color::Yellow);
+    let value = Style::default().fg(Color::White);
+    let dim = Style::default().fg(Color::DarkGray);
+
+    let mut lines = vec![Line::from("")];
+    let mut zones = Vec::new();
+
+    let mut title = vec![
+        Span::styled(
+            format!(" #{} ", pr.number),
+            Style::default().fg(Color::Magenta),
+        ),
+        Span::styled(pr.title.clone(), value.add_modifier(Modifier::BOLD)),
+    ];
+    if pr.draft {
+        title.push(Span::styled("  [draft]", dim));
+    }
+    if !pr.open {
+        title.push(Span::styled("  [closed]", Style::default().fg(Color::Red)));
+    }
+    lines.push(Line::from(title));
+    lines.push(Line::from(vec![
+        Span::styled(format!(" @{}", pr.author), Style::default().fg(Color::Cyan)),
+        Span::styled("  wants to merge ", dim),
+        Span::styled(pr.head.clone(), value),
+        Span::styled(" into ", dim),
+        Span::styled(pr.base.clone(), value),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!(" +{}", pr.additions),
+            Style::default().fg(Color::Green),
+        ),
+        Span::raw(" "),
+        Span::styled(
+            format!("-{}", pr.deletions),
+            Style::default().fg(Color::Red),
+        ),
+        Span::styled(format!("  {} files", pr.changed_files), dim),
+    ]));
+    if let Some(url) = &pr.html_url {
+        push_zone(&mut zones, lines.len(), area, scroll, url);
+        lines.push(Line::from(Span::styled(
+            format!(" ↗ {url}"),
+            Style::default()
+                .fg(Color::Blue)
+                .add_modifier(Modifier::UNDERLINED),
+        )));
+    }
+    lines.push(Line::from(""));
+
+    if !pr.labels.is_empty() {
+        lines.push(labels_line(&pr.labels));
+    }
+    if !pr.assignees.is_empty() {
+        lines.push(assignees_line(&pr.assignees));
+    }
+
+    let merge_color = match pr.mergeability {
+        crate::github::Mergeability::Clean => Color::Green,
+        crate::github::Mergeability::Unknown => Color::DarkGray,
+        crate::github::Mergeability::Unstable | crate::github::Mergeability::Behind => {
+            Color::Yellow
+        }
+        _ => Color::Red,
+    };
+    lines.push(Line::from(vec![
+        Span::styled(" Merge      ", label),
+        Span::styled(pr.mergeability.label(), Style::default().fg(merge_color)),
+    ]));
+
+    let mut reviews = vec![Span::styled(" Reviews    ", label)];
+    if pr.reviews.is_empty() && pr.requested_reviewers.is_empty() {
+        reviews.push(Span::styled("none", dim));
+    }
+    for (reviewer, verdict) in &pr.reviews {
+        let (icon, color) = match verdict {
+            ReviewVerdict::Approved => ("✓", Color::Green),
+            ReviewVerdict::ChangesRequested => ("✗", Color::Red),
+            ReviewVerdict::Commented => ("💬", Color::White),
+            ReviewVerdict::Dismissed => ("−", Color::DarkGray),
+        };
+        reviews.push(Span::styled(
+            format!("{icon} {reviewer}  "),
+            Style::default().fg(color),
+        ));
+    }
+    for reviewer in &pr.requested_reviewers {
+        reviews.push(Span::styled(
+            format!("● {reviewer}  "),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    lines.push(Line::from(reviews));
+
+    let mut checks = vec![Span::styled(" Checks     ", label)];
+    match pr.check_state() {
+        Some(state) => {
+            let (icon, style) = check_style(state);
+            let passed = pr
+                .checks
+                .iter()
+                .filter(|c| c.state == CheckState::Success)
+                .count();
+            checks.push(Span::styled(
+                format!("{icon} {passed}/{} passed", pr.checks.len()),
+                style,
+            ));
+        }
+        None => checks.push(Span::styled("none", dim)),
+    }
+    lines.push(Line::from(checks));
+    // Failing and running checks first, since those are the ones to look at
+    let mut sorted: Vec<_> = pr.checks.iter().collect();
+    sorted.sort_by_key(|c| match c.state {
+        CheckState::Failure => 0,
+        CheckState::Pending => 1,
+        CheckState::Success => 2,
+        CheckState::Neutral => 3,
+    });
+    for check in sorted {
+        let (icon, style) = check_style(check.state);
+        if let Some(url) = &check.url {
+            push_zone(&mut zones, lines.len(), area, scroll, url);
+        }
+        lines.push(Line::from(vec![
+            Span::raw("            "),
+            Span::styled(format!("{icon} "), style),
+            Span::styled(check.name.clone(), value),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    if pr.body.trim().is_empty() {
+        lines.push(Line::from(Span::styled(" No description provided.", dim)));
+    } else {
+        lines.extend(markdown::render(&pr.body));
+    }
+
+    (lines, zones)
+}